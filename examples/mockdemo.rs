@@ -0,0 +1,44 @@
+// mockdemo.rs
+use chemapp_rs::{Calculator,ChemAppBackend,Condition,ResultQuantity,TargetVariable};
+use chemapp_rs::backend::{MockBackend,MockPhase,MockConstituent,MockResults};
+
+pub fn main(){
+	/**********************************************************************************************************************/
+	// a Cu-Ni system with a liquid, a substitutional solid and a Cu2O-like compound; no ChemApp library is needed
+	let backend = MockBackend::new()
+		.component("Cu", 63.546)
+		.component("Ni", 58.693)
+		.phase(MockPhase::new("LIQUID", "RKMP").constituent(MockConstituent::new("Cu", &[1.0, 0.0])).constituent(MockConstituent::new("Ni", &[0.0, 1.0])))
+		.phase(MockPhase::new("FCC_A1", "RKMP").constituent(MockConstituent::new("Cu", &[1.0, 0.0])).constituent(MockConstituent::new("Ni", &[0.0, 1.0])))
+		.solver(|input| {
+			let temp = input.condition("T", 0, 0).unwrap_or(0.0);
+			let amounts = input.incoming_amounts(2);
+			let total : f64 = amounts.iter().sum();
+			let indexp = if temp > 1500.0 {1} else {2};
+			let mut results = MockResults::new();
+			results.insert(("A".to_owned(), indexp, 0), total);
+			results.insert(("AC".to_owned(), indexp, 0), 1.0);
+			for k in 0..2 {
+				results.insert(("A".to_owned(), indexp, k+1), amounts[k]);
+				results.insert(("X".to_owned(), indexp, k+1), amounts[k]/total);
+			}
+			return Ok(results);
+		});
+	/**********************************************************************************************************************/
+	let calculator = Calculator::from_backend(backend).unwrap();
+	let _ = calculator.engine.set_condition(Condition::T,  0, 0, 1200.0).unwrap();
+	let _ = calculator.engine.set_condition(Condition::IA, 0, 1, 0.3).unwrap();
+	let _ = calculator.engine.set_condition(Condition::IA, 0, 2, 0.7).unwrap();
+	let _ = calculator.engine.calculate(TargetVariable::None, 0, 0, (0.0, 0.0)).unwrap();
+	/**********************************************************************************************************************/
+	for (k, phase) in calculator.phases().enumerate() {
+		println!("{:<10} {:>10.4} {:>10.4}", phase.name(), phase.a(), calculator.engine.get_result(ResultQuantity::X, k+1, 2).unwrap());
+	}
+	println!("{:?}", calculator.engine.tqinp("BCC_A2"));
+	/**********************************************************************************************************************/
+	// the same calculation by name, validated before anything is passed to the backend
+	let snapshot = calculator.problem().temperature(1600.0).component("Cu", 0.3).component("Ni", 0.7).solve().unwrap();
+	println!("{:?}", snapshot.phase("LIQUID").map(|p| p.a));
+	println!("{:?}", calculator.problem().temperature(1600.0).component("Co", 1.0).validate());
+	/**********************************************************************************************************************/
+}
@@ -0,0 +1,305 @@
+// chemapp_rs::abi.rs
+
+//! Binary interface of the Fortran routines. ChemApp builds differ in the width of the Fortran `INTEGER` (4 or 8 bytes), in the type and the position of the hidden `CHARACTER` length arguments
+//! (right after every string or at the end of the argument list) and in the calling convention; a mismatch silently corrupts the arguments. `AbiProfile` describes a build and is selected when an `Engine` is created.
+//!
+//! Every scalar `INTEGER` argument is passed through an 8-byte slot (`FInt`) regardless of the profile, so that a library writing 8 bytes never overruns it; the profile decides which part of the slot carries the value.
+//! `INTEGER` arrays are contiguous in Fortran and cannot use the slots, they are allocated with the element width of the profile (`IntArray`).
+
+use std::fmt;
+use serde::{Serialize, Deserialize};
+
+/// An `INTEGER` argument slot, wide enough for both integer widths.
+pub(crate) type FInt = i64;
+
+/// A half of a slot the library has not written to (see `Engine::probe_abi`).
+const SENTINEL_HALF : u32 = 0x5A5A_5A5A;
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Width of a Fortran integer.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Serialize,Deserialize)]
+pub enum IntegerWidth {
+	/// `INTEGER*4`
+	I4,
+	/// `INTEGER*8`
+	I8,
+}
+
+impl IntegerWidth {
+	
+	/// the width of `usize` on the current target
+	pub fn pointer()->IntegerWidth {
+		#[cfg(target_pointer_width = "64")]
+		return IntegerWidth::I8;
+		#[cfg(not(target_pointer_width = "64"))]
+		return IntegerWidth::I4;
+	}
+	
+	/// size in bytes
+	pub fn bytes(&self)->usize {
+		match self {
+			Self::I4 => {return 4;}
+			Self::I8 => {return 8;}
+		}
+	}
+	
+}
+
+/// Position of the hidden `CHARACTER` length arguments.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Serialize,Deserialize)]
+pub enum LengthPosition {
+	/// right after the string they belong to (Intel / Compaq Visual Fortran on Windows)
+	AfterString,
+	/// after all the other arguments, in the order of the strings (gfortran, Intel Fortran on Linux)
+	Trailing,
+}
+
+/// Calling convention of the exported routines.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Serialize,Deserialize)]
+pub enum CallingConvention {
+	/// `extern "system"` (`stdcall` on 32-bit Windows)
+	System,
+	/// `extern "C"`
+	C,
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Fortran binary interface of a ChemApp build.
+/// The wrappers exist in two layouts, `extern "system"` with the lengths after the strings and `extern "C"` with trailing lengths, so the calling convention follows from the position of the lengths (`convention`);
+/// the lengths are passed as `usize` (`length`), which also covers the 4-byte lengths of some 64-bit builds (the callee reads the low half of the register or the stack slot).
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Serialize,Deserialize)]
+pub struct AbiProfile {
+	/// width of `INTEGER` arguments
+	pub integer: IntegerWidth,
+	/// position of the hidden string lengths
+	pub position: LengthPosition,
+}
+
+impl Default for AbiProfile {
+	fn default()->AbiProfile {
+		return AbiProfile::platform();
+	}
+}
+
+impl fmt::Display for AbiProfile {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
+		return write!(f, "INTEGER*{}, {}-byte string lengths {}, {:?} calling convention", self.integer.bytes(), self.length().bytes(), match self.position {LengthPosition::AfterString => "after every string", LengthPosition::Trailing => "trailing"}, self.convention());
+	}
+}
+
+impl AbiProfile {
+	
+	/// The profile of the official ChemApp builds for the current platform (pointer-sized integers and string lengths; lengths after the strings and `stdcall` on Windows, trailing lengths and the C convention elsewhere).
+	pub fn platform()->AbiProfile {
+		#[cfg(target_family="windows")]
+		return AbiProfile {
+			integer: IntegerWidth::pointer(),
+			position: LengthPosition::AfterString,
+		};
+		#[cfg(not(target_family="windows"))]
+		return AbiProfile {
+			integer: IntegerWidth::pointer(),
+			position: LengthPosition::Trailing,
+		};
+	}
+	
+	/// The same profile with another `INTEGER` width.
+	pub fn with_integer(mut self, integer: IntegerWidth)->AbiProfile {
+		self.integer = integer;
+		return self;
+	}
+	
+	/// The same profile with another position of the hidden string lengths.
+	pub fn with_position(mut self, position: LengthPosition)->AbiProfile {
+		self.position = position;
+		return self;
+	}
+	
+	/// the type of the hidden string lengths, always `usize`
+	pub fn length(&self)->IntegerWidth {
+		return IntegerWidth::pointer();
+	}
+	
+	/// the calling convention of the wrappers for the position of the string lengths
+	pub fn convention(&self)->CallingConvention {
+		match self.position {
+			LengthPosition::AfterString => {return CallingConvention::System;}
+			LengthPosition::Trailing    => {return CallingConvention::C;}
+		}
+	}
+	
+	/*************************************************************************************************************************************************************************************************/
+	
+	/// Place an integer argument into a slot.
+	pub(crate) fn encode<T: TryInto<FInt>>(&self, value: T)->FInt {
+		let value = value.try_into().unwrap_or(FInt::MAX);
+		match self.integer {
+			IntegerWidth::I8 => {return value;}
+			// a 4-byte INTEGER is read from the first 4 bytes of the slot
+			IntegerWidth::I4 if cfg!(target_endian="big") => {return ((value as i32) as FInt) << 32;}
+			IntegerWidth::I4 => {return value as i32 as FInt;}
+		}
+	}
+	
+	/// Read an integer written by the library into a slot (sign-extended for `INTEGER*4`).
+	pub(crate) fn decode(&self, slot: FInt)->FInt {
+		match self.integer {
+			IntegerWidth::I8 => {return slot;}
+			IntegerWidth::I4 if cfg!(target_endian="big") => {return (slot >> 32) as i32 as FInt;}
+			IntegerWidth::I4 => {return slot as i32 as FInt;}
+		}
+	}
+	
+	/// Read a count or an index written by the library (negative values are clamped to zero).
+	pub(crate) fn index(&self, slot: FInt)->usize {
+		return self.decode(slot).max(0) as usize;
+	}
+	
+	/// Read a 32-bit integer written by the library.
+	pub(crate) fn int32(&self, slot: FInt)->i32 {
+		return self.decode(slot) as i32;
+	}
+	
+	/// A slot prefilled with a pattern for `written_width`.
+	pub(crate) fn sentinel()->FInt {
+		return (((SENTINEL_HALF as u64) << 32) | SENTINEL_HALF as u64) as FInt;
+	}
+	
+	/// Number of bytes the library has written into a slot prefilled with `sentinel`, `None` if the slot is untouched.
+	pub(crate) fn written_width(slot: FInt)->Option<IntegerWidth> {
+		let bits = slot as u64;
+		let (first, second) = if cfg!(target_endian="big") {((bits >> 32) as u32, bits as u32)} else {(bits as u32, (bits >> 32) as u32)};
+		if first == SENTINEL_HALF && second == SENTINEL_HALF {
+			return None;
+		}
+		if second == SENTINEL_HALF {
+			return Some(IntegerWidth::I4);
+		}
+		return Some(IntegerWidth::I8);
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// An `INTEGER` array argument with the element width of the profile.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub(crate) enum IntArray {
+	I4(Vec<i32>),
+	I8(Vec<i64>),
+}
+
+impl IntArray {
+	
+	/// An array of `len` zeros (at least one element, so that the pointer is always valid).
+	pub(crate) fn zeros(abi: &AbiProfile, len: usize)->IntArray {
+		match abi.integer {
+			IntegerWidth::I4 => {return IntArray::I4(vec![0; len.max(1)]);}
+			IntegerWidth::I8 => {return IntArray::I8(vec![0; len.max(1)]);}
+		}
+	}
+	
+	/// the first element, passed as the array argument
+	pub(crate) fn as_mut_ptr(&mut self)->*mut FInt {
+		match self {
+			Self::I4(values) => {return values.as_mut_ptr() as *mut FInt;}
+			Self::I8(values) => {return values.as_mut_ptr();}
+		}
+	}
+	
+	/// the element `k` (sign-extended for `INTEGER*4`)
+	pub(crate) fn get(&self, k: usize)->FInt {
+		match self {
+			Self::I4(values) => {return values[k] as FInt;}
+			Self::I8(values) => {return values[k];}
+		}
+	}
+	
+	/// a count or an index at `k` (negative values are clamped to zero)
+	pub(crate) fn index(&self, k: usize)->usize {
+		return self.get(k).max(0) as usize;
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+	
+	use super::*;
+	
+	fn profile(integer: IntegerWidth)->AbiProfile {
+		return AbiProfile::platform().with_integer(integer);
+	}
+	
+	#[test]
+	fn encode_decode_round_trip(){
+		for integer in [IntegerWidth::I4, IntegerWidth::I8] {
+			let abi = profile(integer);
+			for value in [0i64, 1, 42, -1, -1000, i32::MAX as i64, i32::MIN as i64] {
+				assert_eq!(abi.decode(abi.encode(value)), value, "{:?} {}", integer, value);
+			}
+		}
+		let abi = profile(IntegerWidth::I8);
+		assert_eq!(abi.decode(abi.encode(1i64 << 40)), 1i64 << 40);
+		assert_eq!(abi.encode(usize::MAX), FInt::MAX);
+	}
+	
+	#[test]
+	fn encode_places_i4_in_the_first_bytes(){
+		let slot = profile(IntegerWidth::I4).encode(7);
+		assert_eq!(&slot.to_ne_bytes()[0..4], &7i32.to_ne_bytes());
+	}
+	
+	#[test]
+	fn decode_ignores_the_unwritten_half(){
+		let abi = profile(IntegerWidth::I4);
+		let mut bytes = AbiProfile::sentinel().to_ne_bytes();
+		bytes[0..4].copy_from_slice(&(-5i32).to_ne_bytes());
+		let slot = FInt::from_ne_bytes(bytes);
+		assert_eq!(abi.decode(slot), -5);
+		assert_eq!(abi.int32(slot), -5);
+		assert_eq!(abi.index(slot), 0);
+	}
+	
+	#[test]
+	fn written_width_of_sentinel_slots(){
+		assert_eq!(AbiProfile::written_width(AbiProfile::sentinel()), None);
+		let mut bytes = AbiProfile::sentinel().to_ne_bytes();
+		bytes[0..4].copy_from_slice(&3i32.to_ne_bytes());
+		assert_eq!(AbiProfile::written_width(FInt::from_ne_bytes(bytes)), Some(IntegerWidth::I4));
+		assert_eq!(AbiProfile::written_width(3), Some(IntegerWidth::I8));
+		assert_eq!(AbiProfile::written_width(0), Some(IntegerWidth::I8));
+	}
+	
+	#[test]
+	fn convention_follows_the_length_position(){
+		assert_eq!(AbiProfile::platform().with_position(LengthPosition::AfterString).convention(), CallingConvention::System);
+		assert_eq!(AbiProfile::platform().with_position(LengthPosition::Trailing).convention(), CallingConvention::C);
+		assert_eq!(AbiProfile::platform().with_integer(IntegerWidth::I4).length().bytes(), std::mem::size_of::<usize>());
+	}
+	
+	#[test]
+	fn int_array_is_packed_by_width(){
+		let mut array = IntArray::zeros(&profile(IntegerWidth::I4), 3);
+		unsafe {
+			let first = array.as_mut_ptr() as *mut i32;
+			*first.add(1) = 9;
+			*first.add(2) = -2;
+		}
+		assert_eq!(array.get(1), 9);
+		assert_eq!(array.get(2), -2);
+		assert_eq!(array.index(2), 0);
+		let mut array = IntArray::zeros(&profile(IntegerWidth::I8), 2);
+		unsafe {*array.as_mut_ptr().add(1) = 1i64 << 33;}
+		assert_eq!(array.get(1), 1i64 << 33);
+	}
+	
+}
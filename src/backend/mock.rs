@@ -0,0 +1,973 @@
+// chemapp_rs::backend::mock.rs
+
+//! An in-process scripted ChemApp replacement. `MockBackend` holds a canned thermochemical system (system components, phases, phase constituents, sublattices) and answers the `tq...` queries from it;
+//! equilibrium results are either canned values or produced by a user-supplied solver closure which receives the conditions entered since the last `tqremc`.
+//! `tqmap` scans the mapping range with the solver and returns the limits and every temperature (pressure) where the set of phases with a positive amount changes.
+//! Error numbers follow the native ones (e.g. 303 for an unknown phase name, 402 for a phase index out of range, 510 for a temperature which is not positive), so error handling paths of the high-level API can be exercised as well.
+
+use std::fmt;
+use std::cell::{RefCell};
+use std::collections::{HashMap};
+
+use crate::{SystemDimensions};
+use crate::error::{ChemAppError};
+use crate::backend::ChemAppBackend;
+use crate::units::{UnitManager, FileKind};
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Results of a mock equilibrium calculation: `(option, indexp, indexc) -> value`, using the same option strings and index conventions as `tqgetr`.
+pub type MockResults = HashMap<(String,usize,usize),f64>;
+
+/// A solver closure called by `tqce` and its variants; returns the results or a native error number.
+pub type MockSolver = Box<dyn Fn(&MockInput)->Result<MockResults,usize> + Send>;
+
+/// number of grid intervals a `tqmap` mapping is scanned on before bisecting the changes of the stable phases
+const MAP_STEPS : usize = 100;
+
+/// A standard-state property of a phase constituent returned by `tqgdpc` (dimensionless, e.g. G/RT) as a function of the temperature condition.
+pub type MockProperty = Box<dyn Fn(f64)->f64 + Send>;
+
+/// the temperature `tqgdpc` is evaluated at when no temperature condition is entered (the ChemApp default)
+const DEFAULT_TEMPERATURE : f64 = 1000.0;
+
+fn native(errcode: usize)->ChemAppError {
+	return ChemAppError::native(errcode);
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// A canned phase constituent.
+#[derive(Debug,Clone)]
+pub struct MockConstituent {
+	pub name   : String,
+	/// stoichiometry in terms of the system components
+	pub stoic  : Vec<f64>,
+	pub charge : i32,
+	pub status : String,
+	/// `true` if the constituent can be used as an incoming species
+	pub incoming : bool,
+}
+
+impl MockConstituent {
+
+	/// create a new constituent with the given stoichiometry (status 'ENTERED')
+	pub fn new(name: &str, stoic: &[f64])->Self {
+		return Self {
+			name     : name.to_owned(),
+			stoic    : stoic.to_vec(),
+			charge   : 0,
+			status   : "ENTERED".to_owned(),
+			incoming : true,
+		};
+	}
+
+	/// set the charge
+	pub fn charge(mut self, charge: i32)->Self {
+		self.charge = charge;
+		return self;
+	}
+
+}
+
+/// A canned phase.
+#[derive(Debug,Clone)]
+pub struct MockPhase {
+	pub name   : String,
+	pub model  : String,
+	pub status : String,
+	pub constituents : Vec<MockConstituent>,
+	/// species names per sublattice (empty for phases without sublattices)
+	pub sublattices  : Vec<Vec<String>>,
+}
+
+impl MockPhase {
+
+	/// create a new phase (status 'ENTERED') without constituents
+	pub fn new(name: &str, model: &str)->Self {
+		return Self {
+			name   : name.to_owned(),
+			model  : model.to_owned(),
+			status : "ENTERED".to_owned(),
+			constituents : Vec::new(),
+			sublattices  : Vec::new(),
+		};
+	}
+
+	/// a stoichiometric (`PURE`) phase with a single constituent of the same name
+	pub fn pure(name: &str, stoic: &[f64])->Self {
+		return Self::new(name, "PURE").constituent(MockConstituent::new(name, stoic));
+	}
+
+	/// add a phase constituent
+	pub fn constituent(mut self, constituent: MockConstituent)->Self {
+		self.constituents.push(constituent);
+		return self;
+	}
+
+	/// add a sublattice with its species
+	pub fn sublattice<T: AsRef<str>>(mut self, species: &[T])->Self {
+		self.sublattices.push(species.iter().map(|s| s.as_ref().to_owned()).collect());
+		return self;
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// The input of a mock equilibrium calculation passed to the solver closure.
+#[derive(Debug,Clone)]
+pub struct MockInput {
+	/// target variable option passed to `tqce` (" ", "T", "P", "IA")
+	pub target     : String,
+	pub indexp     : usize,
+	pub indexc     : usize,
+	pub vals       : (f64,f64),
+	/// conditions entered with `tqsetc` in the order of their condition numbers
+	pub conditions : Vec<(String,usize,usize,f64)>,
+	/// `true` for `tqcen`/`tqcenl`
+	pub from_previous : bool,
+}
+
+impl MockInput {
+
+	/// the last value entered for a condition, if any
+	pub fn condition(&self, option: &str, indexp: usize, indexc: usize)->Option<f64> {
+		return self.conditions.iter().rev().find(|c| c.0 == option && c.1 == indexp && c.2 == indexc).map(|c| c.3);
+	}
+
+	/// incoming amounts of the system components (`IA` conditions with a zero phase index), `ncomponents` long
+	pub fn incoming_amounts(&self, ncomponents: usize)->Vec<f64> {
+		return (1..ncomponents+1).map(|k| self.condition("IA", 0, k).unwrap_or(0.0)).collect();
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+#[derive(Debug,Default)]
+struct MockState {
+	conditions : Vec<(String,usize,usize,f64)>,
+	limits     : HashMap<String,f64>,
+	units      : HashMap<String,String>,
+	io         : HashMap<String,usize>,
+	results    : Option<MockResults>,
+	streams    : HashMap<String,(f64,f64)>,
+	last_error : usize,
+	initialized : bool,
+	calculations : usize,
+	/// the remaining points of a `tqmap` mapping, last first
+	mapping    : Vec<f64>,
+}
+
+/// An in-process scripted replacement of a ChemApp library.
+pub struct MockBackend {
+	components : Vec<(String,f64)>,
+	phases     : RefCell<Vec<MockPhase>>,
+	state      : RefCell<MockState>,
+	canned     : MockResults,
+	site_fractions : HashMap<(usize,usize,usize),f64>,
+	bond_fractions : HashMap<(usize,usize,usize,usize,usize),f64>,
+	solver     : Option<MockSolver>,
+	properties : HashMap<(String,usize,usize),MockProperty>,
+	units      : UnitManager,
+}
+
+impl fmt::Debug for MockBackend {
+
+	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
+		f.debug_struct("MockBackend")
+			.field("components", &self.components)
+			.field("phases", &self.phases)
+			.field("state", &self.state)
+			.field("solver", &self.solver.is_some())
+			.field("properties", &self.properties.keys().collect::<Vec<_>>())
+			.finish()
+	}
+
+}
+
+impl MockBackend {
+
+	/// An empty system in the initialized state (as if a datafile was loaded); add components and phases with the builder methods.
+	pub fn new()->Self {
+		return Self {
+			components : Vec::new(),
+			phases     : RefCell::new(Vec::new()),
+			state      : RefCell::new(MockState {initialized: true, ..MockState::default()}),
+			canned     : HashMap::new(),
+			site_fractions : HashMap::new(),
+			bond_fractions : HashMap::new(),
+			solver     : None,
+			properties : HashMap::new(),
+			units      : UnitManager::new(),
+		};
+	}
+
+	/// add a system component with its molar mass
+	pub fn component(mut self, name: &str, wmass: f64)->Self {
+		self.components.push((name.to_owned(), wmass));
+		return self;
+	}
+
+	/// add a phase
+	pub fn phase(self, phase: MockPhase)->Self {
+		self.phases.borrow_mut().push(phase);
+		return self;
+	}
+
+	/// a canned `tqgetr` result returned after every calculation (solver results take precedence)
+	pub fn result(mut self, option: &str, indexp: usize, indexc: usize, value: f64)->Self {
+		self.canned.insert((option.to_owned(), indexp, indexc), value);
+		return self;
+	}
+
+	/// a canned sublattice site fraction (`tqgtlc`)
+	pub fn site_fraction(mut self, indexp: usize, indexl: usize, indexs: usize, value: f64)->Self {
+		self.site_fractions.insert((indexp, indexl, indexs), value);
+		return self;
+	}
+
+	/// a canned quadruplet/pair fraction (`tqbond`)
+	pub fn bond_fraction(mut self, indexp: usize, indexa: usize, indexb: usize, indexc: usize, indexd: usize, value: f64)->Self {
+		self.bond_fractions.insert((indexp, indexa, indexb, indexc, indexd), value);
+		return self;
+	}
+
+	/// a solver closure producing the results of every equilibrium calculation
+	pub fn solver<F: Fn(&MockInput)->Result<MockResults,usize> + Send + 'static>(mut self, solver: F)->Self {
+		self.solver = Some(Box::new(solver));
+		return self;
+	}
+
+	/// a `tqgdpc` property (`option` as in `tqgdpc`, e.g. "G" for G/RT) of a constituent as a function of the entered temperature
+	pub fn standard_property<F: Fn(f64)->f64 + Send + 'static>(mut self, option: &str, indexp: usize, indexc: usize, property: F)->Self {
+		self.properties.insert((option.to_owned(), indexp, indexc), Box::new(property));
+		return self;
+	}
+
+	/// conditions currently entered, in the order of their condition numbers
+	pub fn conditions(&self)->Vec<(String,usize,usize,f64)> {
+		return self.state.borrow().conditions.clone();
+	}
+
+	/// number of equilibrium calculations performed so far
+	pub fn calculations(&self)->usize {
+		return self.state.borrow().calculations;
+	}
+
+	/*************************************************************************************************************************************************************************************************/
+	/*************************************************************************************************************************************************************************************************/
+
+	fn fail<T>(&self, errcode: usize)->Result<T,ChemAppError> {
+		self.state.borrow_mut().last_error = errcode;
+		return Err(native(errcode));
+	}
+
+	fn check_initialized(&self)->Result<(),ChemAppError> {
+		if !self.state.borrow().initialized {
+			return self.fail(101);
+		}
+		return Ok(());
+	}
+
+	fn check_unit(&self, unit: usize)->Result<(),ChemAppError> {
+		self.check_initialized()?;
+		if !UnitManager::is_permitted(unit) {
+			return self.fail(105);
+		}
+		return Ok(());
+	}
+
+	fn check_component(&self, indexs: usize)->Result<(),ChemAppError> {
+		self.check_initialized()?;
+		if indexs == 0 || indexs > self.components.len() {
+			return self.fail(401);
+		}
+		return Ok(());
+	}
+
+	fn check_phase(&self, indexp: usize)->Result<(),ChemAppError> {
+		self.check_initialized()?;
+		if indexp == 0 || indexp > self.phases.borrow().len() {
+			return self.fail(402);
+		}
+		return Ok(());
+	}
+
+	fn check_constituent(&self, indexp: usize, indexc: usize)->Result<(),ChemAppError> {
+		self.check_phase(indexp)?;
+		if indexc == 0 || indexc > self.phases.borrow()[indexp-1].constituents.len() {
+			return self.fail(403);
+		}
+		return Ok(());
+	}
+
+	fn check_sublattice(&self, indexp: usize, indexl: usize)->Result<(),ChemAppError> {
+		self.check_phase(indexp)?;
+		if indexl == 0 || indexl > self.phases.borrow()[indexp-1].sublattices.len() {
+			return self.fail(406);
+		}
+		return Ok(());
+	}
+
+	fn wmass_of(&self, stoic: &[f64])->f64 {
+		return stoic.iter().zip(self.components.iter()).map(|(n, c)| n*c.1).sum();
+	}
+
+	fn dimensions(&self)->SystemDimensions {
+		let phases = self.phases.borrow();
+		let mut dims = SystemDimensions::new();
+		dims.nconstituents = phases.iter().map(|p| p.constituents.len() as i32).sum();
+		dims.ncomponents = self.components.len() as i32;
+		dims.nmixtures = phases.iter().filter(|p| p.model != "PURE").count() as i32;
+		dims.nsublattices = phases.iter().map(|p| p.sublattices.len() as i32).sum();
+		dims.nspecies = phases.iter().map(|p| p.sublattices.iter().map(|l| l.len() as i32).sum::<i32>()).sum();
+		return dims;
+	}
+
+	fn equilibrium(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64), from_previous: bool)->Result<(),ChemAppError> {
+		self.check_initialized()?;
+		if from_previous && self.state.borrow().results.is_none() {
+			return self.fail(516);
+		}
+		let input = {
+			let state = self.state.borrow();
+			MockInput {
+				target     : option.trim().to_owned(),
+				indexp,
+				indexc,
+				vals,
+				conditions : state.conditions.clone(),
+				from_previous,
+			}
+		};
+		let results = self.evaluate(&input);
+		return self.store(results);
+	}
+
+	/// the results of the solver (on top of the canned ones) for `input`, or a native error number
+	fn evaluate(&self, input: &MockInput)->Result<MockResults,usize> {
+		if input.target != "T" && input.condition("T", 0, 0).unwrap_or(1.0) <= 0.0 {
+			return Err(510);
+		}
+		if input.target != "P" && input.condition("P", 0, 0).unwrap_or(1.0) <= 0.0 {
+			return Err(509);
+		}
+		if !input.conditions.iter().any(|c| c.0 == "IA") {
+			return Err(508);
+		}
+		let mut results : MockResults = self.canned.clone();
+		if let Some(solver) = &self.solver {
+			results.extend(solver(input)?);
+		}
+		return Ok(results);
+	}
+
+	/// make `results` the current ones, or fail with the native error number
+	fn store(&self, results: Result<MockResults,usize>)->Result<(),ChemAppError> {
+		match results {
+			Ok(results) => {
+				let mut state = self.state.borrow_mut();
+				state.results = Some(results);
+				state.calculations += 1;
+				return Ok(());
+			},
+			Err(errcode) => {
+				self.state.borrow_mut().results = None;
+				return self.fail(errcode);
+			},
+		}
+	}
+
+	/// the results at `value` of the mapping variable `variable` ("T" or "P"), the other conditions being the entered ones
+	fn map_point(&self, variable: &str, value: f64)->Result<MockResults,usize> {
+		let mut conditions : Vec<(String,usize,usize,f64)> = self.state.borrow().conditions.iter().filter(|c| c.0 != variable).cloned().collect();
+		conditions.push((variable.to_owned(), 0, 0, value));
+		let input = MockInput {target: String::new(), indexp: 0, indexc: 0, vals: (0.0, 0.0), conditions, from_previous: false};
+		let mut results = self.evaluate(&input)?;
+		results.insert((variable.to_owned(), 0, 0), value);
+		return Ok(results);
+	}
+
+	/// the indices of the phases with a positive amount in `results`
+	fn stable_phases(&self, results: &MockResults)->Vec<usize> {
+		let nphases = self.phases.borrow().len();
+		return (1..=nphases).filter(|p| results.get(&("A".to_owned(), *p, 0)).copied().unwrap_or(0.0) > 0.0).collect();
+	}
+
+	/// The points of a one-dimensional phase map over `vals`: both limits and every value where the set of stable phases changes,
+	/// found on a grid of `MAP_STEPS` intervals and refined by bisection.
+	fn map_points(&self, variable: &str, vals: (f64,f64))->Result<Vec<f64>,usize> {
+		let (low, high) = vals;
+		let tolerance = 1e-9*(high - low).abs();
+		let mut points = vec![low];
+		let mut previous = self.stable_phases(&self.map_point(variable, low)?);
+		for i in 1..=MAP_STEPS {
+			let (mut a, mut b) = (low + (high - low)*(i - 1) as f64/MAP_STEPS as f64, low + (high - low)*i as f64/MAP_STEPS as f64);
+			let current = self.stable_phases(&self.map_point(variable, b)?);
+			if current == previous {continue;}
+			while (b - a).abs() > tolerance {
+				let middle = 0.5*(a + b);
+				if self.stable_phases(&self.map_point(variable, middle)?) == previous {a = middle;} else {b = middle;}
+			}
+			points.push(0.5*(a + b));
+			previous = current;
+		}
+		points.push(high);
+		return Ok(points);
+	}
+
+}
+
+impl Default for MockBackend {
+	fn default()->Self {
+		return Self::new();
+	}
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+impl ChemAppBackend for MockBackend {
+
+	fn library_name(&self)->&str {
+		return "";
+	}
+
+	fn units(&self)->&UnitManager {
+		return &self.units;
+	}
+
+	fn tqini(&self)->Result<(),ChemAppError> {
+		let mut state = self.state.borrow_mut();
+		*state = MockState::default();
+		state.initialized = true;
+		return Ok(());
+	}
+
+	fn tqvers(&self)->Result<i32,ChemAppError> {
+		return Ok(0);
+	}
+
+	fn tqcprt(&self)->Result<(),ChemAppError> {
+		return Ok(());
+	}
+
+	fn tqsize(&self)->Result<SystemDimensions,ChemAppError> {
+		return Ok(self.dimensions());
+	}
+
+	fn tqused(&self)->Result<SystemDimensions,ChemAppError> {
+		return Ok(self.dimensions());
+	}
+
+	fn tqgio(&self, option: &str)->Result<usize,ChemAppError> {
+		let default = match option {
+			"FILE"  => 10,
+			"ERROR" => 6,
+			"LIST"  => 6,
+			_ => {return self.fail(201);}
+		};
+		return Ok(*self.state.borrow().io.get(option).unwrap_or(&default));
+	}
+
+	fn tqcio(&self, option: &str, unit: usize)->Result<(),ChemAppError> {
+		if !["FILE", "ERROR", "LIST"].contains(&option) {
+			return self.fail(201);
+		}
+		self.state.borrow_mut().io.insert(option.to_owned(), unit);
+		return Ok(());
+	}
+
+	fn tqrfil(&self)->Result<(),ChemAppError> {
+		return self.check_initialized();
+	}
+
+	fn tqrbin(&self)->Result<(),ChemAppError> {
+		return self.check_initialized();
+	}
+
+	fn tqrcst(&self)->Result<(),ChemAppError> {
+		return self.check_initialized();
+	}
+
+	fn tqopen(&self, filename: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(filename, unit, FileKind::Other));
+	}
+
+	fn tqopna(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Ascii));
+	}
+
+	fn tqopnb(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Binary));
+	}
+
+	fn tqopnt(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Transparent));
+	}
+
+	fn open_unit(&self, name: &str, unit: usize, kind: FileKind)->Result<(),ChemAppError> {
+		self.check_unit(unit)?;
+		if name.trim().is_empty() {
+			return self.fail(107);
+		}
+		// an output file is created (empty), the datafiles are not read
+		if kind == FileKind::Other && std::fs::File::create(name).is_err() {
+			return self.fail(107);
+		}
+		return Ok(());
+	}
+
+	fn tqclos(&self, unit: usize)->Result<(),ChemAppError> {
+		return self.units.close(unit, || Ok(()));
+	}
+
+	fn tqgsu(&self, option: &str)->Result<String,ChemAppError> {
+		let default = match option {
+			"Pressure"    => "bar",
+			"Volume"      => "dm3",
+			"Temperature" => "K",
+			"Energy"      => "J",
+			"Amount"      => "mol",
+			_ => {return self.fail(201);}
+		};
+		return Ok(self.state.borrow().units.get(option).cloned().unwrap_or(default.to_owned()));
+	}
+
+	fn tqcsu(&self, option: &str, unit: &str)->Result<(),ChemAppError> {
+		self.tqgsu(option)?;
+		self.state.borrow_mut().units.insert(option.to_owned(), unit.to_owned());
+		return Ok(());
+	}
+
+	fn tqinsc(&self, name: &str)->Result<usize,ChemAppError> {
+		self.check_initialized()?;
+		match self.components.iter().position(|c| c.0 == name) {
+			Some(k) => {return Ok(k+1);}
+			None    => {return self.fail(305);}
+		}
+	}
+
+	fn tqgnsc(&self, indexs: usize)->Result<String,ChemAppError> {
+		self.check_component(indexs)?;
+		return Ok(self.components[indexs-1].0.clone());
+	}
+
+	fn tqnosc(&self)->Result<usize,ChemAppError> {
+		self.check_initialized()?;
+		return Ok(self.components.len());
+	}
+
+	fn tqstsc(&self, indexs: usize)->Result<(Vec<f64>,f64),ChemAppError> {
+		self.check_component(indexs)?;
+		let mut stoic = vec![0.0; self.components.len()];
+		stoic[indexs-1] = 1.0;
+		return Ok((stoic, self.components[indexs-1].1));
+	}
+
+	fn tqinp(&self, name: &str)->Result<usize,ChemAppError> {
+		self.check_initialized()?;
+		let position = self.phases.borrow().iter().position(|p| p.name == name);
+		match position {
+			Some(k) => {return Ok(k+1);}
+			None    => {return self.fail(303);}
+		}
+	}
+
+	fn tqgnp(&self, indexp: usize)->Result<String,ChemAppError> {
+		self.check_phase(indexp)?;
+		return Ok(self.phases.borrow()[indexp-1].name.clone());
+	}
+
+	fn tqmodl(&self, indexp: usize)->Result<String,ChemAppError> {
+		self.check_phase(indexp)?;
+		return Ok(self.phases.borrow()[indexp-1].model.clone());
+	}
+
+	fn tqnop(&self)->Result<usize,ChemAppError> {
+		self.check_initialized()?;
+		return Ok(self.phases.borrow().len());
+	}
+
+	fn tqinpc(&self, indexp: usize, name: &str)->Result<usize,ChemAppError> {
+		self.check_phase(indexp)?;
+		let position = self.phases.borrow()[indexp-1].constituents.iter().position(|c| c.name == name);
+		match position {
+			Some(k) => {return Ok(k+1);}
+			None    => {return self.fail(304);}
+		}
+	}
+
+	fn tqgnpc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError> {
+		self.check_constituent(indexp, indexc)?;
+		return Ok(self.phases.borrow()[indexp-1].constituents[indexc-1].name.clone());
+	}
+
+	fn tqpcis(&self, indexp: usize, indexc: usize)->Result<bool,ChemAppError> {
+		self.check_constituent(indexp, indexc)?;
+		return Ok(self.phases.borrow()[indexp-1].constituents[indexc-1].incoming);
+	}
+
+	fn tqnopc(&self, indexp: usize)->Result<usize,ChemAppError> {
+		self.check_phase(indexp)?;
+		return Ok(self.phases.borrow()[indexp-1].constituents.len());
+	}
+
+	fn tqstpc(&self, indexp: usize, indexc: usize)->Result<(Vec<f64>,f64),ChemAppError> {
+		self.check_constituent(indexp, indexc)?;
+		let mut stoic = self.phases.borrow()[indexp-1].constituents[indexc-1].stoic.clone();
+		stoic.resize(self.components.len(), 0.0);
+		let wmass = self.wmass_of(&stoic);
+		return Ok((stoic, wmass));
+	}
+
+	fn tqchar(&self, indexp: usize, indexc: usize)->Result<i32,ChemAppError> {
+		self.check_constituent(indexp, indexc)?;
+		return Ok(self.phases.borrow()[indexp-1].constituents[indexc-1].charge);
+	}
+
+	fn tqgnlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<String,ChemAppError> {
+		self.check_sublattice(indexp, indexl)?;
+		match self.phases.borrow()[indexp-1].sublattices[indexl-1].get(indexc.wrapping_sub(1)) {
+			Some(name) => {return Ok(name.clone());}
+			None       => {}
+		}
+		return self.fail(407);
+	}
+
+	fn tqnosl(&self, indexp: usize)->Result<usize,ChemAppError> {
+		self.check_phase(indexp)?;
+		return Ok(self.phases.borrow()[indexp-1].sublattices.len());
+	}
+
+	fn tqnolc(&self, indexp: usize, index: usize)->Result<usize,ChemAppError> {
+		self.check_sublattice(indexp, index)?;
+		return Ok(self.phases.borrow()[indexp-1].sublattices[index-1].len());
+	}
+
+	fn tqgsp(&self, indexp: usize)->Result<String,ChemAppError> {
+		self.check_phase(indexp)?;
+		return Ok(self.phases.borrow()[indexp-1].status.clone());
+	}
+
+	fn tqcsp(&self, indexp: usize, status: &str)->Result<(),ChemAppError> {
+		self.check_phase(indexp)?;
+		if !["ENTERED", "DORMANT", "ELIMINATED"].contains(&status) {
+			return self.fail(201);
+		}
+		self.phases.borrow_mut()[indexp-1].status = status.to_owned();
+		return Ok(());
+	}
+
+	fn tqgspc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError> {
+		self.check_constituent(indexp, indexc)?;
+		return Ok(self.phases.borrow()[indexp-1].constituents[indexc-1].status.clone());
+	}
+
+	fn tqcspc(&self, indexp: usize, indexc: usize, status: &str)->Result<(),ChemAppError> {
+		self.check_constituent(indexp, indexc)?;
+		if !["ENTERED", "DORMANT", "ELIMINATED"].contains(&status) {
+			return self.fail(201);
+		}
+		self.phases.borrow_mut()[indexp-1].constituents[indexc-1].status = status.to_owned();
+		return Ok(());
+	}
+
+	fn tqsetc(&self, option: &str, indexp: usize, indexc: usize, val: f64)->Result<i32,ChemAppError> {
+		self.check_initialized()?;
+		if !["T", "P", "VT", "IA", "A", "AC", "MU", "CP", "H", "S", "G", "V"].contains(&option) {
+			return self.fail(201);
+		}
+		if indexp > 0 && indexc > 0 {
+			self.check_constituent(indexp, indexc)?;
+		} else if indexp > 0 {
+			self.check_phase(indexp)?;
+		} else if indexc > 0 {
+			self.check_component(indexc)?;
+		}
+		let mut state = self.state.borrow_mut();
+		// a condition entered again keeps its number
+		if let Some(position) = state.conditions.iter().position(|c| c.0 == option && c.1 == indexp && c.2 == indexc) {
+			state.conditions[position].3 = val;
+			return Ok(position as i32 + 1);
+		}
+		state.conditions.push((option.to_owned(), indexp, indexc, val));
+		return Ok(state.conditions.len() as i32);
+	}
+
+	fn tqremc(&self, numcon: i32)->Result<(),ChemAppError> {
+		self.check_initialized()?;
+		let len = self.state.borrow().conditions.len() as i32;
+		match numcon {
+			-2 | 0 => {self.state.borrow_mut().conditions.clear();}
+			n if n > 0 && n <= len => {self.state.borrow_mut().conditions.remove((n-1) as usize);}
+			_ => {return self.fail(404);}
+		}
+		return Ok(());
+	}
+
+	fn tqsttp(&self, idents: &str, vals: (f64,f64))->Result<(),ChemAppError> {
+		self.check_initialized()?;
+		self.state.borrow_mut().streams.insert(idents.to_owned(), vals);
+		return Ok(());
+	}
+
+	fn tqstca(&self, idents: &str, indexp: usize, indexc: usize, val: f64)->Result<(),ChemAppError> {
+		if !self.state.borrow().streams.contains_key(idents) {
+			return self.fail(202);
+		}
+		if val < 0.0 {
+			return self.fail(503);
+		}
+		self.check_constituent(indexp, indexc)?;
+		return Ok(());
+	}
+
+	fn tqstec(&self, option: &str, indexp: usize, val: f64)->Result<(),ChemAppError> {
+		self.tqsetc(option, indexp, 0, val)?;
+		return Ok(());
+	}
+
+	fn tqstrm(&self, idents: &str)->Result<(),ChemAppError> {
+		match self.state.borrow_mut().streams.remove(idents) {
+			Some(_) => {return Ok(());}
+			None    => {}
+		}
+		return self.fail(202);
+	}
+
+	fn tqce(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError> {
+		return self.equilibrium(option, indexp, indexc, vals, false);
+	}
+
+	fn tqcel(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError> {
+		return self.equilibrium(option, indexp, indexc, vals, false);
+	}
+
+	fn tqcen(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError> {
+		return self.equilibrium(option, indexp, indexc, vals, true);
+	}
+
+	fn tqcenl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError> {
+		return self.equilibrium(option, indexp, indexc, vals, true);
+	}
+
+	fn tqmap(&self, option: &str, _indexp: usize, _indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError> {
+		let variable = match option {
+			"TF" | "TN" => "T",
+			"PF" | "PN" => "P",
+			_ => {return self.fail(201);}
+		};
+		self.check_initialized()?;
+		if option.ends_with('F') {
+			match self.map_points(variable, vals) {
+				Ok(points) => {self.state.borrow_mut().mapping = points.into_iter().rev().collect();},
+				Err(errcode) => {
+					self.state.borrow_mut().mapping.clear();
+					self.state.borrow_mut().results = None;
+					return self.fail(errcode);
+				},
+			}
+		}
+		let value = match self.state.borrow_mut().mapping.pop() {
+			Some(value) => value,
+			None => {return self.fail(516);}
+		};
+		self.store(self.map_point(variable, value))?;
+		return Ok(self.state.borrow().mapping.len());
+	}
+
+	fn tqmapl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError> {
+		return self.tqmap(option, indexp, indexc, vals);
+	}
+
+	fn tqclim(&self, option: &str, val: f64)->Result<(),ChemAppError> {
+		if !["TLOW", "THIGH", "PLOW", "PHIGH"].contains(&option) {
+			return self.fail(201);
+		}
+		self.state.borrow_mut().limits.insert(option.to_owned(), val);
+		return Ok(());
+	}
+
+	fn tqshow(&self)->Result<(),ChemAppError> {
+		return self.check_initialized();
+	}
+
+	fn tqgetr(&self, option: &str, indexp: usize, indexc: usize)->Result<f64,ChemAppError> {
+		self.check_initialized()?;
+		if !["T", "P", "VT", "A", "IA", "AP", "X", "XP", "AC", "MU", "CP", "H", "S", "G", "V", "CPM", "HM", "SM", "GM", "VM"].contains(&option) {
+			return self.fail(201);
+		}
+		// `AP`/`XP` take a system component index together with the phase index
+		if indexp > 0 && indexc > 0 && !["AP", "XP"].contains(&option) {
+			self.check_constituent(indexp, indexc)?;
+		} else {
+			if indexp > 0 {self.check_phase(indexp)?;}
+			if indexc > 0 {self.check_component(indexc)?;}
+		}
+		let value = {
+			let state = self.state.borrow();
+			let results = match &state.results {
+				Some(results) => results,
+				None => {
+					drop(state);
+					return self.fail(516);
+				}
+			};
+			let key = (option.to_owned(), indexp, indexc);
+			match results.get(&key) {
+				Some(value) => Some(*value),
+				None => {
+					match option {
+						"T" | "IA" => state.conditions.iter().rev().find(|c| c.0 == option && c.1 == indexp && c.2 == indexc).map(|c| c.3),
+						"P" => state.conditions.iter().rev().find(|c| c.0 == option).map(|c| c.3).or(Some(1.0)),
+						_ => None,
+					}
+				}
+			}
+		};
+		// a valid result not scripted by `result` or the solver
+		return Ok(value.unwrap_or(0.0));
+	}
+
+	fn tqgdpc(&self, option: &str, indexp: usize, index: usize)->Result<f64,ChemAppError> {
+		self.check_constituent(indexp, index)?;
+		if let Some(property) = self.properties.get(&(option.to_owned(), indexp, index)) {
+			let t = self.state.borrow().conditions.iter().rev().find(|c| c.0 == "T").map(|c| c.3).unwrap_or(DEFAULT_TEMPERATURE);
+			return Ok(property(t));
+		}
+		return Ok(*self.canned.get(&(format!("DPC:{}", option), indexp, index)).unwrap_or(&0.0));
+	}
+
+	fn tqstxp(&self, idents: &str, option: &str)->Result<f64,ChemAppError> {
+		if !self.state.borrow().streams.contains_key(idents) {
+			return self.fail(202);
+		}
+		return Ok(0.0);
+	}
+
+	fn tqgtlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<f64,ChemAppError> {
+		self.check_sublattice(indexp, indexl)?;
+		return Ok(*self.site_fractions.get(&(indexp, indexl, indexc)).unwrap_or(&0.0));
+	}
+
+	fn tqbond(&self, indexp: usize, indexa: usize, indexb: usize, indexc: usize, indexd: usize)->Result<f64,ChemAppError> {
+		self.check_phase(indexp)?;
+		return Ok(*self.bond_fractions.get(&(indexp, indexa, indexb, indexc, indexd)).unwrap_or(&0.0));
+	}
+
+	fn tqerr(&self)->Result<String,ChemAppError> {
+		let errcode = self.state.borrow().last_error;
+		return Ok(native(errcode).description());
+	}
+
+	fn tqgdat(&self, indexp: usize, indexc: usize, option: &str, indexr: usize)->Result<Vec<f64>,ChemAppError> {
+		self.check_constituent(indexp, indexc)?;
+		match self.canned.get(&(format!("DAT:{}", option), indexp, indexc)) {
+			Some(value) => {return Ok(vec![*value]);}
+			None        => {return self.fail(1000);}
+		}
+	}
+
+	fn tqlpar(&self, indexp: usize, option: &str)->Result<Vec<String>,ChemAppError> {
+		self.check_phase(indexp)?;
+		return Ok(Vec::new());
+	}
+
+	fn tqgpar(&self, indexp: usize, option: &str, indexx: usize)->Result<Vec<Vec<f64>>,ChemAppError> {
+		self.check_phase(indexp)?;
+		return self.fail(1021);
+	}
+
+	fn tqcdat(&self, i1: usize, i2: usize, i3: usize, i4: usize, i5: usize, val: f64)->Result<(),ChemAppError> {
+		return self.check_initialized();
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// A Cu-Ni system for the tests of the calculation modules: liquid above 1500 K, FCC below, the incoming amounts all going to the stable phase.
+#[cfg(test)]
+pub(crate) fn cu_ni()->MockBackend {
+	return cu_ni_with(|input| {
+		let amounts = input.incoming_amounts(2);
+		let total : f64 = amounts.iter().sum();
+		let indexp = if input.condition("T", 0, 0).unwrap_or(0.0) > 1500.0 {1} else {2};
+		let mut results = MockResults::new();
+		results.insert(("A".to_owned(), indexp, 0), total);
+		for k in 0..2 {
+			results.insert(("A".to_owned(), indexp, k+1), amounts[k]);
+			results.insert(("X".to_owned(), indexp, k+1), amounts[k]/total);
+		}
+		return Ok(results);
+	});
+}
+
+/// The components and phases of `cu_ni` (LIQUID and FCC_A1) with another solver.
+#[cfg(test)]
+pub(crate) fn cu_ni_with<F: Fn(&MockInput)->Result<MockResults,usize> + Send + 'static>(solver: F)->MockBackend {
+	return MockBackend::new()
+		.component("Cu", 63.546)
+		.component("Ni", 58.693)
+		.phase(MockPhase::new("LIQUID", "RKMP").constituent(MockConstituent::new("Cu", &[1.0, 0.0])).constituent(MockConstituent::new("Ni", &[0.0, 1.0])))
+		.phase(MockPhase::new("FCC_A1", "RKMP").constituent(MockConstituent::new("Cu", &[1.0, 0.0])).constituent(MockConstituent::new("Ni", &[0.0, 1.0])))
+		.solver(solver);
+}
+
+/// A Cu-Ni-Fe system (LIQUID, FCC_A1, BCC_A2) for the tests of the ternary diagrams, with a solver.
+#[cfg(test)]
+pub(crate) fn cu_ni_fe_with<F: Fn(&MockInput)->Result<MockResults,usize> + Send + 'static>(solver: F)->MockBackend {
+	let phase = |name: &str| MockPhase::new(name, "RKMP")
+		.constituent(MockConstituent::new("Cu", &[1.0, 0.0, 0.0]))
+		.constituent(MockConstituent::new("Ni", &[0.0, 1.0, 0.0]))
+		.constituent(MockConstituent::new("Fe", &[0.0, 0.0, 1.0]));
+	return MockBackend::new()
+		.component("Cu", 63.546)
+		.component("Ni", 58.693)
+		.component("Fe", 55.845)
+		.phase(phase("LIQUID"))
+		.phase(phase("FCC_A1"))
+		.phase(phase("BCC_A2"))
+		.solver(solver);
+}
+
+/// A calculator on `cu_ni`.
+#[cfg(test)]
+pub(crate) fn calculator()->crate::calculator::Calculator<MockBackend> {
+	return crate::calculator::Calculator::from_backend(cu_ni()).unwrap();
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::options::{Condition, TargetVariable};
+
+	#[test]
+	fn tqgetr_rejects_unknown_options_and_indices(){
+		let calculator = calculator();
+		let engine = &calculator.engine;
+		engine.set_condition(Condition::T, 0, 0, 1000.0).unwrap();
+		engine.set_condition(Condition::IA, 0, 1, 1.0).unwrap();
+		engine.calculate(TargetVariable::None, 0, 0, (0.0, 0.0)).unwrap();
+		assert_eq!(engine.tqgetr("A", 2, 0).unwrap(), 1.0);
+		assert_eq!(engine.tqgetr("G", 0, 0).unwrap(), 0.0);
+		assert_eq!(engine.tqgetr("AX", 0, 0).unwrap_err().code(), Some(201));
+		assert_eq!(engine.tqgetr("A", 3, 0).unwrap_err().code(), Some(402));
+		assert_eq!(engine.tqgetr("A", 1, 3).unwrap_err().code(), Some(403));
+		assert_eq!(engine.tqgetr("A", 0, 3).unwrap_err().code(), Some(401));
+		assert_eq!(engine.tqgetr("AP", 1, 2).unwrap(), 0.0);
+	}
+
+}
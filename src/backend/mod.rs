@@ -0,0 +1,584 @@
+// chemapp_rs::backend.rs
+
+//! The `ChemAppBackend` trait abstracts over the `tq...` routines used by `Calculator`, the entities, the parameter cache and the interaction parsers.
+//! `Engine` (a loaded ChemApp library) is the default implementation; `MockBackend` is an in-process scripted implementation which allows to exercise the high-level API without a licensed ChemApp binary;
+//! `RemoteEngine` hosts an `Engine` in a separate worker process, so that a crash inside the library does not take down the calling process.
+
+pub mod mock;
+pub mod remote;
+
+use std::fmt;
+use std::time::{Instant};
+
+use crate::{SystemDimensions};
+use crate::native::Engine;
+use crate::error::{ChemAppError};
+use crate::units::{UnitManager, FortranFile, FileKind};
+use crate::options::{ChemAppOption, Condition, ResultQuantity, TargetVariable, MapOption, StreamProperty, ConstituentProperty, Status};
+
+pub use mock::{MockBackend, MockPhase, MockConstituent, MockInput, MockResults};
+pub use remote::{RemoteEngine};
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// The subset of ChemApp routines the high-level API relies on; the signatures follow the `Engine` methods of the same name.
+pub trait ChemAppBackend : fmt::Debug {
+	
+	/// A path or a name identifying the backend (the library path for `Engine`).
+	fn library_name(&self)->&str;
+	
+	/// The Fortran units in use by the backend.
+	fn units(&self)->&UnitManager;
+	
+	/// Set (or clear) a deadline for the subsequent calls; only backends able to abort a running call support it (`RemoteEngine`).
+	fn set_deadline(&self, deadline: Option<Instant>)->Result<(),ChemAppError> {
+		if deadline.is_none() {
+			return Ok(());
+		}
+		return Err(ChemAppError::OtherError(format!("{} does not support deadlines", std::any::type_name::<Self>())));
+	}
+	
+	/// INITIALIZE-INTERFACE
+	fn tqini(&self)->Result<(),ChemAppError>;
+	
+	/// GET-VERSION-NUMBER
+	fn tqvers(&self)->Result<i32,ChemAppError>;
+	
+	/// GET-COPYRIGHT-MESSAGE
+	fn tqcprt(&self)->Result<(),ChemAppError>;
+	
+	/// GET-ARRAY-SIZES
+	fn tqsize(&self)->Result<SystemDimensions,ChemAppError>;
+	
+	/// GET-CURRENT-DIMENSIONS
+	fn tqused(&self)->Result<SystemDimensions,ChemAppError>;
+	
+	/// GET-VALUE-OF-INPUT-OUTPUT-OPTION
+	fn tqgio(&self, option: &str)->Result<usize,ChemAppError>;
+	
+	/// CHANGE-INPUT-OPTION
+	fn tqcio(&self, option: &str, unit: usize)->Result<(),ChemAppError>;
+	
+	/// READ-DATA-FILE
+	fn tqrfil(&self)->Result<(),ChemAppError>;
+	
+	/// READ-BINARY-DATA-FILE
+	fn tqrbin(&self)->Result<(),ChemAppError>;
+	
+	/// READ-TRANSPARENT-DATA-FILE
+	fn tqrcst(&self)->Result<(),ChemAppError>;
+	
+	/// OPEN-FILE; the open calls reserve `unit` in `units` (an error if it is already in use) and release it again when opening fails
+	fn tqopen(&self, filename: &str, unit: usize)->Result<(),ChemAppError>;
+	
+	/// OPEN-ASCII-DATA-FILE
+	fn tqopna(&self, name: &str, unit: usize)->Result<(),ChemAppError>;
+	
+	/// OPEN-BINARY-DATA-FILE
+	fn tqopnb(&self, name: &str, unit: usize)->Result<(),ChemAppError>;
+	
+	/// OPEN-TRANSPARENT-DATA-FILE
+	fn tqopnt(&self, name: &str, unit: usize)->Result<(),ChemAppError>;
+	
+	/// Open `name` on `unit` with the routine of `kind` without reserving the unit, which the caller has already taken from `units` (see `FortranFile::open`).
+	fn open_unit(&self, name: &str, unit: usize, kind: FileKind)->Result<(),ChemAppError>;
+	
+	/// CLOSE-FILE; releases `unit` in `units`
+	fn tqclos(&self, unit: usize)->Result<(),ChemAppError>;
+	
+	/// GET-SYSTEM-UNIT
+	fn tqgsu(&self, option: &str)->Result<String,ChemAppError>;
+	
+	/// CHANGE-SYSTEM-UNIT
+	fn tqcsu(&self, option: &str, unit: &str)->Result<(),ChemAppError>;
+	
+	/// GET-INDEX-NUMBER-OF-SYSTEM-COMPONENT
+	fn tqinsc(&self, name: &str)->Result<usize,ChemAppError>;
+	
+	/// GET-NAME-OF-SYSTEM-COMPONENT
+	fn tqgnsc(&self, indexs: usize)->Result<String,ChemAppError>;
+	
+	/// GET-NUMBER-OF-SYSTEM-COMPONENTS
+	fn tqnosc(&self)->Result<usize,ChemAppError>;
+	
+	/// GET-STOICHIOMETRY-OF-SYSTEM-COMPONENT
+	fn tqstsc(&self, indexs: usize)->Result<(Vec<f64>,f64),ChemAppError>;
+	
+	/// GET-INDEX-NUMBER-OF-PHASE
+	fn tqinp(&self, name: &str)->Result<usize,ChemAppError>;
+	
+	/// GET-NAME-OF-PHASE
+	fn tqgnp(&self, indexp: usize)->Result<String,ChemAppError>;
+	
+	/// GET-MODEL-NAME-OF-PHASE
+	fn tqmodl(&self, indexp: usize)->Result<String,ChemAppError>;
+	
+	/// GET-NUMBER-OF-PHASES
+	fn tqnop(&self)->Result<usize,ChemAppError>;
+	
+	/// GET-INDEX-NUMBER-OF-PHASE-CONSTITUENT
+	fn tqinpc(&self, indexp: usize, name: &str)->Result<usize,ChemAppError>;
+	
+	/// GET-NAME-OF-PHASE-CONSTITUENT
+	fn tqgnpc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError>;
+	
+	/// PHASE-CONSTITUENT-IS-INCOMING-SPECIES
+	fn tqpcis(&self, indexp: usize, indexc: usize)->Result<bool,ChemAppError>;
+	
+	/// GET-NUMBER-OF-PHASE-CONSTITUENTS
+	fn tqnopc(&self, indexp: usize)->Result<usize,ChemAppError>;
+	
+	/// GET-STOICHIOMETRY-OF-PHASE-CONSTITUENT
+	fn tqstpc(&self, indexp: usize, indexc: usize)->Result<(Vec<f64>,f64),ChemAppError>;
+	
+	/// GET-CHARGE-OF-PHASE-CONSTITUENT
+	fn tqchar(&self, indexp: usize, indexc: usize)->Result<i32,ChemAppError>;
+	
+	/// GET-NAME-OF-SUBLATTICE-CONSTITUENT
+	fn tqgnlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<String,ChemAppError>;
+	
+	/// GET-NUMBER-OF-SUBLATTICES
+	fn tqnosl(&self, indexp: usize)->Result<usize,ChemAppError>;
+	
+	/// GET-NUMBER-OF-SUBLATTICE-SPECIES
+	fn tqnolc(&self, indexp: usize, index: usize)->Result<usize,ChemAppError>;
+	
+	/// GET-STATUS-OF-PHASE
+	fn tqgsp(&self, indexp: usize)->Result<String,ChemAppError>;
+	
+	/// CHANGE-STATUS-OF-PHASE
+	fn tqcsp(&self, indexp: usize, status: &str)->Result<(),ChemAppError>;
+	
+	/// GET-STATUS-OF-PHASE-CONSTITUENT
+	fn tqgspc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError>;
+	
+	/// CHANGE-STATUS-OF-PHASE-CONSTITUENT
+	fn tqcspc(&self, indexp: usize, indexc: usize, status: &str)->Result<(),ChemAppError>;
+	
+	/// SET-EQUILIBRIUM-CONDITION
+	fn tqsetc(&self, option: &str, indexp: usize, indexc: usize, val: f64)->Result<i32,ChemAppError>;
+	
+	/// REMOVE-EQUILIBRIUM-CONDITION
+	fn tqremc(&self, numcon: i32)->Result<(),ChemAppError>;
+	
+	/// SET-NAME-TEMPERATURE-PRESSURE-FOR-A-STREAM
+	fn tqsttp(&self, idents: &str, vals: (f64,f64))->Result<(),ChemAppError>;
+	
+	/// SET-CONSTITUENT-AMOUNTS-FOR-A-STREAM
+	fn tqstca(&self, idents: &str, indexp: usize, indexc: usize, val: f64)->Result<(),ChemAppError>;
+	
+	/// SET-EQUILIBRIUM-CONDITION-WHEN-STREAM-INPUT
+	fn tqstec(&self, option: &str, indexp: usize, val: f64)->Result<(),ChemAppError>;
+	
+	/// REMOVE-STREAM
+	fn tqstrm(&self, idents: &str)->Result<(),ChemAppError>;
+	
+	/// CALCULATE-EQUILIBRIUM
+	fn tqce(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError>;
+	
+	/// CALCULATE-EQUILIBRIUM-AND-LIST-RESULTS
+	fn tqcel(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError>;
+	
+	/// CALCULATE-EQUILIBRIUM-FROM-PREVIOUS
+	fn tqcen(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError>;
+	
+	/// CALCULATE-EQUILIBRIUM-FROM-PREVIOUS-AND-LIST-RESULTS
+	fn tqcenl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError>;
+	
+	/// CALCULATE-ONE-DIMENSIONAL-PHASE-MAP
+	fn tqmap(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError>;
+	
+	/// CALCULATE-ONE-DIMENSIONAL-PHASE-MAP-AND-LIST-RESULTS
+	fn tqmapl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError>;
+	
+	/// CHANGE-LIMIT-OF-TARGET-VARIABLE
+	fn tqclim(&self, option: &str, val: f64)->Result<(),ChemAppError>;
+	
+	/// SHOW-PRESENT-SETTINGS
+	fn tqshow(&self)->Result<(),ChemAppError>;
+	
+	/// GET-RESULT
+	fn tqgetr(&self, option: &str, indexp: usize, indexc: usize)->Result<f64,ChemAppError>;
+	
+	/// GET-PROPERTY-OF-A-PHASE-CONSTITUENT
+	fn tqgdpc(&self, option: &str, indexp: usize, index: usize)->Result<f64,ChemAppError>;
+	
+	/// GET-THERMODYNAMIC-PROPERTY-OF-A-STREAM
+	fn tqstxp(&self, idents: &str, option: &str)->Result<f64,ChemAppError>;
+	
+	/// GET-CALCULATED-EQUILIBRIUM-SUBLATTICE-SITE-FRACTION
+	fn tqgtlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<f64,ChemAppError>;
+	
+	/// GET-CALCULATED-QUADRUPLET-OR-PAIR-FRACTION
+	fn tqbond(&self, indexp: usize, indexa: usize, indexb: usize, indexc: usize, indexd: usize)->Result<f64,ChemAppError>;
+	
+	/// GET-ERROR-MESSAGE
+	fn tqerr(&self)->Result<String,ChemAppError>;
+	
+	/// GET-INPUT-THERMODYNAMIC-DATA-OF-PHASE-CONSTITUENT
+	fn tqgdat(&self, indexp: usize, indexc: usize, option: &str, indexr: usize)->Result<Vec<f64>,ChemAppError>;
+	
+	/// LIST-EXCESS-PARAMETERS-OF-PHASE
+	fn tqlpar(&self, indexp: usize, option: &str)->Result<Vec<String>,ChemAppError>;
+	
+	/// GET-EXCESS-PARAMETERS-OF-PHASE
+	fn tqgpar(&self, indexp: usize, option: &str, indexx: usize)->Result<Vec<Vec<f64>>,ChemAppError>;
+	
+	/// CHANGES-DATA-OF-THERMODYNAMIC-DATA-FILE
+	fn tqcdat(&self, i1: usize, i2: usize, i3: usize, i4: usize, i5: usize, val: f64)->Result<(),ChemAppError>;
+
+	/*************************************************************************************************************************************************************************************************/
+	/*************************************************************************************************************************************************************************************************/
+	
+	/// Typed `tqsetc`: set an equilibrium condition, returns the condition number `numcon`.
+	fn set_condition(&self, condition: Condition, indexp: usize, indexc: usize, val: f64)->Result<i32,ChemAppError>{
+		condition.check_indices(indexp, indexc)?;
+		return self.tqsetc(condition.code(), indexp, indexc, val);
+	}
+	
+	/// Typed `tqgetr`: retrieve a calculated result.
+	fn get_result(&self, quantity: ResultQuantity, indexp: usize, indexc: usize)->Result<f64,ChemAppError>{
+		quantity.check_indices(indexp, indexc)?;
+		return self.tqgetr(quantity.code(), indexp, indexc);
+	}
+	
+	/// Typed `tqce`: calculate the equilibrium, `vals` are the estimate/limits of the target variable.
+	fn calculate(&self, target: TargetVariable, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError>{
+		target.check_indices(indexp, indexc)?;
+		return self.tqce(target.code(), indexp, indexc, vals);
+	}
+	
+	/// Typed `tqcel`: calculate the equilibrium and list the results.
+	fn calculate_list(&self, target: TargetVariable, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError>{
+		target.check_indices(indexp, indexc)?;
+		return self.tqcel(target.code(), indexp, indexc, vals);
+	}
+	
+	/// Typed `tqcen`: calculate the equilibrium starting from the previous results.
+	fn calculate_next(&self, target: TargetVariable, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError>{
+		target.check_indices(indexp, indexc)?;
+		return self.tqcen(target.code(), indexp, indexc, vals);
+	}
+	
+	/// Typed `tqcenl`: calculate the equilibrium starting from the previous results and list the results.
+	fn calculate_next_list(&self, target: TargetVariable, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(),ChemAppError>{
+		target.check_indices(indexp, indexc)?;
+		return self.tqcenl(target.code(), indexp, indexc, vals);
+	}
+	
+	/// Typed `tqmap`: one-dimensional phase mapping, returns `icont`.
+	fn map(&self, option: MapOption, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError>{
+		option.check_indices(indexp, indexc)?;
+		return self.tqmap(option.code(), indexp, indexc, vals);
+	}
+	
+	/// Typed `tqmapl`: one-dimensional phase mapping with listing of the results, returns `icont`.
+	fn map_list(&self, option: MapOption, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError>{
+		option.check_indices(indexp, indexc)?;
+		return self.tqmapl(option.code(), indexp, indexc, vals);
+	}
+	
+	/// Typed `tqstxp`: a thermodynamic property of a stream.
+	fn stream_property(&self, idents: &str, property: StreamProperty)->Result<f64,ChemAppError>{
+		return self.tqstxp(idents, property.code());
+	}
+	
+	/// Open a file on a free unit, the file is closed when the returned guard is dropped.
+	fn open_file(&self, path: &str, kind: FileKind)->Result<FortranFile<'_, Self>,ChemAppError> where Self: Sized {
+		return FortranFile::open(self, path, kind);
+	}
+	
+	/// Typed `tqcsp`/`tqcspc`: change the status of a phase `(indexp, 0)` or a phase constituent `(indexp, indexc)`.
+	fn set_status(&self, status: Status, indexp: usize, indexc: usize)->Result<(),ChemAppError>{
+		status.check_indices(indexp, indexc)?;
+		if indexc == 0 {
+			return self.tqcsp(indexp, status.code());
+		}
+		return self.tqcspc(indexp, indexc, status.code());
+	}
+	
+	/// Typed `tqgsp`/`tqgspc`: the status of a phase `(indexp, 0)` or a phase constituent `(indexp, indexc)`.
+	fn get_status(&self, indexp: usize, indexc: usize)->Result<Status,ChemAppError>{
+		Status::Entered.check_indices(indexp, indexc)?;
+		let code = if indexc == 0 {self.tqgsp(indexp)?} else {self.tqgspc(indexp, indexc)?};
+		return Status::from_code(&code);
+	}
+	
+	/// Typed `tqgdpc`: a property of a single phase constituent.
+	fn constituent_property(&self, property: ConstituentProperty, indexp: usize, indexc: usize)->Result<f64,ChemAppError>{
+		property.check_indices(indexp, indexc)?;
+		return self.tqgdpc(property.code(), indexp, indexc);
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+impl ChemAppBackend for Engine {
+	
+	fn library_name(&self)->&str {
+		return &self.library_name;
+	}
+	
+	fn units(&self)->&UnitManager {
+		return Engine::units(self);
+	}
+	
+	fn tqini(&self)->Result<(),ChemAppError> {
+		return Engine::tqini(self);
+	}
+	
+	fn tqvers(&self)->Result<i32,ChemAppError> {
+		return Engine::tqvers(self);
+	}
+	
+	fn tqcprt(&self)->Result<(),ChemAppError> {
+		return Engine::tqcprt(self);
+	}
+	
+	fn tqsize(&self)->Result<SystemDimensions,ChemAppError> {
+		return Engine::tqsize(self);
+	}
+	
+	fn tqused(&self)->Result<SystemDimensions,ChemAppError> {
+		return Engine::tqused(self);
+	}
+	
+	fn tqgio(&self, option: &str)->Result<usize,ChemAppError> {
+		return Engine::tqgio(self, option);
+	}
+	
+	fn tqcio(&self, option: &str, unit: usize)->Result<(),ChemAppError> {
+		return Engine::tqcio(self, option, unit);
+	}
+	
+	fn tqrfil(&self)->Result<(),ChemAppError> {
+		return Engine::tqrfil(self);
+	}
+	
+	fn tqrbin(&self)->Result<(),ChemAppError> {
+		return Engine::tqrbin(self);
+	}
+	
+	fn tqrcst(&self)->Result<(),ChemAppError> {
+		return Engine::tqrcst(self);
+	}
+	
+	fn tqopen(&self, filename: &str, unit: usize)->Result<(),ChemAppError> {
+		return Engine::tqopen(self, filename, unit);
+	}
+	
+	fn tqopna(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return Engine::tqopna(self, name, unit);
+	}
+	
+	fn tqopnb(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return Engine::tqopnb(self, name, unit);
+	}
+	
+	fn tqopnt(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return Engine::tqopnt(self, name, unit);
+	}
+	
+	fn open_unit(&self, name: &str, unit: usize, kind: FileKind)->Result<(),ChemAppError> {
+		return Engine::open_unit(self, name, unit, kind);
+	}
+	
+	fn tqclos(&self, unit: usize)->Result<(),ChemAppError> {
+		return Engine::tqclos(self, unit);
+	}
+	
+	fn tqgsu(&self, option: &str)->Result<String,ChemAppError> {
+		return Engine::tqgsu(self, option);
+	}
+	
+	fn tqcsu(&self, option: &str, unit: &str)->Result<(),ChemAppError> {
+		return Engine::tqcsu(self, option, unit);
+	}
+	
+	fn tqinsc(&self, name: &str)->Result<usize,ChemAppError> {
+		return Engine::tqinsc(self, name);
+	}
+	
+	fn tqgnsc(&self, indexs: usize)->Result<String,ChemAppError> {
+		return Engine::tqgnsc(self, indexs);
+	}
+	
+	fn tqnosc(&self)->Result<usize,ChemAppError> {
+		return Engine::tqnosc(self);
+	}
+	
+	fn tqstsc(&self, indexs: usize)->Result<(Vec<f64>,f64),ChemAppError> {
+		return Engine::tqstsc(self, indexs);
+	}
+	
+	fn tqinp(&self, name: &str)->Result<usize,ChemAppError> {
+		return Engine::tqinp(self, name);
+	}
+	
+	fn tqgnp(&self, indexp: usize)->Result<String,ChemAppError> {
+		return Engine::tqgnp(self, indexp);
+	}
+	
+	fn tqmodl(&self, indexp: usize)->Result<String,ChemAppError> {
+		return Engine::tqmodl(self, indexp);
+	}
+	
+	fn tqnop(&self)->Result<usize,ChemAppError> {
+		return Engine::tqnop(self);
+	}
+	
+	fn tqinpc(&self, indexp: usize, name: &str)->Result<usize,ChemAppError> {
+		return Engine::tqinpc(self, indexp, name);
+	}
+	
+	fn tqgnpc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError> {
+		return Engine::tqgnpc(self, indexp, indexc);
+	}
+	
+	fn tqpcis(&self, indexp: usize, indexc: usize)->Result<bool,ChemAppError> {
+		return Engine::tqpcis(self, indexp, indexc);
+	}
+	
+	fn tqnopc(&self, indexp: usize)->Result<usize,ChemAppError> {
+		return Engine::tqnopc(self, indexp);
+	}
+	
+	fn tqstpc(&self, indexp: usize, indexc: usize)->Result<(Vec<f64>,f64),ChemAppError> {
+		return Engine::tqstpc(self, indexp, indexc);
+	}
+	
+	fn tqchar(&self, indexp: usize, indexc: usize)->Result<i32,ChemAppError> {
+		return Engine::tqchar(self, indexp, indexc);
+	}
+	
+	fn tqgnlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<String,ChemAppError> {
+		return Engine::tqgnlc(self, indexp, indexl, indexc);
+	}
+	
+	fn tqnosl(&self, indexp: usize)->Result<usize,ChemAppError> {
+		return Engine::tqnosl(self, indexp);
+	}
+	
+	fn tqnolc(&self, indexp: usize, index: usize)->Result<usize,ChemAppError> {
+		return Engine::tqnolc(self, indexp, index);
+	}
+	
+	fn tqgsp(&self, indexp: usize)->Result<String,ChemAppError> {
+		return Engine::tqgsp(self, indexp);
+	}
+	
+	fn tqcsp(&self, indexp: usize, status: &str)->Result<(),ChemAppError> {
+		return Engine::tqcsp(self, indexp, status);
+	}
+	
+	fn tqgspc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError> {
+		return Engine::tqgspc(self, indexp, indexc);
+	}
+	
+	fn tqcspc(&self, indexp: usize, indexc: usize, status: &str)->Result<(),ChemAppError> {
+		return Engine::tqcspc(self, indexp, indexc, status);
+	}
+	
+	fn tqsetc(&self, option: &str, indexp: usize, indexc: usize, val: f64)->Result<i32,ChemAppError> {
+		return Engine::tqsetc(self, option, indexp, indexc, val);
+	}
+	
+	fn tqremc(&self, numcon: i32)->Result<(),ChemAppError> {
+		return Engine::tqremc(self, numcon);
+	}
+	
+	fn tqsttp(&self, idents: &str, vals: (f64,f64))->Result<(),ChemAppError> {
+		return Engine::tqsttp(self, idents, vals);
+	}
+	
+	fn tqstca(&self, idents: &str, indexp: usize, indexc: usize, val: f64)->Result<(),ChemAppError> {
+		return Engine::tqstca(self, idents, indexp, indexc, val);
+	}
+	
+	fn tqstec(&self, option: &str, indexp: usize, val: f64)->Result<(),ChemAppError> {
+		return Engine::tqstec(self, option, indexp, val);
+	}
+	
+	fn tqstrm(&self, idents: &str)->Result<(),ChemAppError> {
+		return Engine::tqstrm(self, idents);
+	}
+	
+	fn tqce(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return Engine::tqce(self, option, indexp, indexc, vals);
+	}
+	
+	fn tqcel(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return Engine::tqcel(self, option, indexp, indexc, vals);
+	}
+	
+	fn tqcen(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return Engine::tqcen(self, option, indexp, indexc, vals);
+	}
+	
+	fn tqcenl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return Engine::tqcenl(self, option, indexp, indexc, vals);
+	}
+	
+	fn tqmap(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError> {
+		return Engine::tqmap(self, option, indexp, indexc, vals);
+	}
+	
+	fn tqmapl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError> {
+		return Engine::tqmapl(self, option, indexp, indexc, vals);
+	}
+	
+	fn tqclim(&self, option: &str, val: f64)->Result<(),ChemAppError> {
+		return Engine::tqclim(self, option, val);
+	}
+	
+	fn tqshow(&self)->Result<(),ChemAppError> {
+		return Engine::tqshow(self);
+	}
+	
+	fn tqgetr(&self, option: &str, indexp: usize, indexc: usize)->Result<f64,ChemAppError> {
+		return Engine::tqgetr(self, option, indexp, indexc);
+	}
+	
+	fn tqgdpc(&self, option: &str, indexp: usize, index: usize)->Result<f64,ChemAppError> {
+		return Engine::tqgdpc(self, option, indexp, index);
+	}
+	
+	fn tqstxp(&self, idents: &str, option: &str)->Result<f64,ChemAppError> {
+		return Engine::tqstxp(self, idents, option);
+	}
+	
+	fn tqgtlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<f64,ChemAppError> {
+		return Engine::tqgtlc(self, indexp, indexl, indexc);
+	}
+	
+	fn tqbond(&self, indexp: usize, indexa: usize, indexb: usize, indexc: usize, indexd: usize)->Result<f64,ChemAppError> {
+		return Engine::tqbond(self, indexp, indexa, indexb, indexc, indexd);
+	}
+	
+	fn tqerr(&self)->Result<String,ChemAppError> {
+		return Engine::tqerr(self);
+	}
+	
+	fn tqgdat(&self, indexp: usize, indexc: usize, option: &str, indexr: usize)->Result<Vec<f64>,ChemAppError> {
+		return Engine::tqgdat(self, indexp, indexc, option, indexr);
+	}
+	
+	fn tqlpar(&self, indexp: usize, option: &str)->Result<Vec<String>,ChemAppError> {
+		return Engine::tqlpar(self, indexp, option);
+	}
+	
+	fn tqgpar(&self, indexp: usize, option: &str, indexx: usize)->Result<Vec<Vec<f64>>,ChemAppError> {
+		return Engine::tqgpar(self, indexp, option, indexx);
+	}
+	
+	fn tqcdat(&self, i1: usize, i2: usize, i3: usize, i4: usize, i5: usize, val: f64)->Result<(),ChemAppError> {
+		return Engine::tqcdat(self, i1, i2, i3, i4, i5, val);
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
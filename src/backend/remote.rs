@@ -0,0 +1,1164 @@
+// chemapp_rs::backend::remote.rs
+
+//! Out-of-process execution of ChemApp routines. A Fortran STOP or a segmentation fault inside the library terminates the process hosting it; `RemoteEngine` hosts the `Engine` in a separate worker process (the `chemapp_worker` binary of this crate)
+//! and forwards every call over the standard input/output pipes of the worker as length-prefixed bincode frames.
+//! When the worker dies during a call, `RemoteEngine` respawns it, loads the library and the datafile again and returns `ChemAppError::WorkerError` for the failed call.
+//!
+//! A deadline can be set for the calls (see `Calculator::with_deadline`); a worker which does not reply in time is killed and restarted, and the call returns `ChemAppError::Timeout`.
+//! After a restart the calls changing the state of the library (conditions, units, phase statuses, limits, streams, model parameters) recorded since the last `tqini` are replayed.
+
+use std::fmt;
+use std::io::{self, Read, Write, BufReader, BufWriter};
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::{Instant};
+use serde::{Serialize, Deserialize};
+use serde::de::{DeserializeOwned};
+
+use crate::{SystemDimensions, Calculator};
+use crate::native::Engine;
+use crate::abi::{AbiProfile};
+use crate::error::{ChemAppError};
+use crate::backend::ChemAppBackend;
+use crate::units::{UnitManager, FileKind};
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// A request sent to the worker, one variant per `ChemAppBackend` routine.
+#[derive(Debug,Clone,Serialize,Deserialize)]
+pub enum Call {
+	/// load a ChemApp library with a binary interface in the worker, must be the first request
+	Load {library: String, abi: AbiProfile},
+	/// INITIALIZE-INTERFACE
+	Tqini,
+	/// GET-VERSION-NUMBER
+	Tqvers,
+	/// GET-COPYRIGHT-MESSAGE
+	Tqcprt,
+	/// GET-ARRAY-SIZES
+	Tqsize,
+	/// GET-CURRENT-DIMENSIONS
+	Tqused,
+	/// GET-VALUE-OF-INPUT-OUTPUT-OPTION
+	Tqgio {option: String},
+	/// CHANGE-INPUT-OPTION
+	Tqcio {option: String, unit: usize},
+	/// READ-DATA-FILE
+	Tqrfil,
+	/// READ-BINARY-DATA-FILE
+	Tqrbin,
+	/// READ-TRANSPARENT-DATA-FILE
+	Tqrcst,
+	/// OPEN-FILE
+	Tqopen {filename: String, unit: usize},
+	/// OPEN-ASCII-DATA-FILE
+	Tqopna {name: String, unit: usize},
+	/// OPEN-BINARY-DATA-FILE
+	Tqopnb {name: String, unit: usize},
+	/// OPEN-TRANSPARENT-DATA-FILE
+	Tqopnt {name: String, unit: usize},
+	/// CLOSE-FILE
+	Tqclos {unit: usize},
+	/// open a datafile on `unit`, read it (`tqrfil`/`tqrbin`/`tqrcst`) and close it; the journal keeps a datafile load as one such entry
+	Datafile {filename: String, kind: FileKind, unit: usize},
+	/// GET-SYSTEM-UNIT
+	Tqgsu {option: String},
+	/// CHANGE-SYSTEM-UNIT
+	Tqcsu {option: String, unit: String},
+	/// GET-INDEX-NUMBER-OF-SYSTEM-COMPONENT
+	Tqinsc {name: String},
+	/// GET-NAME-OF-SYSTEM-COMPONENT
+	Tqgnsc {indexs: usize},
+	/// GET-NUMBER-OF-SYSTEM-COMPONENTS
+	Tqnosc,
+	/// GET-STOICHIOMETRY-OF-SYSTEM-COMPONENT
+	Tqstsc {indexs: usize},
+	/// GET-INDEX-NUMBER-OF-PHASE
+	Tqinp {name: String},
+	/// GET-NAME-OF-PHASE
+	Tqgnp {indexp: usize},
+	/// GET-MODEL-NAME-OF-PHASE
+	Tqmodl {indexp: usize},
+	/// GET-NUMBER-OF-PHASES
+	Tqnop,
+	/// GET-INDEX-NUMBER-OF-PHASE-CONSTITUENT
+	Tqinpc {indexp: usize, name: String},
+	/// GET-NAME-OF-PHASE-CONSTITUENT
+	Tqgnpc {indexp: usize, indexc: usize},
+	/// PHASE-CONSTITUENT-IS-INCOMING-SPECIES
+	Tqpcis {indexp: usize, indexc: usize},
+	/// GET-NUMBER-OF-PHASE-CONSTITUENTS
+	Tqnopc {indexp: usize},
+	/// GET-STOICHIOMETRY-OF-PHASE-CONSTITUENT
+	Tqstpc {indexp: usize, indexc: usize},
+	/// GET-CHARGE-OF-PHASE-CONSTITUENT
+	Tqchar {indexp: usize, indexc: usize},
+	/// GET-NAME-OF-SUBLATTICE-CONSTITUENT
+	Tqgnlc {indexp: usize, indexl: usize, indexc: usize},
+	/// GET-NUMBER-OF-SUBLATTICES
+	Tqnosl {indexp: usize},
+	/// GET-NUMBER-OF-SUBLATTICE-SPECIES
+	Tqnolc {indexp: usize, index: usize},
+	/// GET-STATUS-OF-PHASE
+	Tqgsp {indexp: usize},
+	/// CHANGE-STATUS-OF-PHASE
+	Tqcsp {indexp: usize, status: String},
+	/// GET-STATUS-OF-PHASE-CONSTITUENT
+	Tqgspc {indexp: usize, indexc: usize},
+	/// CHANGE-STATUS-OF-PHASE-CONSTITUENT
+	Tqcspc {indexp: usize, indexc: usize, status: String},
+	/// SET-EQUILIBRIUM-CONDITION
+	Tqsetc {option: String, indexp: usize, indexc: usize, val: f64},
+	/// REMOVE-EQUILIBRIUM-CONDITION
+	Tqremc {numcon: i32},
+	/// SET-NAME-TEMPERATURE-PRESSURE-FOR-A-STREAM
+	Tqsttp {idents: String, vals: (f64,f64)},
+	/// SET-CONSTITUENT-AMOUNTS-FOR-A-STREAM
+	Tqstca {idents: String, indexp: usize, indexc: usize, val: f64},
+	/// SET-EQUILIBRIUM-CONDITION-WHEN-STREAM-INPUT
+	Tqstec {option: String, indexp: usize, val: f64},
+	/// REMOVE-STREAM
+	Tqstrm {idents: String},
+	/// CALCULATE-EQUILIBRIUM
+	Tqce {option: String, indexp: usize, indexc: usize, vals: (f64,f64)},
+	/// CALCULATE-EQUILIBRIUM-AND-LIST-RESULTS
+	Tqcel {option: String, indexp: usize, indexc: usize, vals: (f64,f64)},
+	/// CALCULATE-EQUILIBRIUM-FROM-PREVIOUS
+	Tqcen {option: String, indexp: usize, indexc: usize, vals: (f64,f64)},
+	/// CALCULATE-EQUILIBRIUM-FROM-PREVIOUS-AND-LIST-RESULTS
+	Tqcenl {option: String, indexp: usize, indexc: usize, vals: (f64,f64)},
+	/// CALCULATE-ONE-DIMENSIONAL-PHASE-MAP
+	Tqmap {option: String, indexp: usize, indexc: usize, vals: (f64,f64)},
+	/// CALCULATE-ONE-DIMENSIONAL-PHASE-MAP-AND-LIST-RESULTS
+	Tqmapl {option: String, indexp: usize, indexc: usize, vals: (f64,f64)},
+	/// CHANGE-LIMIT-OF-TARGET-VARIABLE
+	Tqclim {option: String, val: f64},
+	/// SHOW-PRESENT-SETTINGS
+	Tqshow,
+	/// GET-RESULT
+	Tqgetr {option: String, indexp: usize, indexc: usize},
+	/// GET-PROPERTY-OF-A-PHASE-CONSTITUENT
+	Tqgdpc {option: String, indexp: usize, index: usize},
+	/// GET-THERMODYNAMIC-PROPERTY-OF-A-STREAM
+	Tqstxp {idents: String, option: String},
+	/// GET-CALCULATED-EQUILIBRIUM-SUBLATTICE-SITE-FRACTION
+	Tqgtlc {indexp: usize, indexl: usize, indexc: usize},
+	/// GET-CALCULATED-QUADRUPLET-OR-PAIR-FRACTION
+	Tqbond {indexp: usize, indexa: usize, indexb: usize, indexc: usize, indexd: usize},
+	/// GET-ERROR-MESSAGE
+	Tqerr,
+	/// GET-INPUT-THERMODYNAMIC-DATA-OF-PHASE-CONSTITUENT
+	Tqgdat {indexp: usize, indexc: usize, option: String, indexr: usize},
+	/// LIST-EXCESS-PARAMETERS-OF-PHASE
+	Tqlpar {indexp: usize, option: String},
+	/// GET-EXCESS-PARAMETERS-OF-PHASE
+	Tqgpar {indexp: usize, option: String, indexx: usize},
+	/// CHANGES-DATA-OF-THERMODYNAMIC-DATA-FILE
+	Tqcdat {i1: usize, i2: usize, i3: usize, i4: usize, i5: usize, val: f64},}
+
+/// A successful reply of the worker.
+#[derive(Debug,Clone,Serialize,Deserialize)]
+pub enum Reply {
+	Unit,
+	Int(i32),
+	Size(usize),
+	Float(f64),
+	Flag(bool),
+	Text(String),
+	Texts(Vec<String>),
+	Floats(Vec<f64>),
+	Table(Vec<Vec<f64>>),
+	Stoichiometry((Vec<f64>,f64)),
+	Dimensions(SystemDimensions),
+}
+
+/// Conversion of a reply into the return type of a routine.
+pub trait FromReply : Sized {
+	fn from_reply(reply: Reply)->Result<Self,ChemAppError>;
+}
+
+macro_rules! reply_conversions {
+	($($variant:ident($type:ty)),* $(,)?) => {
+		$(
+		impl From<$type> for Reply {
+			fn from(value: $type)->Reply {
+				return Reply::$variant(value);
+			}
+		}
+		
+		impl FromReply for $type {
+			fn from_reply(reply: Reply)->Result<$type,ChemAppError> {
+				match reply {
+					Reply::$variant(value) => {return Ok(value);}
+					_ => {return Err(ChemAppError::WorkerError(format!("Unexpected reply {:?}, expected {}", reply, stringify!($variant))));}
+				}
+			}
+		}
+		)*
+	};
+}
+
+reply_conversions!(Int(i32), Size(usize), Float(f64), Flag(bool), Text(String), Texts(Vec<String>), Floats(Vec<f64>), Table(Vec<Vec<f64>>), Stoichiometry((Vec<f64>,f64)), Dimensions(SystemDimensions));
+
+impl From<()> for Reply {
+	fn from(_: ())->Reply {
+		return Reply::Unit;
+	}
+}
+
+impl FromReply for () {
+	fn from_reply(reply: Reply)->Result<(),ChemAppError> {
+		match reply {
+			Reply::Unit => {return Ok(());}
+			_ => {return Err(ChemAppError::WorkerError(format!("Unexpected reply {:?}, expected Unit", reply)));}
+		}
+	}
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// The largest frame accepted by `read_frame` (64 MiB); a longer length prefix is garbage in the pipe, e.g. console output of the library.
+pub const MAX_FRAME_SIZE : usize = 64*1024*1024;
+
+/// Write a frame: the length of the bincode-encoded value (`u32`, little endian) followed by the value.
+pub fn write_frame<W: Write, T: Serialize>(writer: &mut W, value: &T)->io::Result<()> {
+	let bytes = bincode::serde::encode_to_vec(value, bincode::config::standard()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+	writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+	writer.write_all(&bytes)?;
+	writer.flush()?;
+	return Ok(());
+}
+
+/// Read a frame written by `write_frame`; a frame longer than `MAX_FRAME_SIZE` is `io::ErrorKind::InvalidData`.
+pub fn read_frame<R: Read, T: DeserializeOwned>(reader: &mut R)->io::Result<T> {
+	let mut length = [0u8;4];
+	reader.read_exact(&mut length)?;
+	let length = u32::from_le_bytes(length) as usize;
+	if length > MAX_FRAME_SIZE {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, format!("A frame of {} bytes exceeds the maximum of {} bytes", length, MAX_FRAME_SIZE)));
+	}
+	let mut bytes = vec![0u8; length];
+	reader.read_exact(&mut bytes)?;
+	let (value, _) = bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+	return Ok(value);
+}
+
+/// Open a datafile, read it with the routine of its kind and close it again, also when the reading fails.
+fn read_datafile<B: ChemAppBackend>(backend: &B, filename: &str, kind: FileKind, unit: usize)->Result<(),ChemAppError> {
+	match kind {
+		FileKind::Ascii       => {backend.tqopna(filename, unit)?;}
+		FileKind::Binary      => {backend.tqopnb(filename, unit)?;}
+		FileKind::Transparent => {backend.tqopnt(filename, unit)?;}
+		FileKind::Other       => {return Err(ChemAppError::WorkerError(format!("{} is not a datafile kind", kind.routine())));}
+	}
+	let read = match kind {
+		FileKind::Ascii       => backend.tqrfil(),
+		FileKind::Binary      => backend.tqrbin(),
+		_                     => backend.tqrcst(),
+	};
+	let closed = backend.tqclos(unit);
+	read?;
+	return closed;
+}
+
+/// Execute a call on a backend (the worker side of the protocol).
+pub fn execute<B: ChemAppBackend>(backend: &B, call: Call)->Result<Reply,ChemAppError> {
+	match call {
+		Call::Load{library, ..} => Err(ChemAppError::WorkerError(format!("{} cannot be loaded, a library is already loaded", library))),
+		Call::Tqini => backend.tqini().map(Reply::from),
+		Call::Tqvers => backend.tqvers().map(Reply::from),
+		Call::Tqcprt => backend.tqcprt().map(Reply::from),
+		Call::Tqsize => backend.tqsize().map(Reply::from),
+		Call::Tqused => backend.tqused().map(Reply::from),
+		Call::Tqgio{option} => backend.tqgio(&option).map(Reply::from),
+		Call::Tqcio{option, unit} => backend.tqcio(&option, unit).map(Reply::from),
+		Call::Tqrfil => backend.tqrfil().map(Reply::from),
+		Call::Tqrbin => backend.tqrbin().map(Reply::from),
+		Call::Tqrcst => backend.tqrcst().map(Reply::from),
+		Call::Tqopen{filename, unit} => backend.tqopen(&filename, unit).map(Reply::from),
+		Call::Tqopna{name, unit} => backend.tqopna(&name, unit).map(Reply::from),
+		Call::Tqopnb{name, unit} => backend.tqopnb(&name, unit).map(Reply::from),
+		Call::Tqopnt{name, unit} => backend.tqopnt(&name, unit).map(Reply::from),
+		Call::Tqclos{unit} => backend.tqclos(unit).map(Reply::from),
+		Call::Datafile{filename, kind, unit} => read_datafile(backend, &filename, kind, unit).map(Reply::from),
+		Call::Tqgsu{option} => backend.tqgsu(&option).map(Reply::from),
+		Call::Tqcsu{option, unit} => backend.tqcsu(&option, &unit).map(Reply::from),
+		Call::Tqinsc{name} => backend.tqinsc(&name).map(Reply::from),
+		Call::Tqgnsc{indexs} => backend.tqgnsc(indexs).map(Reply::from),
+		Call::Tqnosc => backend.tqnosc().map(Reply::from),
+		Call::Tqstsc{indexs} => backend.tqstsc(indexs).map(Reply::from),
+		Call::Tqinp{name} => backend.tqinp(&name).map(Reply::from),
+		Call::Tqgnp{indexp} => backend.tqgnp(indexp).map(Reply::from),
+		Call::Tqmodl{indexp} => backend.tqmodl(indexp).map(Reply::from),
+		Call::Tqnop => backend.tqnop().map(Reply::from),
+		Call::Tqinpc{indexp, name} => backend.tqinpc(indexp, &name).map(Reply::from),
+		Call::Tqgnpc{indexp, indexc} => backend.tqgnpc(indexp, indexc).map(Reply::from),
+		Call::Tqpcis{indexp, indexc} => backend.tqpcis(indexp, indexc).map(Reply::from),
+		Call::Tqnopc{indexp} => backend.tqnopc(indexp).map(Reply::from),
+		Call::Tqstpc{indexp, indexc} => backend.tqstpc(indexp, indexc).map(Reply::from),
+		Call::Tqchar{indexp, indexc} => backend.tqchar(indexp, indexc).map(Reply::from),
+		Call::Tqgnlc{indexp, indexl, indexc} => backend.tqgnlc(indexp, indexl, indexc).map(Reply::from),
+		Call::Tqnosl{indexp} => backend.tqnosl(indexp).map(Reply::from),
+		Call::Tqnolc{indexp, index} => backend.tqnolc(indexp, index).map(Reply::from),
+		Call::Tqgsp{indexp} => backend.tqgsp(indexp).map(Reply::from),
+		Call::Tqcsp{indexp, status} => backend.tqcsp(indexp, &status).map(Reply::from),
+		Call::Tqgspc{indexp, indexc} => backend.tqgspc(indexp, indexc).map(Reply::from),
+		Call::Tqcspc{indexp, indexc, status} => backend.tqcspc(indexp, indexc, &status).map(Reply::from),
+		Call::Tqsetc{option, indexp, indexc, val} => backend.tqsetc(&option, indexp, indexc, val).map(Reply::from),
+		Call::Tqremc{numcon} => backend.tqremc(numcon).map(Reply::from),
+		Call::Tqsttp{idents, vals} => backend.tqsttp(&idents, vals).map(Reply::from),
+		Call::Tqstca{idents, indexp, indexc, val} => backend.tqstca(&idents, indexp, indexc, val).map(Reply::from),
+		Call::Tqstec{option, indexp, val} => backend.tqstec(&option, indexp, val).map(Reply::from),
+		Call::Tqstrm{idents} => backend.tqstrm(&idents).map(Reply::from),
+		Call::Tqce{option, indexp, indexc, vals} => backend.tqce(&option, indexp, indexc, vals).map(Reply::from),
+		Call::Tqcel{option, indexp, indexc, vals} => backend.tqcel(&option, indexp, indexc, vals).map(Reply::from),
+		Call::Tqcen{option, indexp, indexc, vals} => backend.tqcen(&option, indexp, indexc, vals).map(Reply::from),
+		Call::Tqcenl{option, indexp, indexc, vals} => backend.tqcenl(&option, indexp, indexc, vals).map(Reply::from),
+		Call::Tqmap{option, indexp, indexc, vals} => backend.tqmap(&option, indexp, indexc, vals).map(Reply::from),
+		Call::Tqmapl{option, indexp, indexc, vals} => backend.tqmapl(&option, indexp, indexc, vals).map(Reply::from),
+		Call::Tqclim{option, val} => backend.tqclim(&option, val).map(Reply::from),
+		Call::Tqshow => backend.tqshow().map(Reply::from),
+		Call::Tqgetr{option, indexp, indexc} => backend.tqgetr(&option, indexp, indexc).map(Reply::from),
+		Call::Tqgdpc{option, indexp, index} => backend.tqgdpc(&option, indexp, index).map(Reply::from),
+		Call::Tqstxp{idents, option} => backend.tqstxp(&idents, &option).map(Reply::from),
+		Call::Tqgtlc{indexp, indexl, indexc} => backend.tqgtlc(indexp, indexl, indexc).map(Reply::from),
+		Call::Tqbond{indexp, indexa, indexb, indexc, indexd} => backend.tqbond(indexp, indexa, indexb, indexc, indexd).map(Reply::from),
+		Call::Tqerr => backend.tqerr().map(Reply::from),
+		Call::Tqgdat{indexp, indexc, option, indexr} => backend.tqgdat(indexp, indexc, &option, indexr).map(Reply::from),
+		Call::Tqlpar{indexp, option} => backend.tqlpar(indexp, &option).map(Reply::from),
+		Call::Tqgpar{indexp, option, indexx} => backend.tqgpar(indexp, &option, indexx).map(Reply::from),
+		Call::Tqcdat{i1, i2, i3, i4, i5, val} => backend.tqcdat(i1, i2, i3, i4, i5, val).map(Reply::from),
+	}
+}
+
+/// The worker loop: read calls from `reader` and write the replies (`Result<Reply,ChemAppError>`) to `writer` until the input is closed.
+pub fn serve<R: Read, W: Write>(reader: &mut R, writer: &mut W)->Result<(),ChemAppError> {
+	let mut engine : Option<Engine> = None;
+	loop {
+		let call : Call = match read_frame(reader) {
+			Ok(call) => call,
+			Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => {return Ok(());}
+			Err(error) => {return Err(ChemAppError::WorkerError(error.to_string()));}
+		};
+		let reply = match (call, &engine) {
+			(Call::Load{library, abi}, None) => {
+				match Engine::with_abi(&library, abi) {
+					Ok(loaded) => {engine = Some(loaded); Ok(Reply::Unit)}
+					Err(error) => Err(error),
+				}
+			}
+			(call, Some(loaded)) => execute(loaded, call),
+			(call, None) => Err(ChemAppError::WorkerError(format!("{:?} was called before a library was loaded", call))),
+		};
+		write_frame(writer, &reply).map_err(|e| ChemAppError::WorkerError(e.to_string()))?;
+	}
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// A running worker; the replies are read by a separate thread so that waiting for them can be abandoned at a deadline.
+struct WorkerProcess {
+	/// `None` for a worker which is not a child process (e.g. a thread in the tests), it ends when its input is closed
+	child: Option<Child>,
+	input: BufWriter<Box<dyn Write + Send>>,
+	replies: Receiver<io::Result<Result<Reply,ChemAppError>>>,
+	reader: JoinHandle<()>,
+}
+
+/// The ways a call can fail to get a reply.
+enum Failure {
+	Died(io::Error),
+	TimedOut,
+}
+
+/// The key of a journaled condition (`tqstec` enters the conditions of a stream calculation with the phase index only).
+fn condition_key(call: &Call)->Option<(&str,usize,usize)> {
+	match call {
+		Call::Tqsetc{option, indexp, indexc, ..} => {return Some((option, *indexp, *indexc));}
+		Call::Tqstec{option, indexp, ..} => {return Some((option, *indexp, 0));}
+		_ => {return None;}
+	}
+}
+
+/// Drop the journaled calls `same` as `call` and append it.
+fn supersede<F: Fn(&Call)->bool>(journal: &mut Vec<Call>, call: &Call, same: F){
+	journal.retain(|c| !same(c));
+	journal.push(call.clone());
+}
+
+/// A `ChemAppBackend` forwarding the calls to an `Engine` hosted in a worker process.
+pub struct RemoteEngine {
+	worker: PathBuf,
+	library_name: String,
+	abi: AbiProfile,
+	process: RefCell<Option<WorkerProcess>>,
+	respawning: Cell<bool>,
+	restarts: Cell<usize>,
+	deadline: Cell<Option<Instant>>,
+	journal: RefCell<Vec<Call>>,
+	units: UnitManager,
+}
+
+impl fmt::Debug for RemoteEngine {
+	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
+		f.debug_struct("RemoteEngine")
+			.field("worker", &self.worker)
+			.field("library_name", &self.library_name)
+			.field("abi", &self.abi)
+			.field("datafile", &self.datafile())
+			.field("pid", &self.pid())
+			.field("restarts", &self.restarts.get())
+			.field("deadline", &self.deadline.get())
+			.finish()
+	}
+	
+}
+
+impl RemoteEngine {
+	
+	/// Start a worker (path to the `chemapp_worker` executable), load the library in it and, optionally, initialize it with a datafile (see `Calculator::init_engine`).
+	/// The datafile is journaled like one loaded later with `Calculator::load_datafile`, a restarted worker reloads the most recent one.
+	pub fn new(worker: &str, library_name: &str, datafile: Option<&str>)->Result<RemoteEngine,ChemAppError>{
+		return RemoteEngine::with_abi(worker, library_name, datafile, AbiProfile::platform());
+	}
+	
+	/// Same as `new` for a library built with a non-default Fortran binary interface (see `Engine::with_abi`).
+	pub fn with_abi(worker: &str, library_name: &str, datafile: Option<&str>, abi: AbiProfile)->Result<RemoteEngine,ChemAppError>{
+		let engine = RemoteEngine {
+			worker: PathBuf::from(worker),
+			library_name: library_name.to_string(),
+			abi,
+			process: RefCell::new(None),
+			respawning: Cell::new(false),
+			restarts: Cell::new(0),
+			deadline: Cell::new(None),
+			journal: RefCell::new(Vec::new()),
+			units: UnitManager::new(),
+		};
+		engine.respawning.set(true);
+		let result = engine.spawn();
+		engine.respawning.set(false);
+		result?;
+		if let Some(datafile) = datafile {
+			Calculator::init_engine(&engine, datafile)?;
+		}
+		return Ok(engine);
+	}
+	
+	/// the worker executable
+	pub fn worker(&self)->&Path {
+		return &self.worker;
+	}
+	
+	/// the binary interface the worker loads the library with
+	pub fn abi(&self)->AbiProfile {
+		return self.abi;
+	}
+	
+	/// the datafile loaded since the last `tqini`, loaded again after every restart
+	pub fn datafile(&self)->Option<String> {
+		return self.journal.borrow().iter().rev().find_map(|c| match c {
+			Call::Datafile{filename, ..} => Some(filename.clone()),
+			_ => None,
+		});
+	}
+	
+	/// process id of the running worker
+	pub fn pid(&self)->Option<u32> {
+		return self.process.borrow().as_ref().and_then(|p| p.child.as_ref()).map(|c| c.id());
+	}
+	
+	/// number of times the worker was restarted after its death or a timeout
+	pub fn restarts(&self)->usize {
+		return self.restarts.get();
+	}
+	
+	/// the calls replayed after a restart
+	pub fn journal(&self)->Vec<Call> {
+		return self.journal.borrow().clone();
+	}
+	
+	/// Start a worker process, load and initialize the library and replay the journal (which starts with the datafile load, if any).
+	fn spawn(&self)->Result<(),ChemAppError>{
+		let mut child = Command::new(&self.worker).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::inherit()).spawn()
+			.map_err(|e| ChemAppError::WorkerError(format!("Cannot start {}: {}", self.worker.display(), e)))?;
+		let input = child.stdin.take().expect("stdin is piped");
+		let output = child.stdout.take().expect("stdout is piped");
+		self.attach(Some(child), Box::new(input), output);
+		self.call_as::<()>(Call::Load{library: self.library_name.clone(), abi: self.abi})?;
+		self.call_as::<()>(Call::Tqini)?;
+		let journal = self.journal.borrow().clone();
+		for call in journal {
+			self.call(call)?;
+		}
+		return Ok(());
+	}
+	
+	/// Take over a worker: the calls are written to `input`, the replies read from `output` by a separate thread.
+	fn attach<R: Read + Send + 'static>(&self, child: Option<Child>, input: Box<dyn Write + Send>, output: R){
+		let mut output = BufReader::new(output);
+		let (sender, replies) = mpsc::channel();
+		let reader = thread::spawn(move || {
+			loop {
+				let reply = read_frame(&mut output);
+				let failed = reply.is_err();
+				if sender.send(reply).is_err() || failed {break;}
+			}
+		});
+		*self.process.borrow_mut() = Some(WorkerProcess {child, input: BufWriter::new(input), replies, reader});
+	}
+	
+	/// Kill the worker (if still running) and describe its exit status.
+	fn terminate(&self)->String {
+		match self.process.borrow_mut().take() {
+			Some(WorkerProcess {child, input, reader, ..}) => {
+				// closing the input ends a worker which is not a child process
+				drop(input);
+				let status = match child {
+					Some(mut child) => {
+						let _ = child.kill();
+						child.wait().map(|s| s.to_string()).unwrap_or_else(|e| e.to_string())
+					}
+					None => "input closed".to_string(),
+				};
+				let _ = reader.join();
+				return status;
+			}
+			None => {return "not running".to_string();}
+		}
+	}
+	
+	/// Start a new worker after the previous one died or was killed.
+	fn respawn(&self)->Result<(),ChemAppError>{
+		self.terminate();
+		self.respawning.set(true);
+		let result = self.spawn();
+		self.respawning.set(false);
+		match result {
+			Ok(_) => {self.restarts.set(self.restarts.get() + 1);}
+			Err(_) => {self.terminate();}
+		}
+		return result;
+	}
+	
+	/// Send a call and wait for the reply (until the deadline, unless restoring the state after a restart).
+	fn request(&self, call: &Call)->Result<Result<Reply,ChemAppError>,Failure> {
+		let mut process = self.process.borrow_mut();
+		let process = process.as_mut().ok_or(Failure::Died(io::Error::new(io::ErrorKind::NotConnected, "the worker is not running")))?;
+		write_frame(&mut process.input, call).map_err(Failure::Died)?;
+		let reply = match self.deadline.get() {
+			Some(deadline) if !self.respawning.get() => {
+				match process.replies.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+					Ok(reply) => reply,
+					Err(RecvTimeoutError::Timeout) => {return Err(Failure::TimedOut);}
+					Err(RecvTimeoutError::Disconnected) => Err(io::Error::new(io::ErrorKind::BrokenPipe, "the reply channel is closed")),
+				}
+			}
+			_ => process.replies.recv().unwrap_or(Err(io::Error::new(io::ErrorKind::BrokenPipe, "the reply channel is closed"))),
+		};
+		return reply.map_err(Failure::Died);
+	}
+	
+	/// Remember the successful calls changing the state of the library, so that they can be replayed after a restart.
+	fn record(&self, call: &Call){
+		let mut journal = self.journal.borrow_mut();
+		match call {
+			Call::Tqini => {journal.clear();}
+			// the conditions keep the order of their numbers: a condition entered again replaces its entry, a removed condition is dropped
+			Call::Tqsetc{..} | Call::Tqstec{..} => {
+				let key = condition_key(call);
+				match journal.iter().position(|c| condition_key(c).is_some() && condition_key(c) == key) {
+					Some(position) => {journal[position] = call.clone();}
+					None => {journal.push(call.clone());}
+				}
+			}
+			Call::Tqremc{numcon: -2} => {journal.retain(|c| condition_key(c).is_none());}
+			Call::Tqremc{numcon} if *numcon > 0 => {
+				let position = journal.iter().enumerate().filter(|(_, c)| condition_key(c).is_some()).map(|(i, _)| i).nth((*numcon - 1) as usize);
+				if let Some(position) = position {
+					journal.remove(position);
+				}
+			}
+			// the other settings are kept once per key, the latest value replacing the earlier one
+			Call::Tqclim{option, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqclim{option: o, ..} if o == option));}
+			Call::Tqcsu{option, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqcsu{option: o, ..} if o == option));}
+			Call::Tqcsp{indexp, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqcsp{indexp: p, ..} if p == indexp));}
+			Call::Tqcspc{indexp, indexc, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqcspc{indexp: p, indexc: k, ..} if p == indexp && k == indexc));}
+			Call::Tqcdat{i1, i2, i3, i4, i5, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqcdat{i1: j1, i2: j2, i3: j3, i4: j4, i5: j5, ..} if (j1, j2, j3, j4, j5) == (i1, i2, i3, i4, i5)));}
+			Call::Tqsttp{idents, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqsttp{idents: s, ..} if s == idents));}
+			Call::Tqstca{idents, indexp, indexc, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqstca{idents: s, indexp: p, indexc: k, ..} if s == idents && p == indexp && k == indexc));}
+			Call::Tqstrm{idents} => {journal.retain(|c| !matches!(c, Call::Tqsttp{idents: s, ..} | Call::Tqstca{idents: s, ..} if s == idents));}
+			// open files and the I/O routing are restored as well (an output file is reopened, i.e. written anew), a closed file is forgotten
+			Call::Tqopen{..} | Call::Tqopna{..} | Call::Tqopnb{..} | Call::Tqopnt{..} => {journal.push(call.clone());}
+			// a datafile load is kept as one entry (the open, the read and the close) replacing the earlier one; the new system does not inherit the earlier conditions and settings, only the open files, the I/O routing and the units of measure
+			Call::Tqrfil | Call::Tqrbin | Call::Tqrcst => {
+				let kind = match call {
+					Call::Tqrfil => FileKind::Ascii,
+					Call::Tqrbin => FileKind::Binary,
+					_            => FileKind::Transparent,
+				};
+				let open = journal.iter().rposition(|c| matches!((c, kind), (Call::Tqopna{..}, FileKind::Ascii) | (Call::Tqopnb{..}, FileKind::Binary) | (Call::Tqopnt{..}, FileKind::Transparent)));
+				if let Some(position) = open {
+					if let Call::Tqopna{name, unit} | Call::Tqopnb{name, unit} | Call::Tqopnt{name, unit} = journal.remove(position) {
+						journal.retain(|c| matches!(c, Call::Tqopen{..} | Call::Tqopna{..} | Call::Tqopnb{..} | Call::Tqopnt{..} | Call::Tqcio{..} | Call::Tqcsu{..}));
+						journal.push(Call::Datafile{filename: name, kind, unit});
+					}
+				}
+			}
+			Call::Tqclos{unit} => {journal.retain(|c| !matches!(c, Call::Tqopen{unit: u, ..} | Call::Tqopna{unit: u, ..} | Call::Tqopnb{unit: u, ..} | Call::Tqopnt{unit: u, ..} if u == unit));}
+			Call::Tqcio{option, ..} => {supersede(&mut journal, call, |c| matches!(c, Call::Tqcio{option: o, ..} if o == option));}
+			_ => {}
+		}
+	}
+	
+	/// Execute a call in the worker, detect its death or a missed deadline and restart it.
+	pub fn call(&self, call: Call)->Result<Reply,ChemAppError>{
+		if let Some(deadline) = self.deadline.get() {
+			// an expired deadline fails the call before it reaches the worker, which stays untouched
+			if !self.respawning.get() && Instant::now() >= deadline {
+				return Err(ChemAppError::Timeout(format!("{:?} was not sent, the deadline has already passed", call)));
+			}
+		}
+		if self.process.borrow().is_none() && !self.respawning.get() {
+			self.respawn()?;
+		}
+		match self.request(&call) {
+			Ok(reply) => {
+				if reply.is_ok() && !self.respawning.get() {
+					self.record(&call);
+				}
+				return reply;
+			}
+			Err(Failure::Died(error)) => {
+				let status = self.terminate();
+				if !self.respawning.get() {
+					let _ = self.respawn();
+				}
+				return Err(ChemAppError::WorkerError(format!("The worker died while executing {:?}: {} (exit status: {})", call, error, status)));
+			}
+			Err(Failure::TimedOut) => {
+				self.terminate();
+				let _ = self.respawn();
+				return Err(ChemAppError::Timeout(format!("{:?} did not complete before the deadline, the worker was restarted", call)));
+			}
+		}
+	}
+	
+	fn call_as<T: FromReply>(&self, call: Call)->Result<T,ChemAppError>{
+		return T::from_reply(self.call(call)?);
+	}
+	
+}
+
+impl Drop for RemoteEngine {
+	
+	fn drop(&mut self){
+		self.terminate();
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+impl ChemAppBackend for RemoteEngine {
+	
+	fn library_name(&self)->&str {
+		return &self.library_name;
+	}
+	
+	fn units(&self)->&UnitManager {
+		return &self.units;
+	}
+	
+	fn set_deadline(&self, deadline: Option<Instant>)->Result<(),ChemAppError> {
+		self.deadline.set(deadline);
+		return Ok(());
+	}
+	
+	fn tqini(&self)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqini);
+	}
+	
+	fn tqvers(&self)->Result<i32,ChemAppError> {
+		return self.call_as(Call::Tqvers);
+	}
+	
+	fn tqcprt(&self)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcprt);
+	}
+	
+	fn tqsize(&self)->Result<SystemDimensions,ChemAppError> {
+		return self.call_as(Call::Tqsize);
+	}
+	
+	fn tqused(&self)->Result<SystemDimensions,ChemAppError> {
+		return self.call_as(Call::Tqused);
+	}
+	
+	fn tqgio(&self, option: &str)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqgio{option: option.to_string()});
+	}
+	
+	fn tqcio(&self, option: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcio{option: option.to_string(), unit});
+	}
+	
+	fn tqrfil(&self)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqrfil);
+	}
+	
+	fn tqrbin(&self)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqrbin);
+	}
+	
+	fn tqrcst(&self)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqrcst);
+	}
+	
+	fn tqopen(&self, filename: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(filename, unit, FileKind::Other));
+	}
+	
+	fn tqopna(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Ascii));
+	}
+	
+	fn tqopnb(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Binary));
+	}
+	
+	fn tqopnt(&self, name: &str, unit: usize)->Result<(),ChemAppError> {
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Transparent));
+	}
+	
+	fn open_unit(&self, name: &str, unit: usize, kind: FileKind)->Result<(),ChemAppError> {
+		let name = name.to_string();
+		match kind {
+			FileKind::Ascii       => {return self.call_as(Call::Tqopna{name, unit});}
+			FileKind::Binary      => {return self.call_as(Call::Tqopnb{name, unit});}
+			FileKind::Transparent => {return self.call_as(Call::Tqopnt{name, unit});}
+			FileKind::Other       => {return self.call_as(Call::Tqopen{filename: name, unit});}
+		}
+	}
+	
+	fn tqclos(&self, unit: usize)->Result<(),ChemAppError> {
+		return self.units.close(unit, || self.call_as(Call::Tqclos{unit}));
+	}
+	
+	fn tqgsu(&self, option: &str)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqgsu{option: option.to_string()});
+	}
+	
+	fn tqcsu(&self, option: &str, unit: &str)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcsu{option: option.to_string(), unit: unit.to_string()});
+	}
+	
+	fn tqinsc(&self, name: &str)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqinsc{name: name.to_string()});
+	}
+	
+	fn tqgnsc(&self, indexs: usize)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqgnsc{indexs});
+	}
+	
+	fn tqnosc(&self)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqnosc);
+	}
+	
+	fn tqstsc(&self, indexs: usize)->Result<(Vec<f64>,f64),ChemAppError> {
+		return self.call_as(Call::Tqstsc{indexs});
+	}
+	
+	fn tqinp(&self, name: &str)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqinp{name: name.to_string()});
+	}
+	
+	fn tqgnp(&self, indexp: usize)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqgnp{indexp});
+	}
+	
+	fn tqmodl(&self, indexp: usize)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqmodl{indexp});
+	}
+	
+	fn tqnop(&self)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqnop);
+	}
+	
+	fn tqinpc(&self, indexp: usize, name: &str)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqinpc{indexp, name: name.to_string()});
+	}
+	
+	fn tqgnpc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqgnpc{indexp, indexc});
+	}
+	
+	fn tqpcis(&self, indexp: usize, indexc: usize)->Result<bool,ChemAppError> {
+		return self.call_as(Call::Tqpcis{indexp, indexc});
+	}
+	
+	fn tqnopc(&self, indexp: usize)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqnopc{indexp});
+	}
+	
+	fn tqstpc(&self, indexp: usize, indexc: usize)->Result<(Vec<f64>,f64),ChemAppError> {
+		return self.call_as(Call::Tqstpc{indexp, indexc});
+	}
+	
+	fn tqchar(&self, indexp: usize, indexc: usize)->Result<i32,ChemAppError> {
+		return self.call_as(Call::Tqchar{indexp, indexc});
+	}
+	
+	fn tqgnlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqgnlc{indexp, indexl, indexc});
+	}
+	
+	fn tqnosl(&self, indexp: usize)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqnosl{indexp});
+	}
+	
+	fn tqnolc(&self, indexp: usize, index: usize)->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqnolc{indexp, index});
+	}
+	
+	fn tqgsp(&self, indexp: usize)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqgsp{indexp});
+	}
+	
+	fn tqcsp(&self, indexp: usize, status: &str)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcsp{indexp, status: status.to_string()});
+	}
+	
+	fn tqgspc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqgspc{indexp, indexc});
+	}
+	
+	fn tqcspc(&self, indexp: usize, indexc: usize, status: &str)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcspc{indexp, indexc, status: status.to_string()});
+	}
+	
+	fn tqsetc(&self, option: &str, indexp: usize, indexc: usize, val: f64)->Result<i32,ChemAppError> {
+		return self.call_as(Call::Tqsetc{option: option.to_string(), indexp, indexc, val});
+	}
+	
+	fn tqremc(&self, numcon: i32)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqremc{numcon});
+	}
+	
+	fn tqsttp(&self, idents: &str, vals: (f64,f64))->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqsttp{idents: idents.to_string(), vals});
+	}
+	
+	fn tqstca(&self, idents: &str, indexp: usize, indexc: usize, val: f64)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqstca{idents: idents.to_string(), indexp, indexc, val});
+	}
+	
+	fn tqstec(&self, option: &str, indexp: usize, val: f64)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqstec{option: option.to_string(), indexp, val});
+	}
+	
+	fn tqstrm(&self, idents: &str)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqstrm{idents: idents.to_string()});
+	}
+	
+	fn tqce(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqce{option: option.to_string(), indexp, indexc, vals});
+	}
+	
+	fn tqcel(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcel{option: option.to_string(), indexp, indexc, vals});
+	}
+	
+	fn tqcen(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcen{option: option.to_string(), indexp, indexc, vals});
+	}
+	
+	fn tqcenl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64))->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcenl{option: option.to_string(), indexp, indexc, vals});
+	}
+	
+	fn tqmap(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqmap{option: option.to_string(), indexp, indexc, vals});
+	}
+	
+	fn tqmapl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError> {
+		return self.call_as(Call::Tqmapl{option: option.to_string(), indexp, indexc, vals});
+	}
+	
+	fn tqclim(&self, option: &str, val: f64)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqclim{option: option.to_string(), val});
+	}
+	
+	fn tqshow(&self)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqshow);
+	}
+	
+	fn tqgetr(&self, option: &str, indexp: usize, indexc: usize)->Result<f64,ChemAppError> {
+		return self.call_as(Call::Tqgetr{option: option.to_string(), indexp, indexc});
+	}
+	
+	fn tqgdpc(&self, option: &str, indexp: usize, index: usize)->Result<f64,ChemAppError> {
+		return self.call_as(Call::Tqgdpc{option: option.to_string(), indexp, index});
+	}
+	
+	fn tqstxp(&self, idents: &str, option: &str)->Result<f64,ChemAppError> {
+		return self.call_as(Call::Tqstxp{idents: idents.to_string(), option: option.to_string()});
+	}
+	
+	fn tqgtlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<f64,ChemAppError> {
+		return self.call_as(Call::Tqgtlc{indexp, indexl, indexc});
+	}
+	
+	fn tqbond(&self, indexp: usize, indexa: usize, indexb: usize, indexc: usize, indexd: usize)->Result<f64,ChemAppError> {
+		return self.call_as(Call::Tqbond{indexp, indexa, indexb, indexc, indexd});
+	}
+	
+	fn tqerr(&self)->Result<String,ChemAppError> {
+		return self.call_as(Call::Tqerr);
+	}
+	
+	fn tqgdat(&self, indexp: usize, indexc: usize, option: &str, indexr: usize)->Result<Vec<f64>,ChemAppError> {
+		return self.call_as(Call::Tqgdat{indexp, indexc, option: option.to_string(), indexr});
+	}
+	
+	fn tqlpar(&self, indexp: usize, option: &str)->Result<Vec<String>,ChemAppError> {
+		return self.call_as(Call::Tqlpar{indexp, option: option.to_string()});
+	}
+	
+	fn tqgpar(&self, indexp: usize, option: &str, indexx: usize)->Result<Vec<Vec<f64>>,ChemAppError> {
+		return self.call_as(Call::Tqgpar{indexp, option: option.to_string(), indexx});
+	}
+	
+	fn tqcdat(&self, i1: usize, i2: usize, i3: usize, i4: usize, i5: usize, val: f64)->Result<(),ChemAppError> {
+		return self.call_as(Call::Tqcdat{i1, i2, i3, i4, i5, val});
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+	
+	use super::*;
+	use std::io::{Cursor};
+	use std::time::{Duration};
+	use crate::backend::mock::{cu_ni};
+	
+	/// A `RemoteEngine` without a worker, to exercise the journal and the deadline checks.
+	fn detached()->RemoteEngine {
+		return RemoteEngine {
+			worker: PathBuf::from("chemapp_worker"),
+			library_name: "mock".to_string(),
+			abi: AbiProfile::platform(),
+			process: RefCell::new(None),
+			respawning: Cell::new(false),
+			restarts: Cell::new(0),
+			deadline: Cell::new(None),
+			journal: RefCell::new(Vec::new()),
+			units: UnitManager::new(),
+		};
+	}
+	
+	/// A `RemoteEngine` whose worker is a thread executing the calls on the Cu-Ni `MockBackend`.
+	fn with_mock_worker()->RemoteEngine {
+		let remote = detached();
+		let (mut calls, input) = io::pipe().unwrap();
+		let (output, mut replies) = io::pipe().unwrap();
+		thread::spawn(move || {
+			let backend = cu_ni();
+			while let Ok(call) = read_frame::<_, Call>(&mut calls) {
+				if write_frame(&mut replies, &execute(&backend, call)).is_err() {break;}
+			}
+		});
+		remote.attach(None, Box::new(input), output);
+		return remote;
+	}
+	
+	/// Pass a call through a frame, as on the pipe to the worker.
+	fn transmit(call: &Call)->Call {
+		let mut pipe = Vec::new();
+		write_frame(&mut pipe, call).unwrap();
+		return read_frame(&mut Cursor::new(pipe)).unwrap();
+	}
+	
+	#[test]
+	fn frames_round_trip(){
+		let calls = vec![
+			Call::Load{library: "libchemapp.so".to_string(), abi: AbiProfile::platform()},
+			Call::Tqsetc{option: "T".to_string(), indexp: 0, indexc: 0, val: 1000.0},
+			Call::Tqstca{idents: "#1".to_string(), indexp: 1, indexc: 2, val: 0.5},
+			Call::Tqcdat{i1: 1, i2: 2, i3: 3, i4: 4, i5: 5, val: -1.5e3},
+		];
+		let replies : Vec<Result<Reply,ChemAppError>> = vec![
+			Ok(Reply::Unit),
+			Ok(Reply::Table(vec![vec![1.0, 2.0], vec![]])),
+			Ok(Reply::Stoichiometry((vec![1.0, 0.0], 63.546))),
+			Err(ChemAppError::native(104)),
+		];
+		let mut pipe = Vec::new();
+		for call in &calls {
+			write_frame(&mut pipe, call).unwrap();
+		}
+		for reply in &replies {
+			write_frame(&mut pipe, reply).unwrap();
+		}
+		let mut reader = Cursor::new(pipe);
+		for call in &calls {
+			let read : Call = read_frame(&mut reader).unwrap();
+			assert_eq!(format!("{:?}", read), format!("{:?}", call));
+		}
+		for reply in &replies {
+			let read : Result<Reply,ChemAppError> = read_frame(&mut reader).unwrap();
+			assert_eq!(format!("{:?}", read), format!("{:?}", reply));
+		}
+		// the input is exhausted, a truncated frame is not a value
+		assert_eq!(read_frame::<_, Call>(&mut reader).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+		let mut truncated = Vec::new();
+		write_frame(&mut truncated, &calls[1]).unwrap();
+		truncated.pop();
+		assert_eq!(read_frame::<_, Call>(&mut Cursor::new(truncated)).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+		// console output in the pipe reads as a huge length prefix
+		let garbage = b"ChemApp version 8".to_vec();
+		assert_eq!(read_frame::<_, Call>(&mut Cursor::new(garbage)).unwrap_err().kind(), io::ErrorKind::InvalidData);
+	}
+	
+	#[test]
+	fn serve_rejects_calls_before_load(){
+		let mut input = Vec::new();
+		write_frame(&mut input, &Call::Tqini).unwrap();
+		let mut output = Vec::new();
+		serve(&mut Cursor::new(input), &mut output).unwrap();
+		let reply : Result<Reply,ChemAppError> = read_frame(&mut Cursor::new(output)).unwrap();
+		assert!(matches!(reply, Err(ChemAppError::WorkerError(_))));
+	}
+	
+	#[test]
+	fn journal_replay_restores_state(){
+		let remote = detached();
+		let original = cu_ni();
+		let directory = tempfile::tempdir().unwrap();
+		let file = |name: &str| directory.path().join(name).to_string_lossy().into_owned();
+		let calls = vec![
+			Call::Tqsetc{option: "T".to_string(), indexp: 0, indexc: 0, val: 1000.0},
+			Call::Tqremc{numcon: -2},
+			Call::Tqsetc{option: "T".to_string(), indexp: 0, indexc: 0, val: 1600.0},
+			Call::Tqsetc{option: "IA".to_string(), indexp: 0, indexc: 1, val: 0.3},
+			Call::Tqsetc{option: "IA".to_string(), indexp: 0, indexc: 2, val: 0.7},
+			Call::Tqremc{numcon: 2},
+			Call::Tqcsp{indexp: 2, status: "ELIMINATED".to_string()},
+			Call::Tqcio{option: "LIST".to_string(), unit: 21},
+			Call::Tqcio{option: "LIST".to_string(), unit: 22},
+			Call::Tqopen{filename: file("list.txt"), unit: 22},
+			Call::Tqopen{filename: file("other.txt"), unit: 23},
+			Call::Tqclos{unit: 23},
+			Call::Tqgsp{indexp: 2},
+			// a failed call is not recorded
+			Call::Tqcsp{indexp: 3, status: "ENTERED".to_string()},
+		];
+		for call in calls {
+			if execute(&original, call.clone()).is_ok() {
+				remote.record(&call);
+			}
+		}
+		let journal = remote.journal();
+		assert_eq!(journal.len(), 5);
+		assert!(!journal.iter().any(|c| matches!(c, Call::Tqremc{..} | Call::Tqgsp{..} | Call::Tqopen{unit: 23, ..} | Call::Tqcio{unit: 21, ..})));
+		
+		// a restarted worker receives the journal
+		let restarted = cu_ni();
+		for call in &journal {
+			execute(&restarted, transmit(call)).unwrap();
+		}
+		assert_eq!(restarted.conditions(), original.conditions());
+		assert_eq!(restarted.conditions(), vec![("T".to_string(), 0, 0, 1600.0), ("IA".to_string(), 0, 2, 0.7)]);
+		assert_eq!(restarted.tqgsp(2).unwrap(), "ELIMINATED");
+		assert_eq!(restarted.tqgio("LIST").unwrap(), 22);
+		assert_eq!(restarted.units().in_use(), vec![22]);
+		assert_eq!(original.units().in_use(), vec![22]);
+		
+		remote.record(&Call::Tqini);
+		assert!(remote.journal().is_empty());
+	}
+	
+	#[test]
+	fn datafile_loads_are_journaled(){
+		let remote = with_mock_worker();
+		Calculator::load_datafile(&remote, "first.dat").unwrap();
+		remote.tqsetc("T", 0, 0, 1000.0).unwrap();
+		remote.tqcio("LIST", 21).unwrap();
+		assert_eq!(remote.journal().len(), 3);
+		assert_eq!(remote.datafile().as_deref(), Some("first.dat"));
+		
+		// the new datafile replaces the earlier one and its conditions, the I/O routing is kept
+		Calculator::load_datafile(&remote, "other.cst").unwrap();
+		let journal = remote.journal();
+		assert_eq!(journal.len(), 2);
+		assert!(matches!(&journal[0], Call::Tqcio{option, unit: 21} if option == "LIST"));
+		assert!(matches!(&journal[1], Call::Datafile{filename, kind: FileKind::Transparent, ..} if filename == "other.cst"));
+		assert_eq!(remote.datafile().as_deref(), Some("other.cst"));
+		assert!(remote.units().in_use().is_empty());
+		
+		// a restarted worker loads it again
+		let restarted = cu_ni();
+		for call in &journal {
+			execute(&restarted, transmit(call)).unwrap();
+		}
+		assert_eq!(restarted.tqgio("LIST").unwrap(), 21);
+		assert!(restarted.units().in_use().is_empty());
+		
+		remote.tqini().unwrap();
+		assert!(remote.journal().is_empty());
+		assert_eq!(remote.datafile(), None);
+	}
+	
+	#[test]
+	fn journal_keeps_one_entry_per_setting(){
+		let remote = detached();
+		for step in 0..1000 {
+			let t = 1000.0 + step as f64;
+			remote.record(&Call::Tqsetc{option: "T".to_string(), indexp: 0, indexc: 0, val: t});
+			remote.record(&Call::Tqsetc{option: "IA".to_string(), indexp: 0, indexc: 1, val: 1.0});
+			remote.record(&Call::Tqclim{option: "TLOW".to_string(), val: t - 100.0});
+			remote.record(&Call::Tqclim{option: "THIGH".to_string(), val: t + 100.0});
+			remote.record(&Call::Tqcsp{indexp: 2, status: "ELIMINATED".to_string()});
+			remote.record(&Call::Tqcspc{indexp: 1, indexc: 2, status: "DORMANT".to_string()});
+			remote.record(&Call::Tqcsu{option: "Temperature".to_string(), unit: "C".to_string()});
+			remote.record(&Call::Tqcdat{i1: 1, i2: 1, i3: 1, i4: 1, i5: 1, val: t});
+		}
+		let journal = remote.journal();
+		assert_eq!(journal.len(), 8);
+		assert!(matches!(&journal[0], Call::Tqsetc{option, val, ..} if option == "T" && *val == 1999.0));
+		assert!(journal.iter().any(|c| matches!(c, Call::Tqclim{option, val} if option == "THIGH" && *val == 2099.0)));
+		
+		// a removed condition renumbers the later ones, as in the library
+		remote.record(&Call::Tqstec{option: "IA".to_string(), indexp: 2, val: 0.5});
+		remote.record(&Call::Tqremc{numcon: 1});
+		remote.record(&Call::Tqremc{numcon: 2});
+		let conditions : Vec<Call> = remote.journal().into_iter().filter(|c| condition_key(c).is_some()).collect();
+		assert_eq!(conditions.len(), 1);
+		assert!(matches!(&conditions[0], Call::Tqsetc{option, indexc: 1, ..} if option == "IA"));
+		remote.record(&Call::Tqremc{numcon: -2});
+		assert_eq!(remote.journal().len(), 6);
+	}
+	
+	#[test]
+	fn expired_deadline_fails_before_sending(){
+		let remote = detached();
+		remote.set_deadline(Some(Instant::now() - Duration::from_millis(1))).unwrap();
+		assert!(matches!(remote.tqsetc("T", 0, 0, 1000.0), Err(ChemAppError::Timeout(_))));
+		assert!(remote.journal().is_empty());
+		assert_eq!(remote.restarts(), 0);
+		assert_eq!(remote.pid(), None);
+	}
+	
+}
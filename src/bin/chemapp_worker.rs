@@ -0,0 +1,58 @@
+// chemapp_worker.rs
+//! Worker process for `RemoteEngine`: hosts an `Engine`, reads bincode-encoded calls from stdin and writes the replies to stdout.
+use std::io::{self, Write, BufWriter};
+use chemapp_rs::backend::remote::{serve};
+
+/// The stream replies are written to. The library writes its console output (Fortran unit 6) to stdout, so on Unix-like platforms the original stdout is kept for the protocol and file descriptor 1 is pointed to stderr.
+#[cfg(target_family="unix")]
+fn protocol_output()->Box<dyn Write> {
+	use std::fs::File;
+	use std::os::fd::FromRawFd;
+	extern "C" {
+		fn dup(fd: i32)->i32;
+		fn dup2(fd: i32, fd2: i32)->i32;
+	}
+	unsafe {
+		let fd = dup(1);
+		if fd < 0 || dup2(2, 1) < 0 {
+			return Box::new(io::stdout());
+		}
+		return Box::new(File::from_raw_fd(fd));
+	}
+}
+
+/// The stream replies are written to. As on Unix-like platforms, the original stdout is kept for the protocol, while the C runtime descriptor 1 and the standard output handle the library writes its console output to are pointed to stderr.
+#[cfg(target_family="windows")]
+fn protocol_output()->Box<dyn Write> {
+	use std::fs::File;
+	use std::ffi::c_void;
+	use std::os::windows::io::FromRawHandle;
+	const STD_OUTPUT_HANDLE : u32 = -11i32 as u32;
+	const STD_ERROR_HANDLE  : u32 = -12i32 as u32;
+	extern "C" {
+		fn _dup(fd: i32)->i32;
+		fn _dup2(fd: i32, fd2: i32)->i32;
+		fn _get_osfhandle(fd: i32)->isize;
+	}
+	extern "system" {
+		fn GetStdHandle(handle: u32)->*mut c_void;
+		fn SetStdHandle(handle: u32, value: *mut c_void)->i32;
+	}
+	unsafe {
+		let fd = _dup(1);
+		let handle = if fd < 0 {-1} else {_get_osfhandle(fd)};
+		if handle == -1 || _dup2(2, 1) < 0 || SetStdHandle(STD_OUTPUT_HANDLE, GetStdHandle(STD_ERROR_HANDLE)) == 0 {
+			return Box::new(io::stdout());
+		}
+		return Box::new(File::from_raw_handle(handle as *mut c_void));
+	}
+}
+
+pub fn main(){
+	let mut input = io::stdin().lock();
+	let mut output = BufWriter::new(protocol_output());
+	if let Err(error) = serve(&mut input, &mut output) {
+		eprintln!("{}", error);
+		std::process::exit(1);
+	}
+}
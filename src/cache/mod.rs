@@ -7,12 +7,12 @@ use std::collections::{HashMap};
 
 use super::calculator::{Calculator};
 use crate::error::{ChemAppError};
-use crate::{Engine};
+use crate::backend::{ChemAppBackend};
 
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-impl Calculator {
+impl<B: ChemAppBackend> Calculator<B> {
 	
 	/// Creates an instance of `ParameterCache` for changing model parameter values and restoring them back to the original values
 	pub fn generate_parameter_cache<T: AsRef<str> + std::fmt::Debug>(&mut self, phasenames: &[T], include_ge: bool, include_magn: bool, include_endm: bool, include_cmp: bool)->Result<(),ChemAppError> {
@@ -37,7 +37,7 @@ pub struct InteractionGEMQM {
 
 impl InteractionGEMQM {
 	
-	pub fn reset(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		for k in 0..6 {
 			engine.tqcdat(13,self.index,1,k+1,self.indexp,self.values[k])?;
 		}
@@ -60,7 +60,7 @@ pub struct InteractionMagnMQM {
 
 impl InteractionMagnMQM {
 	
-	pub fn reset(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		//todo!();
 		return Ok(());
 	}
@@ -81,7 +81,7 @@ pub struct Endmember {
 
 impl Endmember {
 	
-	pub fn reset(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		engine.tqcdat(1,0,0,self.indexc,self.indexp, self.h298)?; // H298
 		engine.tqcdat(1,0,1,self.indexc,self.indexp, self.s298)?; // S298
 		return Ok(());
@@ -101,7 +101,7 @@ pub struct Compound {
 
 impl Compound {
 	
-	pub fn reset(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		engine.tqcdat(1,0,0,1,self.indexp, self.h298)?; // H298
 		engine.tqcdat(1,0,1,1,self.indexp, self.s298)?; // S298
 		return Ok(());
@@ -129,7 +129,7 @@ pub struct ParameterCache {
 impl ParameterCache {
 	
 	/// load H,S for a compound into the cache
-	pub fn load_compound(calculator: &Calculator, phasename: &str)->Result<Compound,ChemAppError> {
+	pub fn load_compound<B: ChemAppBackend>(calculator: &Calculator<B>, phasename: &str)->Result<Compound,ChemAppError> {
 		let indexp = calculator.engine.tqinp(phasename)?;
 		let h298 = calculator.engine.tqgdat(indexp, 1, "H", 0)?[0];
 		let s298 = calculator.engine.tqgdat(indexp, 1, "S", 0)?[0];
@@ -142,7 +142,7 @@ impl ParameterCache {
 	}
 	
 	/// load H,S for a constituent (endmember) into the cache
-	pub fn load_endmembers(calculator: &Calculator, phasename: &str)->Result<Vec<Endmember>,ChemAppError>{
+	pub fn load_endmembers<B: ChemAppBackend>(calculator: &Calculator<B>, phasename: &str)->Result<Vec<Endmember>,ChemAppError>{
 		let mut vecc : Vec<Endmember> = Vec::new();
 		let indexp = calculator.engine.tqinp(phasename)?;
 		let nendm = calculator.engine.tqnopc(indexp)?;
@@ -163,7 +163,7 @@ impl ParameterCache {
 	}
 	
 	/// load excess Gibbs interactions for a solution phase
-	pub fn load_interactions_ge(calculator: &Calculator, phasename: &str)->Result<Vec<InteractionGEMQM>,ChemAppError>{
+	pub fn load_interactions_ge<B: ChemAppBackend>(calculator: &Calculator<B>, phasename: &str)->Result<Vec<InteractionGEMQM>,ChemAppError>{
 		let indexp = calculator.engine.tqinp(phasename)?;
 		let sinteractions : Vec<String> = calculator.interactions_ge_expanded(indexp)?;
 		let mut interactions : Vec<InteractionGEMQM> = Vec::new();
@@ -182,13 +182,13 @@ impl ParameterCache {
 	}
 	
 	/// load magnetic interactions for a solution phase
-	pub fn load_interactions_magn(calculator: &Calculator, phase: &str)->Result<Vec<InteractionMagnMQM>,ChemAppError>{
+	pub fn load_interactions_magn<B: ChemAppBackend>(calculator: &Calculator<B>, phase: &str)->Result<Vec<InteractionMagnMQM>,ChemAppError>{
 		//todo!();
 		return Ok(vec![]);
 	}
 	
 	/// Initialize a new instance
-	pub fn new<T: AsRef<str> + std::fmt::Debug>(calculator: & Calculator, phasenames: &[T], include_ge: bool, include_magn: bool, include_endm: bool, include_cmp: bool)->Result<Self,ChemAppError> {
+	pub fn new<B: ChemAppBackend, T: AsRef<str> + std::fmt::Debug>(calculator: &Calculator<B>, phasenames: &[T], include_ge: bool, include_magn: bool, include_endm: bool, include_cmp: bool)->Result<Self,ChemAppError> {
 		let compounds : Vec<Compound> = Vec::new();
 		let endmembers : Vec<Endmember> = Vec::new();
 		let mut interactions_ge : Vec<InteractionGEMQM> = Vec::new();
@@ -239,7 +239,7 @@ impl ParameterCache {
 	}
 	
 	/// Set all parameters in the datafile to the initial values from the cache.
-	pub fn reset_all(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset_all<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		self.reset_compounds(engine)?;
 		self.reset_endmembers(engine)?;
 		self.reset_interactions_ge(engine)?;
@@ -248,7 +248,7 @@ impl ParameterCache {
 	}
 	
 	/// Reset H,S for all compounds in the datafile to the initial values from the cache.
-	pub fn reset_compounds(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset_compounds<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		for k in 0..self.compounds.len(){
 			self.compounds[k].reset(engine)?;
 		}
@@ -256,7 +256,7 @@ impl ParameterCache {
 	}
 	
 	/// Reset H,S for all endmembers in the datafile to the initial values from the cache.
-	pub fn reset_endmembers(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset_endmembers<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		for k in 0..self.endmembers.len(){
 			self.endmembers[k].reset(engine)?;
 		}
@@ -264,7 +264,7 @@ impl ParameterCache {
 	}
 	
 	/// Reset interactions (excess Gibbs) in the datafile to the initial values from the cache.
-	pub fn reset_interactions_ge(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset_interactions_ge<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		for k in 0..self.interactions_ge.len(){
 			self.interactions_ge[k].reset(engine)?;
 		}
@@ -272,14 +272,14 @@ impl ParameterCache {
 	}
 	
 	/// Reset magnetic interactions in the datafile to the initial values from the cache.
-	pub fn reset_interactions_magn(&self, engine: &Engine)->Result<(),ChemAppError>{
+	pub fn reset_interactions_magn<B: ChemAppBackend>(&self, engine: &B)->Result<(),ChemAppError>{
 		for k in 0..self.interactions_magn.len(){
 			self.interactions_magn[k].reset(engine)?;
 		}
 		return Ok(());
 	}
 	
-	pub fn set_interaction_ge(&self, engine: &Engine, phase: &str, interaction: &str, value: f64, tindex: usize, isdelta: bool)->Result<bool,ChemAppError>{
+	pub fn set_interaction_ge<B: ChemAppBackend>(&self, engine: &B, phase: &str, interaction: &str, value: f64, tindex: usize, isdelta: bool)->Result<bool,ChemAppError>{
 		//println!("LOOKING for ({:?},{:?}) in {:?}", phase, interaction, &self.lookup_ge);
 		match self.lookup_ge.get(&(phase.to_string(),interaction.to_string())){
 			Some(index) => {
@@ -296,11 +296,11 @@ impl ParameterCache {
 		
 	}
 	
-	pub fn set_interaction_magn(&self, engine: &Engine, phase: &str, interaction: &str, value: f64, tindex: usize, isdelta: bool)->Result<bool,ChemAppError>{
+	pub fn set_interaction_magn<B: ChemAppBackend>(&self, engine: &B, phase: &str, interaction: &str, value: f64, tindex: usize, isdelta: bool)->Result<bool,ChemAppError>{
 		todo!();
 	}
 	
-	pub fn set_compound_h298(&self, engine: &Engine, phase: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
+	pub fn set_compound_h298<B: ChemAppBackend>(&self, engine: &B, phase: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
 		match self.lookup_cmp.get(phase) {
 			Some(index) => {
 				let cmp = &self.compounds[*index];
@@ -313,7 +313,7 @@ impl ParameterCache {
 		}
 	}
 	
-	pub fn set_compound_s298(&self, engine: &Engine, phase: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
+	pub fn set_compound_s298<B: ChemAppBackend>(&self, engine: &B, phase: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
 		match self.lookup_cmp.get(phase) {
 			Some(index) => {
 				let cmp = &self.compounds[*index];
@@ -326,7 +326,7 @@ impl ParameterCache {
 		}
 	}
 	
-	pub fn set_endmember_h298(&self, engine: &Engine, phase: &str, constituent: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
+	pub fn set_endmember_h298<B: ChemAppBackend>(&self, engine: &B, phase: &str, constituent: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
 		match self.lookup_endm.get(&(phase.to_string(),constituent.to_string())) {
 			Some(index) => {
 				let endm = &self.endmembers[*index];
@@ -340,7 +340,7 @@ impl ParameterCache {
 		}
 	}
 	
-	pub fn set_endmember_s298(&self, engine: &Engine, phase: &str, constituent: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
+	pub fn set_endmember_s298<B: ChemAppBackend>(&self, engine: &B, phase: &str, constituent: &str, value: f64, isdelta: bool)->Result<bool,ChemAppError> {
 		match self.lookup_endm.get(&(phase.to_string(),constituent.to_string())) {
 			Some(index) => {
 				let endm = &self.endmembers[*index];
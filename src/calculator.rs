@@ -6,21 +6,33 @@ use std::fmt;
 use std::path::Path;
 use std::ffi::OsStr;
 use std::ops::{Range};
+use std::time::{Duration, Instant};
 use nalgebra::{DVector, SVector, Vector, Dim, Storage};
-use tempfile::NamedTempFile;
+use tempfile::{NamedTempFile, TempPath};
 use chemformula::{Transform};
 
 use crate::{Engine, error::{ChemAppError}};
+use crate::abi::{AbiProfile};
+use crate::backend::{ChemAppBackend};
+use crate::units::{FileKind};
+use crate::options::{Condition, ResultQuantity, TargetVariable, MapOption};
 use crate::cache::{ParameterCache};
 use crate::snapshot::CalculatorSnapshot;
+use crate::result::{EquilibriumResult, CalculationKind, CalculationInputs};
 use crate::parse::*;
 use crate::iterator::SystemComponentIterator;
 use crate::iterator::PhaseIterator;
 use crate::entities::system::System;
+use crate::problem::EquilibriumProblem;
+use crate::step::{Step, StepVariable};
+use crate::reaction::{Reaction};
 
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
+/// Upper bound on the points of a one-dimensional mapping (`tqmap`/`tqmapl`), in case ChemApp keeps reporting more.
+const MAPPING_POINTS_MAX : usize = 1000;
+
 /// A helper function to tell whether we deal with an open format *.DAT, transparent header *.CST, or binary formats/
 fn get_extension_from_filename(filename: &str)->Option<String>{
 	return Path::new(filename).extension().and_then(|s| OsStr::to_str(s).and_then(|s| Some(s.to_lowercase())));
@@ -29,38 +41,71 @@ fn get_extension_from_filename(filename: &str)->Option<String>{
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-/// A higher-level abtraction entity.
+/// A higher-level abtraction entity, generic over the backend executing the ChemApp routines (a loaded library `Engine` by default).
 #[derive(Debug)]
-pub struct Calculator {
+pub struct Calculator<B: ChemAppBackend = Engine> {
 	/// a loaded instance of ChemApp engine
-	pub engine: Engine,
+	pub engine: B,
 	/// a copy of model parameters, allowing to restore delta inputs
 	pub cache: Option<ParameterCache>, 
 	/// datafile 
 	pub file: String, 
 	/// a custom file to output errors
 	pub nondefault_errunit : Option<(String,usize)>,
+	/// the temporary file behind `nondefault_errunit` (deleted on drop) and the error unit it replaced
+	error_file : Option<(TempPath,usize)>,
 	/// isothermal calculation counter
 	pub number_isothermal: usize,
 	/// target calculation counter
 	pub number_target_t: usize,
 	 /// instead of raw input using the system components basis, the user can define a custom formula basis; the transform is handled internally
 	pub transform: Transform,
+	/// the formulas of the transform basis
+	pub basis: Vec<String>,
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Tolerances of the composition-target search `Calculator::calculate_target_x_from_left_with`.
+#[derive(Debug,Clone)]
+pub struct TargetXOptions {
+	/// number of equal steps from `x1` to `x2` scanned to bracket the first appearance of the phase
+	pub scan_steps : usize,
+	/// width of the final bracket, as a fraction of the way from `x1` to `x2`
+	pub tolerance : f64,
+	/// maximal number of bisections
+	pub max_iterations : usize,
+	/// equilibrium amount above which the target phase counts as present
+	pub amount_tolerance : f64,
+}
+
+impl Default for TargetXOptions {
+	fn default()->TargetXOptions {
+		return TargetXOptions {
+			scan_steps : 10,
+			tolerance : 1e-6,
+			max_iterations : 60,
+			amount_tolerance : 1e-12,
+		};
+	}
 }
 
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-impl Default for Calculator {
-	fn default()->Calculator{
+impl<B: ChemAppBackend + Default> Default for Calculator<B> {
+	fn default()->Calculator<B>{
 		return Calculator{
-			engine: Engine::default(),
+			engine: B::default(),
 			cache: None,
 			file: r"".to_string(),
 			nondefault_errunit : None,
+			error_file : None,
 			number_isothermal: 0,
 			number_target_t: 0,
 			transform: Transform::default(),
+			basis: Vec::new(),
 		};
 	}
 }
@@ -68,14 +113,16 @@ impl Default for Calculator {
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-impl Calculator {
-	
-	/***************************************************************************************************************************************************************************************************************************/
-	/***************************************************************************************************************************************************************************************************************************/
+impl Calculator<Engine> {
 	
 	/// Initialize a [`Calculator`] from a ChemApp dll file and a datafile
 	pub fn from_library(libname: & str, datfile: & str)->Result<Calculator, ChemAppError>{
-		let engine = Engine::new(libname).unwrap();
+		return Self::from_library_with_abi(libname, datfile, AbiProfile::platform());
+	}
+	
+	/// Same as `from_library` for a library built with a non-default Fortran binary interface (see `Engine::with_abi`).
+	pub fn from_library_with_abi(libname: &str, datfile: &str, abi: AbiProfile)->Result<Calculator, ChemAppError>{
+		let engine = Engine::with_abi(libname, abi)?;
 		Self::init_engine(&engine, datfile)?;
 		let components : Vec<String> = (0..engine.tqnosc()?).into_iter().map(|idx| engine.tqgnsc(idx+1)).filter_map(|r| r.ok()).collect();
 		let transform = Transform::new(&components, &components, true);
@@ -84,9 +131,11 @@ impl Calculator {
 			cache: None,
 			file: datfile.to_string(),
 			nondefault_errunit: None,
+			error_file: None,
 			number_isothermal: 0,
 			number_target_t: 0,
 			transform: transform.unwrap(),
+			basis: components,
 		});
 	}
 	
@@ -99,9 +148,38 @@ impl Calculator {
 			cache : None,
 			file  : "".to_string(),
 			nondefault_errunit : None,
+			error_file : None,
 			number_isothermal : 0,
 			number_target_t : 0,
 			transform : Transform::default(),
+			basis : Vec::new(),
+		});
+	}
+	
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+impl<B: ChemAppBackend> Calculator<B> {
+	
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+	
+	/// Wrap an already initialized backend (thermochemical data loaded), the composition basis is set to the system components.
+	pub fn from_backend(engine: B)->Result<Calculator<B>,ChemAppError>{
+		let components : Vec<String> = (0..engine.tqnosc()?).into_iter().map(|idx| engine.tqgnsc(idx+1)).filter_map(|r| r.ok()).collect();
+		let transform = Transform::new(&components, &components, true).map_err(|e| ChemAppError::OtherError(format!("{:?}", e)))?;
+		return Ok(Calculator {
+			engine: engine,
+			cache: None,
+			file: "".to_string(),
+			nondefault_errunit: None,
+			error_file: None,
+			number_isothermal: 0,
+			number_target_t: 0,
+			transform: transform,
+			basis: components,
 		});
 	}
 	
@@ -109,14 +187,14 @@ impl Calculator {
 	/***************************************************************************************************************************************************************************************************************************/
 	
 	/// Initializes the ChemApp interface and preconfigures it with the thermodynamic info from a datafile.
-	pub fn init_engine(engine: &Engine, datfile: &str)->Result<(),ChemAppError>{
+	pub fn init_engine(engine: &B, datfile: &str)->Result<(),ChemAppError>{
 		engine.tqini()?;
 		Self::load_datafile(engine, datfile)?;
 		return Ok(());
 	}
 	
 	/// A higher-level abstraction over datafile handling, this function is only needed to be called once while the datafile type (open or transparent header) is automatically detected.
-	pub fn load_datafile(engine: &Engine, datfile: &str)->Result<(),ChemAppError>{
+	pub fn load_datafile(engine: &B, datfile: &str)->Result<(),ChemAppError>{
 		let res = get_extension_from_filename(datfile);
 		match res {
 			Some(extension) => {
@@ -124,21 +202,21 @@ impl Calculator {
 				match extension.as_ref() {
 					"dat" => {
 						// loading ASCII file
-						engine.tqopna(datfile, 10)?; // unit number
+						let file = engine.open_file(datfile, FileKind::Ascii)?;
 						engine.tqrfil()?;
-						engine.tqclos(10)?;
+						file.close()?;
 					}
 					"cst" => {
 						// loading Transparent-Header File
-						engine.tqopnt(datfile, 10)?;
+						let file = engine.open_file(datfile, FileKind::Transparent)?;
 						engine.tqrcst()?;
-						engine.tqclos(10)?;
+						file.close()?;
 					}
 					"bin" => {
 						// loading a Binary File
-						engine.tqopnb(datfile, 10)?;
+						let file = engine.open_file(datfile, FileKind::Binary)?;
 						engine.tqrbin()?;
-						engine.tqclos(10)?;
+						file.close()?;
 					}
 					_ => {
 						return Err(ChemAppError::OtherError(format!("{} is not a recognized datafile extension for {}", extension, datfile)));
@@ -154,21 +232,68 @@ impl Calculator {
 	/// Set a formula transform for input compositions
 	pub fn set_transform<T: AsRef<str>>(&mut self, basis: &[T])->Result<(),ChemAppError>{
 		self.transform = Transform::new(&self.components().map(|c| c.name()).collect::<Vec<String>>(), basis, true).unwrap();
+		self.basis = basis.iter().map(|b| b.as_ref().to_string()).collect();
 		return Ok(());
 	}
 	/// Internally, creates a temporary file (deleted once the current `Calculator` instance is dropped) to redirect ChemApp outputs; this is a useful feature in environments where console window is not available.
 	pub fn redirect_error_to_temp(&mut self)->Result<(),ChemAppError>{
-		let parent = Path::new(&self.engine.library_name).parent().expect("Does not have a parent directory").to_path_buf();
-		let temp_file = NamedTempFile::new_in(parent).unwrap();
-		let temp_file_ = temp_file.keep().unwrap().1;
-		let filename : String = temp_file_.to_string_lossy().into_owned();
-		let unit = 30;
-		self.engine.tqopen(&filename,unit)?;
-		self.engine.tqcio("ERROR",unit)?;
+		let parent = Path::new(self.engine.library_name()).parent().filter(|p| !p.as_os_str().is_empty()).map(|p| p.to_path_buf()).unwrap_or(std::env::temp_dir());
+		let temp_path = NamedTempFile::new_in(parent).map_err(|e| ChemAppError::OtherError(format!("Cannot create a temporary file: {}", e)))?.into_temp_path();
+		let filename : String = temp_path.to_string_lossy().into_owned();
+		self.close_error_file();
+		let previous = self.engine.tqgio("ERROR")?;
+		let file = self.engine.open_file(&filename, FileKind::Other)?;
+		self.engine.tqcio("ERROR",file.unit())?;
+		// the file stays open for the lifetime of the calculator, closed in `drop`
+		let unit = file.detach();
 		self.nondefault_errunit = Some((filename,unit));
+		self.error_file = Some((temp_path, previous));
 		return Ok(());
 	}
 	
+	/// Route the errors back to the unit they went to before `redirect_error_to_temp`, close the error file (if any) and delete it.
+	fn close_error_file(&mut self){
+		if let Some((_, unit)) = self.nondefault_errunit.take() {
+			if let Some((_, previous)) = &self.error_file {
+				let _ = self.engine.tqcio("ERROR", *previous);
+			}
+			let _ = self.engine.tqclos(unit);
+		}
+		self.error_file = None;
+	}
+	
+	/// Run `func` with the ChemApp output unit `option` (`"LIST"` for the `tqshow`/`tqcel`/`tqcprt` output, `"ERROR"` for error messages) redirected into a temporary file and return the text written to it;
+	/// the previous routing is restored afterwards.
+	pub fn capture_output<T, F: FnOnce(&Self)->Result<T,ChemAppError>>(&self, option: &str, func: F)->Result<(T,String),ChemAppError>{
+		let previous = self.engine.tqgio(option)?;
+		let directory = tempfile::Builder::new().prefix("chemapp_rs").tempdir().map_err(|e| ChemAppError::OtherError(format!("Cannot create a temporary directory: {}", e)))?;
+		let path = directory.path().join("output.txt");
+		let file = self.engine.open_file(&path.to_string_lossy(), FileKind::Other)?;
+		self.engine.tqcio(option, file.unit())?;
+		// declared after `file`, so that a panic in `func` restores the routing before the file is closed
+		let routing = RoutingGuard {backend: &self.engine, option, previous: Some(previous)};
+		let result = func(self);
+		let restored = routing.restore();
+		// closing flushes the Fortran buffers
+		let closed = file.close();
+		let value = result?;
+		restored?;
+		closed?;
+		let text = std::fs::read(&path).map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+			.map_err(|e| ChemAppError::OtherError(format!("Cannot read the captured output {}: {}", path.display(), e)))?;
+		return Ok((value, text));
+	}
+	
+	/// The present settings as listed by `tqshow`.
+	pub fn show_settings(&self)->Result<String,ChemAppError>{
+		return Ok(self.capture_output("LIST", |c| c.engine.tqshow())?.1);
+	}
+	
+	/// The copyright message printed by `tqcprt`.
+	pub fn copyright(&self)->Result<String,ChemAppError>{
+		return Ok(self.capture_output("LIST", |c| c.engine.tqcprt())?.1);
+	}
+	
 	/***************************************************************************************************************************************************************************************************************************/
 	/***************************************************************************************************************************************************************************************************************************/
 	
@@ -178,20 +303,29 @@ impl Calculator {
 		return Ok(());
 	}
 	
+	/// Run `func` with a deadline `timeout` from now; a backend call still running at the deadline is aborted, the backend is restarted with the datafile and the conditions restored, and `ChemAppError::Timeout` is returned.
+	/// Requires a backend supporting deadlines (`RemoteEngine`), e.g. `calculator.with_deadline(Duration::from_secs(30), |c| c.calculate_target_t(&x, 1, 2, (300.0, 3000.0), false, None, None))`.
+	pub fn with_deadline<T, F: FnOnce(&Self)->Result<T,ChemAppError>>(&self, timeout: Duration, func: F)->Result<T,ChemAppError>{
+		self.engine.set_deadline(Some(Instant::now() + timeout))?;
+		// clears the deadline also when `func` panics
+		let _guard = DeadlineGuard(&self.engine);
+		return func(self);
+	}
+	
 	/***************************************************************************************************************************************************************************************************************************/
 	/***************************************************************************************************************************************************************************************************************************/
 	
 	/// Global system properties accessor.
-	pub fn system(&self)->System<'_>{
+	pub fn system(&self)->System<'_, B>{
 		return System::new(self);
 	}
 	
 	/// Iterates over system component indices.
-	pub fn components(&self)->SystemComponentIterator<'_> {
+	pub fn components(&self)->SystemComponentIterator<'_, B> {
 		return SystemComponentIterator::new(self);
 	}
 	/// Iterates over phase indices.
-	pub fn phases(&self)->PhaseIterator<'_> {
+	pub fn phases(&self)->PhaseIterator<'_, B> {
 		return PhaseIterator::new(self);
 	}
 	
@@ -217,50 +351,114 @@ impl Calculator {
 		return Ok(());
 	}
 	
+	/// Index of a system component by name.
+	pub fn component_index(&self, name: &str)->Result<usize,ChemAppError>{
+		for k in 1..=self.engine.tqnosc()? {
+			if self.engine.tqgnsc(k)?.trim() == name.trim() {return Ok(k);}
+		}
+		return Err(ChemAppError::OtherError(format!("'{}' is not a system component of {}", name, self.datafile_label())));
+	}
+	
+	/// Index of a phase by name.
+	pub fn phase_index(&self, name: &str)->Result<usize,ChemAppError>{
+		for k in 1..=self.engine.tqnop()? {
+			if self.engine.tqgnp(k)?.trim() == name.trim() {return Ok(k);}
+		}
+		return Err(ChemAppError::OtherError(format!("'{}' is not a phase of {}", name, self.datafile_label())));
+	}
+	
+	/// Index of a phase constituent by name within the phase `indexp`.
+	pub fn constituent_index(&self, indexp: usize, name: &str)->Result<usize,ChemAppError>{
+		for k in 1..=self.engine.tqnopc(indexp)? {
+			if self.engine.tqgnpc(indexp, k)?.trim() == name.trim() {return Ok(k);}
+		}
+		return Err(ChemAppError::OtherError(format!("'{}' is not a constituent of phase '{}'", name, self.engine.tqgnp(indexp)?.trim())));
+	}
+	
+	/// The datafile name used in error messages.
+	fn datafile_label(&self)->String {
+		if self.file.is_empty() {
+			return "the loaded datafile".to_string();
+		}
+		return format!("datafile {}", self.file);
+	}
+	
+	/// Start an equilibrium problem with name-based inputs, see [`EquilibriumProblem`].
+	pub fn problem(&self)->EquilibriumProblem<'_, B>{
+		return EquilibriumProblem::new(self);
+	}
+
+	/// Start a step calculation of `variable` over `range`, see [`Step`].
+	pub fn step(&self, variable: StepVariable, range: (f64,f64))->Step<'_, B>{
+		return Step::new(self, variable, range);
+	}
+
+	/// Parse a reaction between phase constituents, e.g. `2 Fe + 1.5 O2 = Fe2O3`, see [`Reaction`].
+	pub fn reaction(&self, equation: &str)->Result<Reaction<'_, B>,ChemAppError>{
+		return Reaction::new(self, equation);
+	}
+	
 	/***************************************************************************************************************************************************************************************************************************/
 	/***************************************************************************************************************************************************************************************************************************/
 	
-		/// set (tlower, thigh) limits
+	/// set (tlower, thigh) limits; panics if ChemApp rejects them, see `try_set_clim`
+	#[deprecated(note = "panics on rejected limits, use `try_set_clim`")]
 	pub fn set_clim(&self, interval: (f64,f64), inverse_order: bool){
-		if inverse_order {
-			let res = self.engine.tqclim("THIGH", interval.1);
-			match res {
-				Ok(_)  => {self.engine.tqclim("TLOW", interval.0).unwrap();}
-				Err(_) => {self.set_clim(interval, false);}
-			}
-		} else {
-			let res = self.engine.tqclim("TLOW", interval.0);
-			match res {
-				Ok(_)  => {self.engine.tqclim("THIGH", interval.1).unwrap();}
-				Err(_) => {self.set_clim(interval, true);}
-			}
+		self.try_set_clim(interval, inverse_order).unwrap();
+	}
+	
+	/// set (tlower, thigh) limits, starting with THIGH if `inverse_order`; a rejected first limit (e.g. TLOW above the current THIGH) is retried in the other order
+	pub fn try_set_clim(&self, interval: (f64,f64), inverse_order: bool)->Result<(),ChemAppError>{
+		let (first, second) = if inverse_order {(("THIGH", interval.1), ("TLOW", interval.0))} else {(("TLOW", interval.0), ("THIGH", interval.1))};
+		if self.engine.tqclim(first.0, first.1).is_err() {
+			self.engine.tqclim(second.0, second.1)?;
+			self.engine.tqclim(first.0, first.1)?;
+			return Ok(());
 		}
+		self.engine.tqclim(second.0, second.1)?;
+		return Ok(());
 	}
 	
 	/***************************************************************************************************************************************************************************************************************************/
 	/***************************************************************************************************************************************************************************************************************************/
 	
 	/// A simple isothermal calculation (temperature + initial composition in the pre-transformed basis).
-	fn calculate_isothermal_(&self, x_i: &DVector<f64>, temp: f64)->Result<(),ChemAppError>{
+	pub(crate) fn calculate_isothermal_(&self, x_i: &DVector<f64>, temp: f64)->Result<(),ChemAppError>{
 		self.reset()?;
-		self.engine.tqsetc("T", 0, 0, temp)?;
-		for k in 0..x_i.len(){self.engine.tqsetc("IA", 0, k+1, x_i[k])?;}
+		self.engine.set_condition(Condition::T, 0, 0, temp)?;
+		for k in 0..x_i.len(){self.engine.set_condition(Condition::IA, 0, k+1, x_i[k])?;}
 		//self.engine.tqshow();
-		self.engine.tqce(" ", 0, 0, (10.0, 6000.0))?;
+		self.engine.calculate(TargetVariable::None, 0, 0, (10.0, 6000.0))?;
 		//self.number_isothermal += 1;
 		return Ok(());
 	}
 	/// Perform a no-target isothermal calculation for an input composition and a temperature, use dynamic vectors; TODO check the composition transformations
-	pub fn calculate_isothermal<D: Dim, S: Storage<f64,D>>(& self, compositions: &Vector<f64,D,S>, temp: f64)->Result<(),ChemAppError>{
-		return self.calculate_isothermal_(&self.transform.transform_final2init(compositions, false, false, false).column(0).into_owned(), temp);
+	pub fn calculate_isothermal<D: Dim, S: Storage<f64,D>>(& self, compositions: &Vector<f64,D,S>, temp: f64)->Result<EquilibriumResult,ChemAppError>{
+		let start = Instant::now();
+		let x_i : DVector<f64> = self.transform.transform_final2init(compositions, false, false, false).column(0).into_owned();
+		self.calculate_isothermal_(&x_i, temp)?;
+		return Ok(EquilibriumResult {
+			kind       : CalculationKind::Isothermal,
+			inputs     : CalculationInputs {
+				composition : DVector::from_iterator(compositions.len(), compositions.iter().cloned()),
+				amounts     : x_i,
+				temperature : Some(temp),
+				..CalculationInputs::default()
+			},
+			target     : None,
+			iterations : 1,
+			elapsed    : start.elapsed(),
+			snapshot   : self.snapshot(),
+		});
 	}
 	
-	fn calculate_target_t_(&self, x_i: &DVector<f64>, masterphase: usize, target: usize, interval: (f64,f64), precipitation: bool, fixed: Option<usize>, adjusting: Option<usize>)->Result<(),ChemAppError>{
+	/// Returns the number of iterations of the fixed/adjusting loop (1 without it), an error if the loop does not converge.
+	fn calculate_target_t_(&self, x_i: &DVector<f64>, masterphase: usize, target: usize, interval: (f64,f64), precipitation: bool, fixed: Option<usize>, adjusting: Option<usize>)->Result<usize,ChemAppError>{
 		// set non-compositional conditions
 		let nitermax = 10usize;
 		let val = if precipitation {-0.5} else {0.0};
-		self.engine.tqsetc("A", target, 0, val)?;
-		self.set_clim(interval, true);
+		self.engine.set_condition(Condition::A, target, 0, val)?;
+		self.try_set_clim(interval, true)?;
 		// set compositions
 		let mut xvar : DVector<f64> = x_i.clone();
 		//let mut xvarprev : DVector<f64> = xvar.clone();
@@ -268,67 +466,170 @@ impl Calculator {
 		match (fixed,adjusting) {
 			(Some(sidxf),Some(sidxa)) => {
 				for iter in 0..nitermax {
-					for k in 0..xvar.len(){self.engine.tqsetc("IA", 0, k+1, xvar[k])?;}
+					for k in 0..xvar.len(){self.engine.set_condition(Condition::IA, 0, k+1, xvar[k])?;}
 					//self.engine.tqshow()?;
-					self.engine.tqce("T", 0, 0, interval)?;
+					self.engine.calculate(TargetVariable::T, 0, 0, interval)?;
 					xvarprev = xvar.clone();
 					let xfold = xvar[sidxf-1];
 					let xaold = xvar[sidxa-1];
-					let xfnew = self.engine.tqgetr("XP", masterphase, sidxf)?;
-					let xanew = self.engine.tqgetr("XP", masterphase, sidxa)?;
-					let tliq  = self.engine.tqgetr("T", 0, 0)?;
+					let xfnew = self.engine.get_result(ResultQuantity::XP, masterphase, sidxf)?;
+					let xanew = self.engine.get_result(ResultQuantity::XP, masterphase, sidxa)?;
+					let tliq  = self.engine.get_result(ResultQuantity::T, 0, 0)?;
 					xvar[sidxa-1] = xvar[sidxf-1]*xanew/xfnew;
 					if iter > 0 {
 						xvar = (&xvar + &xvarprev)*0.5;
 					}
 					//println!("iter = {:?}, tliq = {:?}, xfold = {:?}, xaold = {:?}, xfnew = {:?}, xanew = {:?}, xvarprev = {:?}, xvar = {:?}", &iter, &tliq, &xfold, &xaold, &xfnew, &xanew, &xvarprev, &xvar);
-					if (&xvar-&xvarprev).abs().sum() < 5e-3 {return Ok(());}
+					if (&xvar-&xvarprev).abs().sum() < 5e-3 {return Ok(iter+1);}
 				}
+				return Err(ChemAppError::OtherError(format!("Cannot converge the fixed/adjusting component loop of the T target within {} iterations", nitermax)));
 			}
 			(None,None) => {
-				for k in 0..xvar.len(){self.engine.tqsetc("IA", 0, k+1, xvar[k])?;}
+				for k in 0..xvar.len(){self.engine.set_condition(Condition::IA, 0, k+1, xvar[k])?;}
 				// perform calculation
 				//self.engine.tqshow()?;
-				self.engine.tqce("T", 0, 0, interval)?;
+				self.engine.calculate(TargetVariable::T, 0, 0, interval)?;
 			}
-			_ => {panic!("Both or none of fixed and adjusting system components must be defined");}
+			_ => {return Err(ChemAppError::OtherError("Both or none of fixed and adjusting system components must be defined".to_string()));}
 		}
 		//self.number_target_t += 1;
-		return Ok(());
+		return Ok(1);
 	}
 	/// Perform a T-target calculation for an input composition and a temperature, use dynamic vectors; TODO check the composition transformations
-	pub fn calculate_target_t<D: Dim, S: Storage<f64,D>>(&self, compositions: &Vector<f64,D,S>, masterphase: usize, target: usize, interval: (f64,f64), precipitation: bool, fixed: Option<usize>, adjusting: Option<usize>)->Result<(),ChemAppError>{
-		return self.calculate_target_t_(&self.transform.transform_final2init(compositions, false, false, false).column(0).into_owned(), masterphase, target, interval, precipitation, fixed, adjusting);
-	}
-	
-	fn calculate_target_x_from_left_(&self, x1: &DVector<f64>, x2: &DVector<f64>, temp: f64, target: usize)->Result<(),ChemAppError>{
-		todo!();
-		/*
-		let n_iter_max = 10;
-		let mut x_initial = x1.clone();
-		let mut x_other = x2;
-		for k in 0..n_iter_max{
-			x_initial = (&x_initial + x_other) * 0.5;
-			self.calculate_isothermal(&x_initial, temp)?;
-			if self.phases()?.phases_stable(&self).any(|pid| pid == target) {
-				if self.phases()?.phases_stable(&self).count() > 1 {
-					return Ok(());
-				}
-				//x_other = x2;
+	pub fn calculate_target_t<D: Dim, S: Storage<f64,D>>(&self, compositions: &Vector<f64,D,S>, masterphase: usize, target: usize, interval: (f64,f64), precipitation: bool, fixed: Option<usize>, adjusting: Option<usize>)->Result<EquilibriumResult,ChemAppError>{
+		let start = Instant::now();
+		let x_i : DVector<f64> = self.transform.transform_final2init(compositions, false, false, false).column(0).into_owned();
+		let iterations = self.calculate_target_t_(&x_i, masterphase, target, interval, precipitation, fixed, adjusting)?;
+		return Ok(EquilibriumResult {
+			kind       : CalculationKind::TargetT,
+			inputs     : CalculationInputs {
+				composition : DVector::from_iterator(compositions.len(), compositions.iter().cloned()),
+				amounts     : x_i,
+				interval    : Some(interval),
+				target      : Some(target),
+				..CalculationInputs::default()
+			},
+			target     : Some(self.engine.get_result(ResultQuantity::T, 0, 0)?),
+			iterations,
+			elapsed    : start.elapsed(),
+			snapshot   : self.snapshot(),
+		});
+	}
+	
+	/// Whether the phase `target` is present in the current equilibrium.
+	fn phase_present(&self, target: usize, tolerance: f64)->Result<bool,ChemAppError>{
+		return Ok(self.engine.get_result(ResultQuantity::A, target, 0)? > tolerance);
+	}
+	
+	/// Bracket the first appearance of `target` along `x1 + lambda*(x2 - x1)` by a scan from `x1`, then bisect the bracket; `u1`, `u2` are the same end points in the transform basis.
+	fn calculate_target_x_from_left_(&self, x1: &DVector<f64>, x2: &DVector<f64>, u1: &DVector<f64>, u2: &DVector<f64>, temp: f64, target: usize, options: &TargetXOptions)->Result<EquilibriumResult,ChemAppError>{
+		let start = Instant::now();
+		if target == 0 || target > self.engine.tqnop()? {
+			return Err(ChemAppError::OtherError(format!("Target phase index {} is out of range", target)));
+		}
+		let point = |lambda: f64|->DVector<f64> {x1 + (x2 - x1)*lambda};
+		let mut iterations = 1usize;
+		self.calculate_isothermal_(&point(0.0), temp)?;
+		if self.phase_present(target, options.amount_tolerance)? {
+			return Err(ChemAppError::OtherError(format!("Target phase {} is already present at the starting composition", self.engine.tqgnp(target)?.trim())));
+		}
+		// scan for the first point where the phase is present
+		let nsteps = options.scan_steps.max(1);
+		let mut bracket : Option<(f64,f64)> = None;
+		for k in 1..=nsteps {
+			let lambda = k as f64/nsteps as f64;
+			iterations += 1;
+			self.calculate_isothermal_(&point(lambda), temp)?;
+			if self.phase_present(target, options.amount_tolerance)? {
+				bracket = Some(((k-1) as f64/nsteps as f64, lambda));
+				break;
+			}
+		}
+		let (mut low, mut high) = bracket.ok_or_else(|| ChemAppError::OtherError(format!("Target phase {} does not appear between the end compositions at {}", self.engine.tqgnp(target).unwrap_or_default().trim(), temp)))?;
+		// bisection, the phase is absent at `low` and present at `high`
+		let mut bisections = 0usize;
+		while high - low > options.tolerance {
+			if bisections >= options.max_iterations {
+				return Err(ChemAppError::OtherError(format!("Cannot converge X target within {} iterations, bracket ({}, {})", options.max_iterations, low, high)));
 			}
-			x_other = x1;
+			let middle = 0.5*(low + high);
+			self.calculate_isothermal_(&point(middle), temp)?;
+			if self.phase_present(target, options.amount_tolerance)? {high = middle;} else {low = middle;}
+			bisections += 1;
 		}
-		return Err(ChemAppError::OtherError("Cannot converge X target".to_string()));
-		*/
+		// the boundary is reported on the side where the phase is present
+		let x_i = point(high);
+		self.calculate_isothermal_(&x_i, temp)?;
+		return Ok(EquilibriumResult {
+			kind       : CalculationKind::TargetX,
+			inputs     : CalculationInputs {
+				composition : u1 + (u2 - u1)*high,
+				amounts     : x_i,
+				temperature : Some(temp),
+				target      : Some(target),
+				..CalculationInputs::default()
+			},
+			target     : Some(high),
+			iterations : iterations + bisections + 1,
+			elapsed    : start.elapsed(),
+			snapshot   : self.snapshot(),
+		});
+	}
+	/// Perform a composition search from `x1` towards `x2` (transform basis) at temperature `temp` until the phase `target` appears; the result holds the boundary composition and the fraction of the way from `x1` to `x2` as the target value.
+	pub fn calculate_target_x_from_left<D: Dim, S: Storage<f64,D>>(&self, x1: &Vector<f64,D,S>, x2: &Vector<f64,D,S>, temp: f64, target: usize)->Result<EquilibriumResult,ChemAppError>{
+		return self.calculate_target_x_from_left_with(x1, x2, temp, target, &TargetXOptions::default());
 	}
-	/// Perform a composition search starting from `x1` until a required phase is met, use dynamic vectors; TODO check the composition transformations
-	pub fn calculate_target_x_from_left<D: Dim, S: Storage<f64,D>>(&self, x1: &Vector<f64,D,S>, x2: &Vector<f64,D,S>, temp: f64, target: usize)->Result<(),ChemAppError>{
-		return self.calculate_target_x_from_left_(&self.transform.transform_final2init(x1, false, false, false).column(0).into_owned(), &self.transform.transform_final2init(x2, false, false, false).column(0).into_owned(), temp, target);
+	/// `calculate_target_x_from_left` with explicit tolerances.
+	pub fn calculate_target_x_from_left_with<D: Dim, S: Storage<f64,D>>(&self, x1: &Vector<f64,D,S>, x2: &Vector<f64,D,S>, temp: f64, target: usize, options: &TargetXOptions)->Result<EquilibriumResult,ChemAppError>{
+		let u1 : DVector<f64> = DVector::from_iterator(x1.len(), x1.iter().cloned());
+		let u2 : DVector<f64> = DVector::from_iterator(x2.len(), x2.iter().cloned());
+		return self.calculate_target_x_from_left_(&self.transform.transform_final2init(x1, false, false, false).column(0).into_owned(), &self.transform.transform_final2init(x2, false, false, false).column(0).into_owned(), &u1, &u2, temp, target, options);
 	}
 	
 	/***************************************************************************************************************************************************************************************************************************/
 	/***************************************************************************************************************************************************************************************************************************/
 	
+	/// Calculate the equilibrium with the conditions set so far (`tqcel`) and return the result together with the result listing.
+	pub fn calculate_and_list(&self, target: TargetVariable, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(EquilibriumResult,String),ChemAppError>{
+		return self.capture_output("LIST", |c| {
+			let start = Instant::now();
+			c.engine.calculate_list(target, indexp, indexc, vals)?;
+			return c.listed_result(CalculationKind::Listed, target, start);
+		});
+	}
+	
+	/// Calculate the equilibrium starting from the previous results (`tqcenl`) and return the result together with the result listing.
+	pub fn calculate_next_and_list(&self, target: TargetVariable, indexp: usize, indexc: usize, vals: (f64,f64))->Result<(EquilibriumResult,String),ChemAppError>{
+		return self.capture_output("LIST", |c| {
+			let start = Instant::now();
+			c.engine.calculate_next_list(target, indexp, indexc, vals)?;
+			return c.listed_result(CalculationKind::ListedNext, target, start);
+		});
+	}
+	
+	/// The result of a calculation with the conditions set outside the calculator, the incoming amounts are read back from ChemApp.
+	fn listed_result(&self, kind: CalculationKind, target: TargetVariable, start: Instant)->Result<EquilibriumResult,ChemAppError>{
+		let elapsed = start.elapsed();
+		let ncomponents = self.engine.tqnosc()?;
+		let amounts : DVector<f64> = DVector::from_vec((1..=ncomponents).map(|k| self.engine.get_result(ResultQuantity::IA, 0, k)).collect::<Result<Vec<f64>,ChemAppError>>()?);
+		let value = match target {
+			TargetVariable::T  => Some(self.engine.get_result(ResultQuantity::T, 0, 0)?),
+			TargetVariable::P  => Some(self.engine.get_result(ResultQuantity::P, 0, 0)?),
+			_ => None,
+		};
+		return Ok(EquilibriumResult {
+			kind,
+			inputs     : CalculationInputs {
+				amounts,
+				..CalculationInputs::default()
+			},
+			target     : value,
+			iterations : 1,
+			elapsed,
+			snapshot   : self.snapshot(),
+		});
+	}
+	
 	/// Create a snapshot of the current state.
 	pub fn snapshot(&self)->CalculatorSnapshot {
 		return CalculatorSnapshot::new(self);
@@ -399,34 +700,40 @@ impl Calculator {
 	/***************************************************************************************************************************************************************************************************************************/
 	/***************************************************************************************************************************************************************************************************************************/
 	/// A higher-level abstraction over `tqmap` and `tqmapl` ChemApp routines
-	pub fn mapping_temperature(&self, tmin: f64, tmax: f64, list: bool)->Result<Vec<CalculatorSnapshot>,ChemAppError>{
-		let func = |option: &str, indexp: usize, indexc: usize, vals: (f64,f64)| { if list {self.engine.tqmap(option, indexp, indexp, vals)} else {self.engine.tqmapl(option, indexp, indexp, vals)}};
-		let icont = func("TF", 0, 0, (tmin,tmax))?;
-		let snap_first = self.snapshot();
-		let mut icont = func("TN", 0, 0, (tmin,tmax))?;
-		let snap_last  = self.snapshot();
-		let mut vecc : Vec<CalculatorSnapshot> = vec![snap_first];
-		if icont > 0 {
-			icont = func("TN", 0, 0, (tmin,tmax))?;
-			vecc.push(self.snapshot());
-		}
-		vecc.push(snap_last);
-		return Ok(vecc);
+	pub fn mapping_temperature(&self, tmin: f64, tmax: f64, list: bool)->Result<Vec<EquilibriumResult>,ChemAppError>{
+		return self.mapping(MapOption::TF, MapOption::TN, ResultQuantity::T, CalculationKind::MappingT, (tmin, tmax), list);
 	}
 	
 	/// A higher-level abstraction over `tqmap` and `tqmapl` ChemApp routines
-	pub fn mapping_pressure(&self, pmin: f64, pmax: f64, list: bool)->Result<Vec<CalculatorSnapshot>,ChemAppError> {
-		let func = |option: &str, indexp: usize, indexc: usize, vals: (f64,f64)| { if list {self.engine.tqmap(option, indexp, indexp, vals)} else {self.engine.tqmapl(option, indexp, indexp, vals)}};
-		let icont = func("PF", 0, 0, (pmin,pmax))?;
-		let snap_first = self.snapshot();
-		let mut icont = func("PN", 0, 0, (pmin,pmax))?;
-		let snap_last  = self.snapshot();
-		let mut vecc : Vec<CalculatorSnapshot> = vec![snap_first];
-		if icont > 0 {
-			icont = func("PN", 0, 0, (pmin,pmax))?;
-			vecc.push(self.snapshot());
+	pub fn mapping_pressure(&self, pmin: f64, pmax: f64, list: bool)->Result<Vec<EquilibriumResult>,ChemAppError> {
+		return self.mapping(MapOption::PF, MapOption::PN, ResultQuantity::P, CalculationKind::MappingP, (pmin, pmax), list);
+	}
+	
+	fn mapping(&self, first: MapOption, next: MapOption, variable: ResultQuantity, kind: CalculationKind, interval: (f64,f64), list: bool)->Result<Vec<EquilibriumResult>,ChemAppError>{
+		let func = |option: MapOption, indexp: usize, indexc: usize, vals: (f64,f64)| { if list {self.engine.map_list(option, indexp, indexc, vals)} else {self.engine.map(option, indexp, indexc, vals)}};
+		let point = |start: Instant|->Result<EquilibriumResult,ChemAppError> {
+			let elapsed = start.elapsed();
+			return Ok(EquilibriumResult {
+				kind,
+				inputs     : CalculationInputs {
+					interval : Some(interval),
+					..CalculationInputs::default()
+				},
+				target     : Some(self.engine.get_result(variable, 0, 0)?),
+				iterations : 1,
+				elapsed,
+				snapshot   : self.snapshot(),
+			});
+		};
+		// the first point, then the next ones while ChemApp reports more (in the order they are returned)
+		let start = Instant::now();
+		let mut icont = func(first, 0, 0, interval)?;
+		let mut vecc : Vec<EquilibriumResult> = vec![point(start)?];
+		while icont > 0 && vecc.len() < MAPPING_POINTS_MAX {
+			let start = Instant::now();
+			icont = func(next, 0, 0, interval)?;
+			vecc.push(point(start)?);
 		}
-		vecc.push(snap_last);
 		return Ok(vecc);
 	}
 	
@@ -438,20 +745,190 @@ impl Calculator {
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-/// Custom `Drop` re-implementation to ensure any temporary files are deleted.
-impl Drop for Calculator {
+/// Clears the deadline of a backend on drop.
+struct DeadlineGuard<'a, B: ChemAppBackend>(&'a B);
+
+impl<'a, B: ChemAppBackend> Drop for DeadlineGuard<'a, B> {
 	
 	fn drop(&mut self){
-		match &self.nondefault_errunit {
-			Some((filename,unit)) => {
-				let _ = self.engine.tqclos(*unit);
-				let _ = std::fs::remove_file(&filename);
-			}
-			None => {}
+		let _ = self.0.set_deadline(None);
+	}
+	
+}
+
+/// Restores the unit of an output option (`tqcio`) on drop.
+struct RoutingGuard<'a, B: ChemAppBackend> {
+	backend  : &'a B,
+	option   : &'a str,
+	previous : Option<usize>,
+}
+
+impl<'a, B: ChemAppBackend> RoutingGuard<'a, B> {
+	
+	/// Restore the routing now, reporting a `tqcio` error.
+	fn restore(mut self)->Result<(),ChemAppError>{
+		return match self.previous.take() {
+			Some(unit) => self.backend.tqcio(self.option, unit),
+			None => Ok(()),
+		};
+	}
+	
+}
+
+impl<'a, B: ChemAppBackend> Drop for RoutingGuard<'a, B> {
+	
+	fn drop(&mut self){
+		if let Some(unit) = self.previous.take() {
+			let _ = self.backend.tqcio(self.option, unit);
 		}
 	}
 	
 }
 
+/// Custom `Drop` re-implementation to ensure any temporary files are deleted.
+impl<B: ChemAppBackend> Drop for Calculator<B> {
+	
+	fn drop(&mut self){
+		self.close_error_file();
+	}
+	
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
-/*******************************************************************************************************************************************************************************************************************************/
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::{MockBackend, MockResults};
+	use crate::backend::mock::{cu_ni_with, calculator};
+
+	/// mole fraction of Ni above which FCC_A1 coexists with the liquid
+	const BOUNDARY : f64 = 0.33;
+
+	/// a Cu-Ni system at constant temperature: liquid up to `BOUNDARY`, FCC above, with an amount growing linearly from the boundary
+	fn at_boundary()->Calculator<MockBackend> {
+		let backend = cu_ni_with(|input| {
+			let amounts = input.incoming_amounts(2);
+			let total : f64 = amounts.iter().sum();
+			let fcc = (total*(amounts[1]/total - BOUNDARY)).max(0.0);
+			let mut results = MockResults::new();
+			results.insert(("A".to_owned(), 1, 0), total - fcc);
+			results.insert(("A".to_owned(), 2, 0), fcc);
+			return Ok(results);
+		});
+		return Calculator::from_backend(backend).unwrap();
+	}
+
+	/// a liquid (phase 1) whose Ni content is `ratio` of its Cu content, as a function of the incoming Ni, at 1400 K
+	fn adjusting(ratio: fn(f64)->f64)->Calculator<MockBackend> {
+		let backend = cu_ni_with(move |input| {
+			let amounts = input.incoming_amounts(2);
+			let mut results = MockResults::new();
+			results.insert(("T".to_owned(), 0, 0), 1400.0);
+			results.insert(("A".to_owned(), 1, 0), amounts.iter().sum());
+			results.insert(("XP".to_owned(), 1, 1), 1.0);
+			results.insert(("XP".to_owned(), 1, 2), ratio(amounts[1]));
+			return Ok(results);
+		});
+		return Calculator::from_backend(backend).unwrap();
+	}
+
+	#[test]
+	fn target_t_reports_the_adjusting_iterations(){
+		let calculator = adjusting(|_| 0.5);
+		let result = calculator.calculate_target_t(&DVector::from_vec(vec![1.0, 0.2]), 1, 1, (1000.0, 2000.0), false, Some(1), Some(2)).unwrap();
+		assert_eq!(result.kind, CalculationKind::TargetT);
+		assert_eq!(result.target, Some(1400.0));
+		assert_eq!(result.iterations, 2);
+		let bytes = bincode::serde::encode_to_vec(&result, bincode::config::standard()).unwrap();
+		let (decoded, _) : (EquilibriumResult, usize) = bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+		assert_eq!(decoded, result);
+	}
+
+	#[test]
+	fn target_t_fails_when_the_adjusting_loop_diverges(){
+		let calculator = adjusting(|ni| 2.0*ni + 1.0);
+		let result = calculator.calculate_target_t(&DVector::from_vec(vec![1.0, 0.2]), 1, 1, (1000.0, 2000.0), false, Some(1), Some(2));
+		assert!(other_error(result, "within 10 iterations"));
+	}
+
+	#[test]
+	fn target_t_needs_both_or_none_of_fixed_and_adjusting(){
+		let calculator = adjusting(|_| 0.5);
+		let x = DVector::from_vec(vec![1.0, 0.2]);
+		assert!(other_error(calculator.calculate_target_t(&x, 1, 1, (1000.0, 2000.0), false, Some(1), None), "Both or none"));
+		assert!(other_error(calculator.calculate_target_t(&x, 1, 1, (1000.0, 2000.0), false, None, Some(2)), "Both or none"));
+	}
+
+	fn other_error(result: Result<EquilibriumResult,ChemAppError>, text: &str)->bool {
+		return matches!(result, Err(ChemAppError::OtherError(message)) if message.contains(text));
+	}
+
+	#[test]
+	fn target_x_finds_the_boundary(){
+		let calculator = at_boundary();
+		let options = TargetXOptions {tolerance: 1e-8, ..TargetXOptions::default()};
+		let (x1, x2) = (DVector::from_vec(vec![1.0, 0.0]), DVector::from_vec(vec![0.0, 1.0]));
+		let result = calculator.calculate_target_x_from_left_with(&x1, &x2, 1400.0, 2, &options).unwrap();
+		assert_eq!(result.kind, CalculationKind::TargetX);
+		let lambda = result.target.unwrap();
+		assert!(lambda > BOUNDARY && lambda - BOUNDARY <= options.tolerance);
+		assert!((&result.inputs.composition - DVector::from_vec(vec![1.0 - lambda, lambda])).abs().max() < 1e-12);
+		assert!((&result.inputs.amounts - &result.inputs.composition).abs().max() < 1e-12);
+		assert_eq!(result.inputs.temperature, Some(1400.0));
+		assert!(result.snapshot.phase("FCC_A1").unwrap().a > 0.0);
+	}
+
+	#[test]
+	fn target_x_rejects_a_phase_present_at_the_start(){
+		let calculator = at_boundary();
+		let (x1, x2) = (DVector::from_vec(vec![0.5, 0.5]), DVector::from_vec(vec![0.0, 1.0]));
+		assert!(other_error(calculator.calculate_target_x_from_left(&x1, &x2, 1400.0, 2), "already present"));
+	}
+
+	#[test]
+	fn target_x_fails_when_the_phase_never_appears(){
+		let calculator = at_boundary();
+		let (x1, x2) = (DVector::from_vec(vec![1.0, 0.0]), DVector::from_vec(vec![0.8, 0.2]));
+		assert!(other_error(calculator.calculate_target_x_from_left(&x1, &x2, 1400.0, 2), "does not appear"));
+	}
+
+	#[test]
+	fn target_x_stops_after_max_iterations(){
+		let calculator = at_boundary();
+		let options = TargetXOptions {tolerance: 1e-12, max_iterations: 5, ..TargetXOptions::default()};
+		let (x1, x2) = (DVector::from_vec(vec![1.0, 0.0]), DVector::from_vec(vec![0.0, 1.0]));
+		assert!(other_error(calculator.calculate_target_x_from_left_with(&x1, &x2, 1400.0, 2, &options), "within 5 iterations"));
+	}
+
+	#[test]
+	fn output_routing_is_restored(){
+		let mut calculator = calculator();
+		let (value, text) = calculator.capture_output("LIST", |c| c.engine.tqgio("LIST")).unwrap();
+		assert_eq!(value, 20);
+		assert_eq!(text, "");
+		assert_eq!(calculator.engine.tqgio("LIST").unwrap(), 6);
+		let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let _ = calculator.capture_output("LIST", |_| -> Result<(),ChemAppError> {panic!("inside the capture")});
+		}));
+		assert!(panicked.is_err());
+		assert_eq!(calculator.engine.tqgio("LIST").unwrap(), 6);
+		assert!(calculator.engine.units().in_use().is_empty());
+		calculator.redirect_error_to_temp().unwrap();
+		calculator.redirect_error_to_temp().unwrap();
+		assert_eq!(calculator.engine.units().in_use().len(), 1);
+		assert_eq!(calculator.engine.tqgio("ERROR").unwrap(), calculator.nondefault_errunit.as_ref().unwrap().1);
+	}
+
+	#[test]
+	fn calculator_name_lookups(){
+		let calculator = calculator();
+		assert_eq!(calculator.component_index("Ni").unwrap(), 2);
+		assert_eq!(calculator.phase_index("FCC_A1").unwrap(), 2);
+		assert_eq!(calculator.constituent_index(1, "Ni").unwrap(), 2);
+		assert!(calculator.phase_index("BCC_A2").is_err());
+		assert_eq!(calculator.basis, vec!["Cu".to_string(), "Ni".to_string()]);
+	}
+
+}
@@ -0,0 +1,251 @@
+// chemapp_rs::diagram::binary.rs
+
+//! Binary T-x phase diagrams of a section between two formulas (e.g. SiO2-Al2O3).
+//! Every composition column is mapped over the temperature range with `tqmap`, which locates the phase boundaries crossed in temperature exactly; every temperature row is scanned in composition and the changes of the stable assemblage are refined by bisection,
+//! which locates the boundaries crossed in composition (solubility limits, stoichiometric compounds). The points are then grouped by the pair of assemblages they separate and chained into polylines.
+
+use nalgebra::{DVector, dvector};
+use chemformula::{Transform};
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{Condition, ResultQuantity, MapOption};
+use crate::diagram::{Assemblage, Boundary, RegionLabel, DiagramBuilder};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Resolution and tolerances of `PhaseDiagram::calculate`.
+#[derive(Debug,Clone)]
+pub struct PhaseDiagramOptions {
+	/// number of composition columns mapped in temperature (including both end members)
+	pub columns : usize,
+	/// number of temperature rows scanned in composition (excluding the range limits)
+	pub rows : usize,
+	/// width of the final composition bracket of a boundary crossed in composition
+	pub x_tolerance : f64,
+	/// equilibrium amount above which a phase counts as stable
+	pub amount_tolerance : f64,
+	/// maximal number of `tqmap` calls per column
+	pub max_map_steps : usize,
+}
+
+impl Default for PhaseDiagramOptions {
+	fn default()->PhaseDiagramOptions {
+		return PhaseDiagramOptions {
+			columns : 41,
+			rows : 40,
+			x_tolerance : 1e-4,
+			amount_tolerance : 1e-12,
+			max_map_steps : 200,
+		};
+	}
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A calculated binary T-x phase diagram; the points are `[x, T]`, `x` being the fraction of the second end member (in the amount units of the current system).
+#[derive(Debug,Clone)]
+pub struct PhaseDiagram {
+	/// the end member formulas
+	pub endmembers : [String;2],
+	/// the temperature range
+	pub temperatures : (f64,f64),
+	/// phase boundaries
+	pub boundaries : Vec<Boundary>,
+	/// phase fields
+	pub regions : Vec<RegionLabel>,
+	/// number of calculations which failed (their points are missing from the diagram)
+	pub failures : usize,
+}
+
+impl PhaseDiagram {
+
+	/// Calculate the diagram of the section `endmembers[0]`-`endmembers[1]` over `temperatures` (current temperature unit).
+	pub fn calculate<B: ChemAppBackend>(calculator: &Calculator<B>, endmembers: [&str;2], temperatures: (f64,f64), options: &PhaseDiagramOptions)->Result<PhaseDiagram,ChemAppError>{
+		let (tmin, tmax) = temperatures;
+		if !(tmin.is_finite() && tmax.is_finite() && tmin > 0.0 && tmin < tmax) {
+			return Err(ChemAppError::OtherError(format!("Temperature range must satisfy 0 < tmin < tmax, got ({}, {})", tmin, tmax)));
+		}
+		if options.columns < 2 {
+			return Err(ChemAppError::OtherError("A phase diagram needs at least two composition columns".to_string()));
+		}
+		let section = Section::new(calculator, endmembers, options)?;
+		let mut builder = DiagramBuilder::default();
+		let mut failures = 0usize;
+		let eps = (tmax - tmin)*1e-6;
+		let xs : Vec<f64> = (0..options.columns).map(|j| j as f64/(options.columns - 1) as f64).collect();
+		// boundaries crossed in temperature
+		for &x in xs.iter() {
+			let temps = match section.map_column(x, temperatures) {
+				Ok(temps) => temps,
+				Err(_) => {failures += 1; continue;}
+			};
+			let mut edges : Vec<f64> = vec![tmin];
+			for t in temps.into_iter().filter(|t| *t > tmin + eps && *t < tmax - eps) {
+				if t - edges[edges.len()-1] > eps {edges.push(t);}
+			}
+			edges.push(tmax);
+			let labels : Vec<Option<Assemblage>> = edges.windows(2).map(|w| {
+				let t = 0.5*(w[0] + w[1]);
+				let label = section.assemblage(x, t).ok();
+				if let Some(assemblage) = &label {builder.sample(assemblage, [x, t]);}
+				return label;
+			}).collect();
+			failures += labels.iter().filter(|l| l.is_none()).count();
+			for k in 1..edges.len()-1 {
+				if let (Some(lower), Some(upper)) = (&labels[k-1], &labels[k]) {
+					if lower != upper {builder.boundary(lower, upper, [x, edges[k]]);}
+				}
+			}
+		}
+		// boundaries crossed in composition
+		for i in 1..=options.rows {
+			let t = tmin + (tmax - tmin)*i as f64/(options.rows + 1) as f64;
+			let labels : Vec<Option<Assemblage>> = xs.iter().map(|&x| section.assemblage(x, t).ok()).collect();
+			failures += labels.iter().filter(|l| l.is_none()).count();
+			for (j, label) in labels.iter().enumerate() {
+				if let Some(assemblage) = label {builder.sample(assemblage, [xs[j], t]);}
+			}
+			for j in 0..xs.len()-1 {
+				if let (Some(left), Some(right)) = (&labels[j], &labels[j+1]) {
+					if left == right {continue;}
+					match section.bisect(t, (xs[j], xs[j+1]), left, right) {
+						Ok((x, other)) => {builder.boundary(left, &other, [x, t]);}
+						Err(_) => {failures += 1;}
+					}
+				}
+			}
+		}
+		let scale = [1.0, tmax - tmin];
+		let gap = 2.5*(1.0/(options.columns - 1) as f64).max(1.0/(options.rows + 1) as f64);
+		let (boundaries, regions) = builder.finish(scale, gap);
+		return Ok(PhaseDiagram {
+			endmembers : [endmembers[0].to_string(), endmembers[1].to_string()],
+			temperatures,
+			boundaries,
+			regions,
+			failures,
+		});
+	}
+
+	/// The boundaries of a phase field, i.e. those having `assemblage` on one side.
+	pub fn boundaries_of<'a>(&'a self, assemblage: &'a Assemblage)->impl Iterator<Item=&'a Boundary> + 'a {
+		return self.boundaries.iter().filter(move |b| &b.between.0 == assemblage || &b.between.1 == assemblage);
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The calculations along a binary section.
+struct Section<'a, B: ChemAppBackend> {
+	calculator : &'a Calculator<B>,
+	transform  : Transform,
+	options    : &'a PhaseDiagramOptions,
+}
+
+impl<'a, B: ChemAppBackend> Section<'a, B> {
+
+	fn new(calculator: &'a Calculator<B>, endmembers: [&str;2], options: &'a PhaseDiagramOptions)->Result<Self,ChemAppError>{
+		let components : Vec<String> = calculator.components().map(|c| c.name()).collect();
+		let basis : Vec<String> = endmembers.iter().map(|e| e.to_string()).collect();
+		let transform = Transform::new(&components, &basis, true).map_err(|e| ChemAppError::OtherError(format!("{:?} cannot be expressed in the system components {:?}: {:?}", endmembers, components, e)))?;
+		return Ok(Self {calculator, transform, options});
+	}
+
+	/// incoming amounts of the system components at the fraction `x` of the second end member
+	fn amounts(&self, x: f64)->DVector<f64> {
+		return self.transform.transform_final2init(&dvector![1.0 - x, x], false, false, false).column(0).into_owned();
+	}
+
+	/// the stable assemblage at `(x, t)`
+	fn assemblage(&self, x: f64, t: f64)->Result<Assemblage,ChemAppError>{
+		self.calculator.calculate_isothermal_(&self.amounts(x), t)?;
+		return Assemblage::current(self.calculator, self.options.amount_tolerance);
+	}
+
+	/// the temperatures returned by a one-dimensional temperature mapping of the column `x`
+	fn map_column(&self, x: f64, temperatures: (f64,f64))->Result<Vec<f64>,ChemAppError>{
+		let engine = &self.calculator.engine;
+		let amounts = self.amounts(x);
+		self.calculator.reset()?;
+		for k in 0..amounts.len() {engine.set_condition(Condition::IA, 0, k+1, amounts[k])?;}
+		let mut temps : Vec<f64> = Vec::new();
+		let mut icont = engine.map(MapOption::TF, 0, 0, temperatures)?;
+		temps.push(engine.get_result(ResultQuantity::T, 0, 0)?);
+		let mut steps = 1usize;
+		while icont > 0 && steps < self.options.max_map_steps {
+			icont = engine.map(MapOption::TN, 0, 0, temperatures)?;
+			temps.push(engine.get_result(ResultQuantity::T, 0, 0)?);
+			steps += 1;
+		}
+		temps.sort_by(|a, b| a.total_cmp(b));
+		return Ok(temps);
+	}
+
+	/// Bisect `(x0, x1)` at temperature `t` for the first change from the assemblage `left`; returns the boundary composition and the assemblage beyond it.
+	fn bisect(&self, t: f64, interval: (f64,f64), left: &Assemblage, right: &Assemblage)->Result<(f64,Assemblage),ChemAppError>{
+		let (mut low, mut high) = interval;
+		let mut beyond = right.clone();
+		while high - low > self.options.x_tolerance {
+			let middle = 0.5*(low + high);
+			let label = self.assemblage(middle, t)?;
+			if &label == left {
+				low = middle;
+			} else {
+				high = middle;
+				beyond = label;
+			}
+		}
+		return Ok((0.5*(low + high), beyond));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::mock::{calculator};
+
+	fn assemblage(phases: &[&str])->Assemblage {
+		return Assemblage(phases.iter().map(|p| p.to_string()).collect());
+	}
+
+	#[test]
+	fn melting_boundary_of_cu_ni(){
+		// the Cu-Ni mock is liquid above 1500 K at every composition
+		let calculator = calculator();
+		let options = PhaseDiagramOptions {columns: 5, rows: 4, ..PhaseDiagramOptions::default()};
+		let diagram = PhaseDiagram::calculate(&calculator, ["Cu", "Ni"], (1000.0, 2000.0), &options).unwrap();
+		assert_eq!(diagram.failures, 0);
+		let (liquid, fcc) = (assemblage(&["LIQUID"]), assemblage(&["FCC_A1"]));
+		assert_eq!(diagram.boundaries.len(), 1);
+		let boundary = &diagram.boundaries[0];
+		assert!(boundary.between == (liquid.clone(), fcc.clone()) || boundary.between == (fcc.clone(), liquid.clone()));
+		assert_eq!(boundary.points.len(), 5);
+		assert!(boundary.points.iter().all(|p| (p[1] - 1500.0).abs() < 1e-3));
+		assert_eq!(diagram.boundaries_of(&fcc).count(), 1);
+		let liquid_label = diagram.regions.iter().find(|r| r.assemblage == liquid).unwrap();
+		let fcc_label = diagram.regions.iter().find(|r| r.assemblage == fcc).unwrap();
+		assert_eq!(diagram.regions.len(), 2);
+		assert!(liquid_label.position[1] > 1500.0);
+		assert!(fcc_label.position[1] < 1500.0);
+	}
+
+	#[test]
+	fn phase_diagram_rejects_invalid_ranges(){
+		let calculator = calculator();
+		let options = PhaseDiagramOptions::default();
+		assert!(PhaseDiagram::calculate(&calculator, ["Cu", "Ni"], (2000.0, 1000.0), &options).is_err());
+		assert!(PhaseDiagram::calculate(&calculator, ["Cu", "Ni"], (1000.0, 2000.0), &PhaseDiagramOptions {columns: 1, ..options}).is_err());
+	}
+
+}
@@ -0,0 +1,317 @@
+// chemapp_rs::diagram::ellingham.rs
+
+//! Ellingham diagrams: the standard Gibbs energy of the formation reactions `a M + X2 = b MmXn` per mole of the oxidant gas (`O2`, `S2`, `Cl2`, ...) versus temperature, from the Gibbs data of the loaded datafile.
+//! The metals and their compounds are the `PURE` phases whose constituent stoichiometry (`tqstpc`) holds only the metal, or only the metal and the element of the oxidant; the phases of the same stoichiometry are the modifications of one substance
+//! and the most stable of them (the lowest Gibbs energy) is used at every temperature, so that a melting or a transformation of the metal or the compound appears as a kink of the line.
+//! Only `PURE` phases are considered: a metal or a compound whose liquid (or any other modification) is modelled only as a constituent of a solution phase keeps its solid line above the melting point, without a kink.
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{ConstituentProperty};
+use crate::reaction::{StandardState};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Resolution of `EllinghamDiagram::calculate`.
+#[derive(Debug,Clone)]
+pub struct EllinghamOptions {
+	/// number of temperature points of every line (including the range limits)
+	pub points : usize,
+	/// width of the final temperature bracket of a phase change
+	pub t_tolerance : f64,
+}
+
+impl Default for EllinghamOptions {
+	fn default()->EllinghamOptions {
+		return EllinghamOptions {
+			points : 101,
+			t_tolerance : 1e-3,
+		};
+	}
+}
+
+/// A change of the stable modification of a reactant or a product along a line.
+#[derive(Debug,Clone)]
+pub struct Kink {
+	/// the temperature of the change
+	pub temperature : f64,
+	/// the Gibbs energy of the reaction at the change [J per mol of oxidant]
+	pub delta_g : f64,
+	/// the substance changing (the metal or the compound formula)
+	pub substance : String,
+	/// the stable phase below the temperature
+	pub from : String,
+	/// the stable phase above the temperature
+	pub to : String,
+}
+
+/// The line of one formation reaction.
+#[derive(Debug,Clone)]
+pub struct EllinghamLine {
+	/// the metal (system component)
+	pub metal : String,
+	/// the compound formula, e.g. `Al2O3`
+	pub compound : String,
+	/// the reaction, e.g. `1.3333 Al + O2 = 0.66667 Al2O3`
+	pub reaction : String,
+	/// `[T, ΔG°]` points, ΔG° in J per mol of oxidant
+	pub points : Vec<[f64;2]>,
+	/// phase changes of the metal or the compound, in increasing temperature
+	pub kinks : Vec<Kink>,
+}
+
+/// A calculated Ellingham diagram.
+#[derive(Debug,Clone)]
+pub struct EllinghamDiagram {
+	/// the oxidant gas constituent
+	pub oxidant : String,
+	/// the temperature range [current temperature unit]
+	pub temperatures : (f64,f64),
+	/// one line per compound
+	pub lines : Vec<EllinghamLine>,
+}
+
+impl EllinghamDiagram {
+
+	/// Calculate the lines of all compounds of `metals` (system component names) with the oxidant `gas = (phase, constituent)` over `temperatures` (current temperature unit).
+	/// All conditions of `calculator` are removed (`Calculator::reset`) before the Gibbs energies are evaluated, because the temperature is entered as a global condition; enter the conditions again for a following calculation.
+	pub fn calculate<B: ChemAppBackend>(calculator: &Calculator<B>, metals: &[&str], gas: (&str,&str), temperatures: (f64,f64), options: &EllinghamOptions)->Result<EllinghamDiagram,ChemAppError>{
+		let (tmin, tmax) = temperatures;
+		if !(tmin.is_finite() && tmax.is_finite() && tmin < tmax) {
+			return Err(ChemAppError::OtherError(format!("Temperature range must satisfy tmin < tmax, got ({}, {})", tmin, tmax)));
+		}
+		if options.points < 2 {
+			return Err(ChemAppError::OtherError("An Ellingham line needs at least two temperature points".to_string()));
+		}
+		let engine = &calculator.engine;
+		let components : Vec<String> = calculator.components().map(|c| c.name()).collect();
+		let indexg = calculator.phase_index(gas.0)?;
+		let indexo = calculator.constituent_index(indexg, gas.1)?;
+		let oxidant = Substance {
+			name : gas.1.to_string(),
+			variants : vec![(indexg, indexo, gas.0.to_string())],
+			stoichiometry : engine.tqstpc(indexg, indexo)?.0,
+		};
+		let elements : Vec<usize> = (0..components.len()).filter(|&k| oxidant.stoichiometry[k] != 0.0).collect();
+		if elements.len() != 1 {
+			return Err(ChemAppError::OtherError(format!("The oxidant {} must consist of a single element", gas.1)));
+		}
+		let element = elements[0];
+		let substances = pure_substances(calculator)?;
+		let evaluator = Evaluator::new(calculator)?;
+		let ts : Vec<f64> = (0..options.points).map(|i| tmin + (tmax - tmin)*i as f64/(options.points - 1) as f64).collect();
+		// the temperature of every evaluation is a global condition, which cannot be combined with incoming streams
+		calculator.reset()?;
+		let mut lines : Vec<EllinghamLine> = Vec::new();
+		for metal in metals.iter() {
+			let m = calculator.component_index(metal)? - 1;
+			let only = |s: &Substance, allowed: &[usize]| s.stoichiometry.iter().enumerate().all(|(k, v)| *v == 0.0 || allowed.contains(&k));
+			let reference = match substances.iter().find(|s| s.stoichiometry[m] > 0.0 && only(s, &[m])) {
+				Some(reference) => reference,
+				None => {return Err(ChemAppError::OtherError(format!("No PURE phase of the metal {}", metal)));}
+			};
+			for compound in substances.iter().filter(|s| s.stoichiometry[m] > 0.0 && s.stoichiometry[element] > 0.0 && only(s, &[m, element])) {
+				// a M + X2 = b MmXn
+				let b = oxidant.stoichiometry[element]/compound.stoichiometry[element];
+				let a = b*compound.stoichiometry[m]/reference.stoichiometry[m];
+				let delta = |t: f64|->Result<(f64,usize,usize),ChemAppError>{
+					let (gp, ip) = evaluator.stable(compound, t)?;
+					let (gm, im) = evaluator.stable(reference, t)?;
+					let (go, _) = evaluator.stable(&oxidant, t)?;
+					return Ok((b*gp - a*gm - go, im, ip));
+				};
+				let mut points : Vec<[f64;2]> = Vec::with_capacity(ts.len());
+				let mut kinks : Vec<Kink> = Vec::new();
+				let mut previous : Option<(f64,usize,usize)> = None;
+				for &t in ts.iter() {
+					let (dg, im, ip) = delta(t)?;
+					if let Some((t0, im0, ip0)) = previous {
+						for (substance, from, to) in [(reference, im0, im), (compound, ip0, ip)] {
+							if from != to {
+								let tk = evaluator.transition(substance, from, to, (t0, t), options.t_tolerance)?;
+								kinks.push(Kink {
+									temperature : tk,
+									delta_g : delta(tk)?.0,
+									substance : substance.name.clone(),
+									from : substance.variants[from].2.clone(),
+									to : substance.variants[to].2.clone(),
+								});
+							}
+						}
+					}
+					points.push([t, dg]);
+					previous = Some((t, im, ip));
+				}
+				kinks.sort_by(|x, y| x.temperature.total_cmp(&y.temperature));
+				lines.push(EllinghamLine {
+					metal : metal.to_string(),
+					compound : compound.name.clone(),
+					reaction : format!("{} {} + {} = {} {}", number(a), reference.name, oxidant.name, number(b), compound.name),
+					points,
+					kinks,
+				});
+			}
+		}
+		return Ok(EllinghamDiagram {
+			oxidant : gas.1.to_string(),
+			temperatures,
+			lines,
+		});
+	}
+
+	/// The lines of a metal.
+	pub fn lines_of<'a>(&'a self, metal: &'a str)->impl Iterator<Item=&'a EllinghamLine> + 'a {
+		return self.lines.iter().filter(move |l| l.metal == metal);
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A substance: the phase constituents of one stoichiometry.
+#[derive(Debug,Clone)]
+struct Substance {
+	/// the formula built from the stoichiometry
+	name : String,
+	/// `(indexp, indexc, phase name)` of the modifications
+	variants : Vec<(usize,usize,String)>,
+	/// amounts of the system components in the formula unit
+	stoichiometry : Vec<f64>,
+}
+
+/// The `PURE` phases grouped by stoichiometry.
+fn pure_substances<B: ChemAppBackend>(calculator: &Calculator<B>)->Result<Vec<Substance>,ChemAppError>{
+	let engine = &calculator.engine;
+	let components : Vec<String> = calculator.components().map(|c| c.name()).collect();
+	let mut substances : Vec<Substance> = Vec::new();
+	for indexp in 1..=engine.tqnop()? {
+		if engine.tqmodl(indexp)?.trim() != "PURE" {continue;}
+		let (stoichiometry, _) = engine.tqstpc(indexp, 1)?;
+		let phase = engine.tqgnp(indexp)?.trim().to_string();
+		let same = |s: &&mut Substance| s.stoichiometry.iter().zip(stoichiometry.iter()).all(|(a, b)| (a - b).abs() <= 1e-9*a.abs().max(1.0));
+		match substances.iter_mut().find(same) {
+			Some(substance) => {substance.variants.push((indexp, 1, phase));}
+			None => {
+				let name = formula(&components, &stoichiometry);
+				substances.push(Substance {name, variants: vec![(indexp, 1, phase)], stoichiometry});
+			}
+		}
+	}
+	return Ok(substances);
+}
+
+/// a formula from component names and amounts, e.g. `Al2O3`
+fn formula(components: &[String], stoichiometry: &[f64])->String {
+	return components.iter().zip(stoichiometry.iter()).filter(|(_, v)| **v != 0.0).map(|(c, v)| if *v == 1.0 {c.trim().to_string()} else {format!("{}{}", c.trim(), number(*v))}).collect();
+}
+
+/// a stoichiometric coefficient with at most 5 significant digits
+fn number(value: f64)->String {
+	let text = format!("{:.5}", value);
+	return text.trim_end_matches('0').trim_end_matches('.').to_string();
+}
+
+/// Standard Gibbs energies of phase constituents at a temperature.
+struct Evaluator<'a, B: ChemAppBackend> {
+	state : StandardState<'a, B>,
+}
+
+impl<'a, B: ChemAppBackend> Evaluator<'a, B> {
+
+	fn new(calculator: &'a Calculator<B>)->Result<Self,ChemAppError>{
+		return Ok(Self {state: StandardState::new(calculator)?});
+	}
+
+	/// the Gibbs energy [J/mol] of a constituent at `t` (current temperature unit)
+	fn gibbs(&self, indexp: usize, indexc: usize, t: f64)->Result<f64,ChemAppError>{
+		return self.state.property(ConstituentProperty::G, indexp, indexc, t);
+	}
+
+	/// the lowest Gibbs energy of the modifications of a substance and the index of that modification
+	fn stable(&self, substance: &Substance, t: f64)->Result<(f64,usize),ChemAppError>{
+		let mut best : Option<(f64,usize)> = None;
+		for (i, (indexp, indexc, _)) in substance.variants.iter().enumerate() {
+			let g = self.gibbs(*indexp, *indexc, t)?;
+			if best.map(|b| g < b.0).unwrap_or(true) {best = Some((g, i));}
+		}
+		return best.ok_or(ChemAppError::OtherError(format!("No phase of {}", substance.name)));
+	}
+
+	/// the temperature in `interval` where the modifications `from` and `to` have the same Gibbs energy (bisection)
+	fn transition(&self, substance: &Substance, from: usize, to: usize, interval: (f64,f64), tolerance: f64)->Result<f64,ChemAppError>{
+		let difference = |t: f64|->Result<f64,ChemAppError>{
+			let (pf, cf, _) = &substance.variants[from];
+			let (pt, ct, _) = &substance.variants[to];
+			return Ok(self.gibbs(*pt, *ct, t)? - self.gibbs(*pf, *cf, t)?);
+		};
+		let (mut low, mut high) = interval;
+		// `to` is more stable (negative difference) at `high`
+		while high - low > tolerance {
+			let middle = 0.5*(low + high);
+			if difference(middle)? < 0.0 {high = middle;} else {low = middle;}
+		}
+		return Ok(0.5*(low + high));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::{MockBackend, MockPhase, MockConstituent};
+
+	/// Al-O: solid and liquid Al melting at 1000 K, Al2O3 and an O2 gas; the Gibbs energies are in J/mol and entered as G/RT.
+	fn aluminium()->Calculator<MockBackend> {
+		const R : f64 = 8.314462618;
+		let backend = MockBackend::new()
+			.component("Al", 26.982)
+			.component("O", 15.999)
+			.phase(MockPhase::new("GAS", "IDMX").constituent(MockConstituent::new("O2", &[0.0, 2.0])))
+			.phase(MockPhase::pure("AL(S)", &[1.0, 0.0]))
+			.phase(MockPhase::pure("AL(L)", &[1.0, 0.0]))
+			.phase(MockPhase::pure("AL2O3(S)", &[2.0, 3.0]))
+			.standard_property("G", 3, 1, |t| (10000.0 - 10.0*t)/(R*t))
+			.standard_property("G", 4, 1, |t| (-1.6e6 + 300.0*t)/(R*t));
+		return Calculator::from_backend(backend).unwrap();
+	}
+
+	#[test]
+	fn oxidation_of_aluminium_per_mole_of_oxygen(){
+		let calculator = aluminium();
+		let options = EllinghamOptions {points: 6, ..EllinghamOptions::default()};
+		let diagram = EllinghamDiagram::calculate(&calculator, &["Al"], ("GAS", "O2"), (800.0, 1300.0), &options).unwrap();
+		assert_eq!(diagram.lines.len(), 1);
+		let line = &diagram.lines[0];
+		assert_eq!((line.metal.as_str(), line.compound.as_str()), ("Al", "Al2O3"));
+		// 4/3 Al + O2 = 2/3 Al2O3
+		assert_eq!(line.reaction, "1.33333 Al + O2 = 0.66667 Al2O3");
+		assert_eq!(line.points.len(), 6);
+		for point in line.points.iter() {
+			let t = point[0];
+			let metal = (10000.0 - 10.0*t).min(0.0);
+			let expected = 2.0/3.0*(-1.6e6 + 300.0*t) - 4.0/3.0*metal;
+			assert!((point[1] - expected).abs() < 1e-6*expected.abs(), "{:?} instead of {}", point, expected);
+		}
+		// the melting of the metal
+		assert_eq!(line.kinks.len(), 1);
+		let kink = &line.kinks[0];
+		assert_eq!((kink.substance.as_str(), kink.from.as_str(), kink.to.as_str()), ("Al", "AL(S)", "AL(L)"));
+		assert!((kink.temperature - 1000.0).abs() < options.t_tolerance);
+		assert!((kink.delta_g - 2.0/3.0*(-1.6e6 + 300.0*1000.0)).abs() < 1.0);
+	}
+
+	#[test]
+	fn invalid_ranges_and_metals_fail(){
+		let calculator = aluminium();
+		assert!(EllinghamDiagram::calculate(&calculator, &["Al"], ("GAS", "O2"), (1300.0, 800.0), &EllinghamOptions::default()).is_err());
+		assert!(EllinghamDiagram::calculate(&calculator, &["O"], ("GAS", "O2"), (800.0, 1300.0), &EllinghamOptions::default()).is_err());
+	}
+
+}
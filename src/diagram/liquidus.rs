@@ -0,0 +1,377 @@
+// chemapp_rs::diagram::liquidus.rs
+
+//! Liquidus projections of ternary systems in the basis of three formulas set with `Calculator::set_transform`.
+//! At every node of a triangular grid the liquidus temperature is found with `Calculator::calculate_target_t` (the liquid as the master phase with a precipitation target); the primary phase is the condensed phase other than the liquid (the gas excluded) with the highest activity there.
+//! Grid edges joining different primary phases are bisected to trace the univariant lines, triangles joining three primary phases locate the invariant points, and the liquidus isotherms are contoured over the grid triangles.
+//!
+//! Compositions are given as `[x2, x3]` as in `diagram::ternary`.
+
+use std::collections::{HashMap};
+use nalgebra::{DVector};
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{ResultQuantity};
+use crate::diagram::{Assemblage, RegionLabel, DiagramBuilder, chain};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Resolution and tolerances of `LiquidusProjection::calculate`.
+#[derive(Debug,Clone)]
+pub struct LiquidusOptions {
+	/// number of divisions of each side of the triangle
+	pub divisions : usize,
+	/// width of the final bracket of a univariant point, as a fraction of a grid edge
+	pub x_tolerance : f64,
+}
+
+impl Default for LiquidusOptions {
+	fn default()->LiquidusOptions {
+		return LiquidusOptions {
+			divisions : 20,
+			x_tolerance : 1e-3,
+		};
+	}
+}
+
+/// The liquidus at a composition.
+#[derive(Debug,Clone)]
+pub struct LiquidusPoint {
+	/// composition
+	pub composition : [f64;2],
+	/// liquidus temperature
+	pub temperature : f64,
+	/// the first phase to crystallize on cooling
+	pub primary : String,
+}
+
+/// A liquidus isotherm (one polyline; an isotherm may consist of several).
+#[derive(Debug,Clone)]
+pub struct Isotherm {
+	/// temperature
+	pub temperature : f64,
+	/// points of the polyline
+	pub points : Vec<[f64;2]>,
+}
+
+/// A univariant line: the liquidus in equilibrium with two primary phases.
+#[derive(Debug,Clone)]
+pub struct UnivariantLine {
+	/// the primary phases on both sides
+	pub phases : (String,String),
+	/// points of the polyline
+	pub points : Vec<[f64;2]>,
+	/// liquidus temperatures at the points
+	pub temperatures : Vec<f64>,
+}
+
+/// An invariant point where three primary crystallization fields meet; located within the grid resolution.
+#[derive(Debug,Clone)]
+pub struct InvariantPoint {
+	/// the primary phases meeting at the point
+	pub phases : Vec<String>,
+	/// composition
+	pub composition : [f64;2],
+	/// liquidus temperature
+	pub temperature : f64,
+}
+
+/// A calculated liquidus projection.
+#[derive(Debug,Clone)]
+pub struct LiquidusProjection {
+	/// the basis formulas
+	pub basis : [String;3],
+	/// the liquid phase
+	pub liquid : String,
+	/// liquidus at the grid nodes
+	pub points : Vec<LiquidusPoint>,
+	/// liquidus isotherms
+	pub isotherms : Vec<Isotherm>,
+	/// univariant lines
+	pub univariant_lines : Vec<UnivariantLine>,
+	/// invariant points
+	pub invariant_points : Vec<InvariantPoint>,
+	/// primary crystallization fields, labelled by the primary phase
+	pub fields : Vec<RegionLabel>,
+	/// number of calculations which failed (their points are missing from the projection)
+	pub failures : usize,
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A grid node `(i, j)` with `x2 = i/n`, `x3 = j/n`.
+type Node = (usize,usize);
+
+impl LiquidusProjection {
+
+	/// Calculate the projection for the liquid phase `liquid`, searching the liquidus within `interval` and contouring the isotherms at `isotherms` (current temperature unit).
+	pub fn calculate<B: ChemAppBackend>(calculator: &Calculator<B>, liquid: &str, interval: (f64,f64), isotherms: &[f64], options: &LiquidusOptions)->Result<LiquidusProjection,ChemAppError>{
+		if calculator.basis.len() != 3 {
+			return Err(ChemAppError::OtherError(format!("A liquidus projection needs three basis formulas, the transform has {:?}", calculator.basis)));
+		}
+		if !(interval.0.is_finite() && interval.1.is_finite() && interval.0 > 0.0 && interval.0 < interval.1) {
+			return Err(ChemAppError::OtherError(format!("Temperature interval must satisfy 0 < low < high, got ({}, {})", interval.0, interval.1)));
+		}
+		if options.divisions == 0 {
+			return Err(ChemAppError::OtherError("A liquidus projection needs at least one grid division".to_string()));
+		}
+		let n = options.divisions;
+		let search = Search {calculator, liquid: calculator.phase_index(liquid)?, interval};
+		let point = |node: Node|->[f64;2] {[node.0 as f64/n as f64, node.1 as f64/n as f64]};
+		// liquidus at the grid nodes
+		let mut failures = 0usize;
+		let mut nodes : HashMap<Node,(f64,String)> = HashMap::new();
+		let mut points : Vec<LiquidusPoint> = Vec::new();
+		let mut builder = DiagramBuilder::default();
+		for i in 0..=n {
+			for j in 0..=n-i {
+				match search.liquidus(point((i, j))) {
+					Ok((temperature, primary)) => {
+						builder.sample(&Assemblage(vec![primary.clone()]), point((i, j)));
+						points.push(LiquidusPoint {composition: point((i, j)), temperature, primary: primary.clone()});
+						nodes.insert((i, j), (temperature, primary));
+					}
+					Err(_) => {failures += 1;}
+				}
+			}
+		}
+		// grid triangles
+		let mut triangles : Vec<[Node;3]> = Vec::new();
+		for i in 0..n {
+			for j in 0..n-i {
+				triangles.push([(i, j), (i+1, j), (i, j+1)]);
+				if i + j + 2 <= n {triangles.push([(i+1, j), (i, j+1), (i+1, j+1)]);}
+			}
+		}
+		// univariant points on the edges joining different primary phases
+		let mut univariant : HashMap<(String,String), Vec<([f64;2],f64)>> = HashMap::new();
+		let mut edge_points : HashMap<(Node,Node),[f64;2]> = HashMap::new();
+		for triangle in triangles.iter() {
+			for (a, b) in [(triangle[0], triangle[1]), (triangle[1], triangle[2]), (triangle[2], triangle[0])] {
+				let (a, b) = if a <= b {(a, b)} else {(b, a)};
+				if edge_points.contains_key(&(a, b)) {continue;}
+				let (pa, pb) = match (nodes.get(&a), nodes.get(&b)) {
+					(Some(na), Some(nb)) if na.1 != nb.1 => (na.1.clone(), nb.1.clone()),
+					_ => {continue;}
+				};
+				match search.bisect(point(a), point(b), &pa, options.x_tolerance) {
+					Ok((x, temperature)) => {
+						let key = if pa <= pb {(pa, pb)} else {(pb, pa)};
+						univariant.entry(key).or_default().push((x, temperature));
+						edge_points.insert((a, b), x);
+					}
+					Err(_) => {failures += 1;}
+				}
+			}
+		}
+		let gap = 2.5/(n as f64);
+		let mut univariant_lines : Vec<UnivariantLine> = Vec::new();
+		let mut keys : Vec<(String,String)> = univariant.keys().cloned().collect();
+		keys.sort();
+		for key in keys {
+			let samples = &univariant[&key];
+			for line in chain(samples.iter().map(|s| s.0).collect(), [1.0, 1.0], gap) {
+				let temperatures : Vec<f64> = line.iter().map(|p| samples.iter().find(|s| s.0 == *p).map(|s| s.1).unwrap_or(f64::NAN)).collect();
+				univariant_lines.push(UnivariantLine {phases: key.clone(), points: line, temperatures});
+			}
+		}
+		// invariant points in the triangles joining three primary phases
+		let mut invariant_points : Vec<InvariantPoint> = Vec::new();
+		for triangle in triangles.iter() {
+			let mut phases : Vec<String> = Vec::new();
+			for node in triangle.iter() {
+				match nodes.get(node) {
+					Some((_, primary)) => {if !phases.contains(primary) {phases.push(primary.clone());}}
+					None => {}
+				}
+			}
+			if phases.len() < 3 {continue;}
+			let found : Vec<[f64;2]> = [(triangle[0], triangle[1]), (triangle[1], triangle[2]), (triangle[2], triangle[0])].iter().filter_map(|(a, b)| edge_points.get(&if a <= b {(*a, *b)} else {(*b, *a)}).cloned()).collect();
+			let corners : Vec<[f64;2]> = triangle.iter().map(|node| point(*node)).collect();
+			let used = if found.is_empty() {&corners} else {&found};
+			let composition = [used.iter().map(|p| p[0]).sum::<f64>()/used.len() as f64, used.iter().map(|p| p[1]).sum::<f64>()/used.len() as f64];
+			match search.liquidus(composition) {
+				Ok((temperature, _)) => {
+					phases.sort();
+					invariant_points.push(InvariantPoint {phases, composition, temperature});
+				}
+				Err(_) => {failures += 1;}
+			}
+		}
+		// isotherms
+		let mut isotherm_lines : Vec<Isotherm> = Vec::new();
+		for &level in isotherms.iter() {
+			let mut crossings : Vec<[f64;2]> = Vec::new();
+			for triangle in triangles.iter() {
+				for (a, b) in [(triangle[0], triangle[1]), (triangle[1], triangle[2]), (triangle[2], triangle[0])] {
+					if let (Some((ta, _)), Some((tb, _))) = (nodes.get(&a), nodes.get(&b)) {
+						if (ta - level)*(tb - level) < 0.0 {
+							let w = (level - ta)/(tb - ta);
+							let (pa, pb) = (point(a), point(b));
+							let crossing = [pa[0] + w*(pb[0] - pa[0]), pa[1] + w*(pb[1] - pa[1])];
+							if !crossings.iter().any(|c| (c[0] - crossing[0]).abs() < 1e-12 && (c[1] - crossing[1]).abs() < 1e-12) {crossings.push(crossing);}
+						}
+					}
+				}
+			}
+			for line in chain(crossings, [1.0, 1.0], 1.5/(n as f64)) {
+				isotherm_lines.push(Isotherm {temperature: level, points: line});
+			}
+		}
+		let (_, fields) = builder.finish([1.0, 1.0], gap);
+		return Ok(LiquidusProjection {
+			basis : [calculator.basis[0].clone(), calculator.basis[1].clone(), calculator.basis[2].clone()],
+			liquid : liquid.to_string(),
+			points,
+			isotherms : isotherm_lines,
+			univariant_lines,
+			invariant_points,
+			fields,
+			failures,
+		});
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Liquidus calculations at single compositions.
+struct Search<'a, B: ChemAppBackend> {
+	calculator : &'a Calculator<B>,
+	liquid     : usize,
+	interval   : (f64,f64),
+}
+
+impl<'a, B: ChemAppBackend> Search<'a, B> {
+
+	/// The liquidus temperature and the primary phase at a composition.
+	fn liquidus(&self, composition: [f64;2])->Result<(f64,String),ChemAppError>{
+		let x = DVector::from_vec(vec![(1.0 - composition[0] - composition[1]).max(0.0), composition[0], composition[1]]);
+		self.calculator.reset()?;
+		let result = self.calculator.calculate_target_t(&x, self.liquid, self.liquid, self.interval, true, None, None)?;
+		let temperature = result.target.unwrap_or(f64::NAN);
+		// the precipitating phase has the highest activity among the condensed phases; a gas phase is always phase 1 with the ideal mixing model in ChemApp
+		let engine = &self.calculator.engine;
+		let mut primary : Option<(usize,f64)> = None;
+		for indexp in (1..=engine.tqnop()?).filter(|p| *p != self.liquid) {
+			if indexp == 1 && engine.tqmodl(indexp)?.trim() == "IDMX" {continue;}
+			let activity = engine.get_result(ResultQuantity::AC, indexp, 0)?;
+			if !(activity > 0.0) {continue;}
+			if primary.map_or(true, |(_, best)| activity > best) {primary = Some((indexp, activity));}
+		}
+		let (indexp, _) = primary.ok_or_else(|| ChemAppError::OtherError("No condensed phase other than the liquid to crystallize".to_string()))?;
+		return Ok((temperature, engine.tqgnp(indexp)?.trim().to_string()));
+	}
+
+	/// Bisect the segment `a`-`b` for the change of the primary phase from `primary`; returns the univariant composition and its liquidus temperature.
+	fn bisect(&self, a: [f64;2], b: [f64;2], primary: &str, tolerance: f64)->Result<([f64;2],f64),ChemAppError>{
+		let (mut low, mut high) = (0.0f64, 1.0f64);
+		let at = |w: f64|->[f64;2] {[a[0] + w*(b[0] - a[0]), a[1] + w*(b[1] - a[1])]};
+		while high - low > tolerance {
+			let middle = 0.5*(low + high);
+			if self.liquidus(at(middle))?.1 == primary {low = middle;} else {high = middle;}
+		}
+		let x = at(0.5*(low + high));
+		return Ok((x, self.liquidus(x)?.0));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::{MockBackend, MockPhase, MockConstituent, MockResults};
+
+	const SOLIDS : [&str;3] = ["CU(S)", "NI(S)", "FE(S)"];
+
+	/// Cu-Ni-Fe with a gas, a liquid and the three pure solids: the primary phase is the solid of the most abundant component and the liquidus is `1000 + 500*x_max`;
+	/// the gas has the highest activity everywhere.
+	fn eutectic()->Calculator<MockBackend> {
+		let mut backend = MockBackend::new().component("Cu", 63.546).component("Ni", 58.693).component("Fe", 55.845);
+		for (name, model) in [("GAS", "IDMX"), ("LIQUID", "RKMP")] {
+			backend = backend.phase(MockPhase::new(name, model)
+				.constituent(MockConstituent::new("Cu", &[1.0, 0.0, 0.0]))
+				.constituent(MockConstituent::new("Ni", &[0.0, 1.0, 0.0]))
+				.constituent(MockConstituent::new("Fe", &[0.0, 0.0, 1.0])));
+		}
+		backend = backend
+			.phase(MockPhase::pure(SOLIDS[0], &[1.0, 0.0, 0.0]))
+			.phase(MockPhase::pure(SOLIDS[1], &[0.0, 1.0, 0.0]))
+			.phase(MockPhase::pure(SOLIDS[2], &[0.0, 0.0, 1.0]));
+		let backend = backend.solver(|input| {
+			let amounts = input.incoming_amounts(3);
+			let total : f64 = amounts.iter().sum();
+			let primary = (0..3).fold(0, |best, k| if amounts[k] > amounts[best] {k} else {best});
+			let mut results = MockResults::new();
+			results.insert(("T".to_owned(), 0, 0), 1000.0 + 500.0*amounts[primary]/total);
+			results.insert(("AC".to_owned(), 1, 0), 10.0);
+			results.insert(("AC".to_owned(), 2, 0), 1.0);
+			for k in 0..3 {
+				results.insert(("AC".to_owned(), k+3, 0), if k == primary {1.0} else {0.5});
+			}
+			return Ok(results);
+		});
+		let mut calculator = Calculator::from_backend(backend).unwrap();
+		calculator.set_transform(&["Cu", "Ni", "Fe"]).unwrap();
+		return calculator;
+	}
+
+	/// the primary phase expected at a composition
+	fn primary(composition: [f64;2])->&'static str {
+		let x = [1.0 - composition[0] - composition[1], composition[0], composition[1]];
+		return SOLIDS[(0..3).fold(0, |best, k| if x[k] > x[best] {k} else {best})];
+	}
+
+	#[test]
+	fn projection_of_a_ternary_eutectic(){
+		let calculator = eutectic();
+		let options = LiquidusOptions {divisions: 7, ..LiquidusOptions::default()};
+		let projection = LiquidusProjection::calculate(&calculator, "LIQUID", (1000.0, 2000.0), &[1400.0], &options).unwrap();
+		assert_eq!(projection.failures, 0);
+		assert_eq!(projection.points.len(), 36);
+		// the primary phase is the solid with the highest activity, the gas excluded
+		for point in projection.points.iter() {
+			assert_eq!(point.primary, primary(point.composition));
+		}
+		let corner = projection.points.iter().find(|p| p.composition == [0.0, 0.0]).unwrap();
+		assert!((corner.temperature - 1500.0).abs() < 1e-9);
+		assert_eq!(projection.fields.len(), 3);
+		// univariant lines lie where the two most abundant components are equally abundant
+		let pairs : Vec<(String,String)> = projection.univariant_lines.iter().map(|l| l.phases.clone()).collect();
+		for pair in [(SOLIDS[0], SOLIDS[2]), (SOLIDS[1], SOLIDS[0]), (SOLIDS[2], SOLIDS[1])] {
+			assert!(pairs.iter().any(|p| (p.0.as_str(), p.1.as_str()) == pair || (p.1.as_str(), p.0.as_str()) == pair), "{:?} missing from {:?}", pair, pairs);
+		}
+		for line in projection.univariant_lines.iter() {
+			for (point, temperature) in line.points.iter().zip(line.temperatures.iter()) {
+				let mut x = [1.0 - point[0] - point[1], point[0], point[1]];
+				x.sort_by(|a, b| b.total_cmp(a));
+				assert!(x[0] - x[1] < 2e-3/options.divisions as f64, "{:?} is not univariant", point);
+				assert!((temperature - (1000.0 + 500.0*x[0])).abs() < 1e-3);
+			}
+		}
+		// the three fields meet at the centre of the triangle
+		assert_eq!(projection.invariant_points.len(), 1);
+		let invariant = &projection.invariant_points[0];
+		assert_eq!(invariant.phases, vec!["CU(S)".to_string(), "FE(S)".to_string(), "NI(S)".to_string()]);
+		assert!(invariant.composition.iter().all(|x| (x - 1.0/3.0).abs() < 1.0/options.divisions as f64));
+		assert!(projection.isotherms.iter().all(|i| i.temperature == 1400.0) && projection.isotherms.len() >= 3);
+	}
+
+	#[test]
+	fn projection_needs_three_basis_formulas(){
+		let mut calculator = eutectic();
+		calculator.set_transform(&["Cu", "Ni"]).unwrap();
+		assert!(LiquidusProjection::calculate(&calculator, "LIQUID", (1000.0, 2000.0), &[], &LiquidusOptions::default()).is_err());
+		let calculator = eutectic();
+		assert!(LiquidusProjection::calculate(&calculator, "LIQUID", (2000.0, 1000.0), &[], &LiquidusOptions::default()).is_err());
+	}
+
+}
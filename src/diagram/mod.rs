@@ -0,0 +1,205 @@
+// chemapp_rs::diagram
+
+//! Phase diagram generators built on the `Calculator` calculation functions. The diagrams are returned as data - boundary polylines and labelled regions - and can be plotted with any tool.
+
+pub mod binary;
+pub mod ternary;
+pub mod liquidus;
+pub mod predominance;
+pub mod ellingham;
+
+use std::fmt;
+use std::collections::{BTreeMap};
+use serde::{Serialize, Deserialize};
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{ResultQuantity};
+
+pub use binary::{PhaseDiagram, PhaseDiagramOptions};
+pub use ternary::{IsothermalSection, IsothermalSectionOptions, TieLine, TieTriangle};
+pub use liquidus::{LiquidusProjection, LiquidusOptions, LiquidusPoint, Isotherm, UnivariantLine, InvariantPoint};
+pub use predominance::{PredominanceDiagram, PredominanceOptions, PredominanceAxis};
+pub use ellingham::{EllinghamDiagram, EllinghamOptions, EllinghamLine, Kink};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The set of stable phases of an equilibrium, in the phase index order.
+#[derive(Debug,Clone,PartialEq,Eq,Hash,PartialOrd,Ord,Serialize,Deserialize)]
+pub struct Assemblage(pub Vec<String>);
+
+impl Assemblage {
+
+	/// the phase names
+	pub fn phases(&self)->&[String] {
+		return &self.0;
+	}
+
+	/// `true` if the phase is stable
+	pub fn contains(&self, phase: &str)->bool {
+		return self.0.iter().any(|p| p == phase);
+	}
+
+	/// number of stable phases
+	pub fn len(&self)->usize {
+		return self.0.len();
+	}
+
+	/// `true` if no phase is stable
+	pub fn is_empty(&self)->bool {
+		return self.0.is_empty();
+	}
+
+	/// The phases with an equilibrium amount above `tolerance` in the current state of the calculator.
+	pub fn current<B: ChemAppBackend>(calculator: &Calculator<B>, tolerance: f64)->Result<Assemblage,ChemAppError>{
+		let mut phases : Vec<String> = Vec::new();
+		for indexp in 1..=calculator.engine.tqnop()? {
+			if calculator.engine.get_result(ResultQuantity::A, indexp, 0)? > tolerance {
+				phases.push(calculator.engine.tqgnp(indexp)?.trim().to_string());
+			}
+		}
+		return Ok(Assemblage(phases));
+	}
+
+}
+
+impl fmt::Display for Assemblage {
+
+	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
+		return write!(f, "{}", self.0.join(" + "));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A phase boundary: a polyline separating two phase assemblages, in the coordinates of the diagram.
+#[derive(Debug,Clone)]
+pub struct Boundary {
+	/// the assemblages on both sides (ordered)
+	pub between : (Assemblage, Assemblage),
+	/// the polyline points
+	pub points  : Vec<[f64;2]>,
+}
+
+/// A labelled region of a diagram.
+#[derive(Debug,Clone)]
+pub struct RegionLabel {
+	/// the stable phase assemblage
+	pub assemblage : Assemblage,
+	/// a position inside the region to place the label at (the sample closest to the centroid of all samples)
+	pub position   : [f64;2],
+	/// number of grid samples with this assemblage
+	pub samples    : usize,
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The distance between two points, each coordinate divided by `scale`.
+fn distance(a: &[f64;2], b: &[f64;2], scale: [f64;2])->f64 {
+	return (((a[0] - b[0])/scale[0]).powi(2) + ((a[1] - b[1])/scale[1]).powi(2)).sqrt();
+}
+
+/// Order unordered boundary points into polylines: starting from the point farthest from the centroid (an end of the curve), the nearest remaining point is appended until it is farther than `gap` (in scaled units).
+pub(crate) fn chain(mut points: Vec<[f64;2]>, scale: [f64;2], gap: f64)->Vec<Vec<[f64;2]>> {
+	let mut lines : Vec<Vec<[f64;2]>> = Vec::new();
+	while !points.is_empty() {
+		let n = points.len() as f64;
+		let centroid = [points.iter().map(|p| p[0]).sum::<f64>()/n, points.iter().map(|p| p[1]).sum::<f64>()/n];
+		let start = (0..points.len()).max_by(|&i, &j| distance(&points[i], &centroid, scale).total_cmp(&distance(&points[j], &centroid, scale))).unwrap_or(0);
+		let mut line : Vec<[f64;2]> = vec![points.swap_remove(start)];
+		loop {
+			let last = line[line.len()-1];
+			let next = (0..points.len()).map(|i| (i, distance(&last, &points[i], scale))).min_by(|a, b| a.1.total_cmp(&b.1));
+			match next {
+				Some((i, d)) if d <= gap => {line.push(points.swap_remove(i));}
+				_ => {break;}
+			}
+		}
+		lines.push(line);
+	}
+	return lines;
+}
+
+/// Collects boundary points and region samples of a diagram and turns them into polylines and labels.
+#[derive(Debug,Default)]
+pub(crate) struct DiagramBuilder {
+	boundaries : BTreeMap<(Assemblage,Assemblage), Vec<[f64;2]>>,
+	samples    : BTreeMap<Assemblage, Vec<[f64;2]>>,
+}
+
+impl DiagramBuilder {
+
+	/// a boundary point between two assemblages (in any order)
+	pub(crate) fn boundary(&mut self, a: &Assemblage, b: &Assemblage, point: [f64;2]){
+		let key = if a <= b {(a.clone(), b.clone())} else {(b.clone(), a.clone())};
+		self.boundaries.entry(key).or_default().push(point);
+	}
+
+	/// a point known to belong to a region
+	pub(crate) fn sample(&mut self, assemblage: &Assemblage, point: [f64;2]){
+		self.samples.entry(assemblage.clone()).or_default().push(point);
+	}
+
+	/// Chain the boundary points into polylines (`gap` in the units of `scale`) and place the region labels.
+	pub(crate) fn finish(self, scale: [f64;2], gap: f64)->(Vec<Boundary>, Vec<RegionLabel>) {
+		let mut boundaries : Vec<Boundary> = Vec::new();
+		for (between, points) in self.boundaries.into_iter() {
+			for line in chain(points, scale, gap) {
+				boundaries.push(Boundary {between: between.clone(), points: line});
+			}
+		}
+		let mut regions : Vec<RegionLabel> = Vec::new();
+		for (assemblage, points) in self.samples.into_iter() {
+			let n = points.len() as f64;
+			let centroid = [points.iter().map(|p| p[0]).sum::<f64>()/n, points.iter().map(|p| p[1]).sum::<f64>()/n];
+			let position = points.iter().min_by(|a, b| distance(a, &centroid, scale).total_cmp(&distance(b, &centroid, scale))).cloned().unwrap_or(centroid);
+			regions.push(RegionLabel {assemblage, position, samples: points.len()});
+		}
+		return (boundaries, regions);
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn chain_orders_a_curve_from_its_end(){
+		let points = vec![[3.0, 0.0], [0.0, 0.0], [6.0, 0.0], [1.0, 0.0], [4.0, 0.0], [2.0, 0.0]];
+		let lines = chain(points, [1.0, 1.0], 2.5);
+		assert_eq!(lines, vec![vec![[6.0, 0.0], [4.0, 0.0], [3.0, 0.0], [2.0, 0.0], [1.0, 0.0], [0.0, 0.0]]]);
+		assert!(chain(Vec::new(), [1.0, 1.0], 1.0).is_empty());
+	}
+
+	#[test]
+	fn chain_splits_at_gaps(){
+		let points = vec![[0.0, 0.0], [2.0, 100.0], [1.0, 0.0], [0.0, 100.0], [2.0, 0.0], [1.0, 100.0]];
+		let lines = chain(points, [1.0, 1.0], 1.5);
+		assert_eq!(lines.len(), 2);
+		for line in lines.iter() {
+			assert_eq!(line.len(), 3);
+			assert!(line.iter().all(|p| p[1] == line[0][1]));
+			assert_eq!(line[1][0], 1.0);
+			assert_eq!((line[0][0] - line[2][0]).abs(), 2.0);
+		}
+		assert_ne!(lines[0][0][1], lines[1][0][1]);
+	}
+
+	#[test]
+	fn chain_measures_the_gap_in_scaled_units(){
+		let points = vec![[0.0, 0.0], [0.0, 10.0], [0.0, 20.0]];
+		assert_eq!(chain(points.clone(), [1.0, 1.0], 1.5).len(), 3);
+		assert_eq!(chain(points, [1.0, 10.0], 1.5), vec![vec![[0.0, 20.0], [0.0, 10.0], [0.0, 0.0]]]);
+	}
+
+}
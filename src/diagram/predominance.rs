@@ -0,0 +1,344 @@
+// chemapp_rs::diagram::predominance.rs
+
+//! Predominance area (Kellogg) diagrams of a metal-gas system, e.g. Cu-O-S: the activities of two gas constituents (log pO2 vs log pS2 at a fixed temperature) or one activity and the temperature are the axes.
+//! The activities are entered as `AC` conditions on the gas constituents, so the non-metallic elements are open to the gas; one mol of the metal is entered and the region of a point is labelled by the condensed phase holding the most metal.
+//! The grid nodes are labelled, the label changes along the grid lines are refined by bisection, and the points are chained into boundary lines as in the other diagrams.
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{Condition, ResultQuantity, TargetVariable};
+use crate::diagram::{Assemblage, Boundary, RegionLabel, DiagramBuilder};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// An axis of a predominance diagram.
+#[derive(Debug,Clone,PartialEq)]
+pub enum PredominanceAxis {
+	/// `log10` of the activity of a gas constituent `(phase, constituent)` (the partial pressure in bar for an ideal gas) over `(low, high)`
+	Activity(String, String, (f64,f64)),
+	/// temperature [current temperature unit] over `(low, high)`
+	Temperature((f64,f64)),
+}
+
+impl PredominanceAxis {
+
+	/// the axis label, e.g. `log a(O2)`
+	pub fn name(&self)->String {
+		return match self {
+			Self::Activity(_, constituent, _) => format!("log a({})", constituent),
+			Self::Temperature(_) => "T".to_string(),
+		};
+	}
+
+	/// the axis range
+	pub fn range(&self)->(f64,f64) {
+		return match self {
+			Self::Activity(_, _, range) | Self::Temperature(range) => *range,
+		};
+	}
+
+}
+
+/// Resolution and tolerances of `PredominanceDiagram::calculate`.
+#[derive(Debug,Clone)]
+pub struct PredominanceOptions {
+	/// number of grid nodes along the x axis
+	pub columns : usize,
+	/// number of grid nodes along the y axis
+	pub rows : usize,
+	/// number of bisection steps locating a boundary between two grid nodes
+	pub bisection_steps : usize,
+	/// amount of the metal in a phase above which the phase counts
+	pub amount_tolerance : f64,
+}
+
+impl Default for PredominanceOptions {
+	fn default()->PredominanceOptions {
+		return PredominanceOptions {
+			columns : 41,
+			rows : 41,
+			bisection_steps : 12,
+			amount_tolerance : 1e-12,
+		};
+	}
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A calculated predominance diagram; the points are `[x, y]` in the axis units, every region assemblage holds the single predominant phase.
+#[derive(Debug,Clone)]
+pub struct PredominanceDiagram {
+	/// the metal (system component)
+	pub metal : String,
+	/// the axis labels
+	pub axes : [String;2],
+	/// the axis ranges
+	pub ranges : [(f64,f64);2],
+	/// boundaries between the predominance areas
+	pub boundaries : Vec<Boundary>,
+	/// the predominance areas
+	pub regions : Vec<RegionLabel>,
+	/// number of calculations which failed (their points are missing from the diagram)
+	pub failures : usize,
+}
+
+impl PredominanceDiagram {
+
+	/// Calculate the diagram of the system component `metal` over the axes `x` and `y`; `temperature` is required unless one of the axes is the temperature.
+	pub fn calculate<B: ChemAppBackend>(calculator: &Calculator<B>, metal: &str, x: PredominanceAxis, y: PredominanceAxis, temperature: Option<f64>, options: &PredominanceOptions)->Result<PredominanceDiagram,ChemAppError>{
+		let plane = Plane::new(calculator, metal, [&x, &y], temperature, options)?;
+		let [xs, ys] = &plane.nodes;
+		let mut builder = DiagramBuilder::default();
+		let mut failures = 0usize;
+		let labels : Vec<Vec<Option<Assemblage>>> = ys.iter().map(|&v| xs.iter().map(|&u| plane.label(u, v).ok()).collect()).collect();
+		for (i, row) in labels.iter().enumerate() {
+			for (j, label) in row.iter().enumerate() {
+				match label {
+					Some(assemblage) => {builder.sample(assemblage, [xs[j], ys[i]]);}
+					None => {failures += 1;}
+				}
+			}
+		}
+		for i in 0..ys.len() {
+			for j in 0..xs.len() {
+				let here = match &labels[i][j] {Some(label) => label, None => continue};
+				// along x
+				if let Some(Some(right)) = labels[i].get(j+1) {
+					if here != right {
+						match plane.bisect(|s| [s, ys[i]], (xs[j], xs[j+1]), here) {
+							Ok((s, beyond)) => {builder.boundary(here, &beyond, [s, ys[i]]);}
+							Err(_) => {failures += 1;}
+						}
+					}
+				}
+				// along y
+				if let Some(Some(upper)) = labels.get(i+1).map(|row| &row[j]) {
+					if here != upper {
+						match plane.bisect(|s| [xs[j], s], (ys[i], ys[i+1]), here) {
+							Ok((s, beyond)) => {builder.boundary(here, &beyond, [xs[j], s]);}
+							Err(_) => {failures += 1;}
+						}
+					}
+				}
+			}
+		}
+		let (xr, yr) = (x.range(), y.range());
+		let scale = [xr.1 - xr.0, yr.1 - yr.0];
+		let gap = 2.5*(1.0/(options.columns - 1) as f64).max(1.0/(options.rows - 1) as f64);
+		let (boundaries, regions) = builder.finish(scale, gap);
+		return Ok(PredominanceDiagram {
+			metal : metal.to_string(),
+			axes : [x.name(), y.name()],
+			ranges : [xr, yr],
+			boundaries,
+			regions,
+			failures,
+		});
+	}
+
+	/// The area of a predominant phase, `None` if the phase does not predominate anywhere.
+	pub fn region_of(&self, phase: &str)->Option<&RegionLabel> {
+		return self.regions.iter().find(|r| r.assemblage.contains(phase));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// An axis with the names resolved.
+#[derive(Debug,Clone,Copy)]
+enum Resolved {
+	Activity(usize, usize),
+	Temperature,
+}
+
+/// The calculations in the plane of the diagram.
+struct Plane<'a, B: ChemAppBackend> {
+	calculator  : &'a Calculator<B>,
+	metal       : usize,
+	axes        : [Resolved;2],
+	temperature : Option<f64>,
+	/// the gas phases of the activity axes, not counted as predominant
+	gases       : Vec<usize>,
+	nodes       : [Vec<f64>;2],
+	options     : &'a PredominanceOptions,
+}
+
+impl<'a, B: ChemAppBackend> Plane<'a, B> {
+
+	fn new(calculator: &'a Calculator<B>, metal: &str, axes: [&PredominanceAxis;2], temperature: Option<f64>, options: &'a PredominanceOptions)->Result<Self,ChemAppError>{
+		if options.columns < 2 || options.rows < 2 {
+			return Err(ChemAppError::OtherError("A predominance diagram needs at least two grid nodes along each axis".to_string()));
+		}
+		let mut resolved = [Resolved::Temperature;2];
+		let mut gases : Vec<usize> = Vec::new();
+		for (k, axis) in axes.iter().enumerate() {
+			let (low, high) = axis.range();
+			if !(low.is_finite() && high.is_finite() && low < high) {
+				return Err(ChemAppError::OtherError(format!("Axis range of {} must satisfy low < high, got ({}, {})", axis.name(), low, high)));
+			}
+			resolved[k] = match axis {
+				PredominanceAxis::Activity(phase, constituent, _) => {
+					let indexp = calculator.phase_index(phase)?;
+					if !gases.contains(&indexp) {gases.push(indexp);}
+					Resolved::Activity(indexp, calculator.constituent_index(indexp, constituent)?)
+				}
+				PredominanceAxis::Temperature((low, _)) => {
+					if !(*low > 0.0) {
+						return Err(ChemAppError::OtherError(format!("Temperature range must be positive, got {:?}", axis.range())));
+					}
+					Resolved::Temperature
+				}
+			};
+		}
+		if gases.is_empty() {
+			return Err(ChemAppError::OtherError("A predominance diagram needs at least one activity axis".to_string()));
+		}
+		let has_temperature = resolved.iter().any(|a| matches!(a, Resolved::Temperature));
+		if !has_temperature && temperature.is_none() {
+			return Err(ChemAppError::OtherError("A temperature is required when no axis is the temperature".to_string()));
+		}
+		let counts = [options.columns, options.rows];
+		let nodes = [0, 1].map(|k| {
+			let (low, high) = axes[k].range();
+			(0..counts[k]).map(|i| low + (high - low)*i as f64/(counts[k] - 1) as f64).collect::<Vec<f64>>()
+		});
+		return Ok(Self {
+			calculator,
+			metal : calculator.component_index(metal)?,
+			axes : resolved,
+			temperature : if has_temperature {None} else {temperature},
+			gases,
+			nodes,
+			options,
+		});
+	}
+
+	/// the predominant phase at `(u, v)`
+	fn label(&self, u: f64, v: f64)->Result<Assemblage,ChemAppError>{
+		let engine = &self.calculator.engine;
+		self.calculator.reset()?;
+		if let Some(t) = self.temperature {engine.set_condition(Condition::T, 0, 0, t)?;}
+		engine.set_condition(Condition::IA, 0, self.metal, 1.0)?;
+		for (axis, value) in self.axes.iter().zip([u, v]) {
+			match axis {
+				Resolved::Activity(indexp, indexc) => {engine.set_condition(Condition::AC, *indexp, *indexc, 10f64.powf(value))?;}
+				Resolved::Temperature => {engine.set_condition(Condition::T, 0, 0, value)?;}
+			}
+		}
+		engine.calculate(TargetVariable::None, 0, 0, (0.0, 0.0))?;
+		let mut best : Option<(usize,f64,bool)> = None;
+		for indexp in 1..=engine.tqnop()? {
+			let amount = engine.get_result(ResultQuantity::AP, indexp, self.metal)?;
+			if amount <= self.options.amount_tolerance {continue;}
+			let condensed = !self.gases.contains(&indexp);
+			// condensed phases first, then the larger amount of the metal
+			let better = match best {
+				None => true,
+				Some((_, a, c)) => (condensed && !c) || (condensed == c && amount > a),
+			};
+			if better {best = Some((indexp, amount, condensed));}
+		}
+		return match best {
+			Some((indexp, _, _)) => Ok(Assemblage(vec![engine.tqgnp(indexp)?.trim().to_string()])),
+			None => Err(ChemAppError::OtherError(format!("No phase holds the metal at ({}, {})", u, v))),
+		};
+	}
+
+	/// Bisect `interval` along the line `point(s)` for the change from the label `from`; returns the boundary position and the label beyond it.
+	fn bisect<F: Fn(f64)->[f64;2]>(&self, point: F, interval: (f64,f64), from: &Assemblage)->Result<(f64,Assemblage),ChemAppError>{
+		let (mut low, mut high) = interval;
+		let [u, v] = point(high);
+		let mut beyond = self.label(u, v)?;
+		for _ in 0..self.options.bisection_steps {
+			let middle = 0.5*(low + high);
+			let [u, v] = point(middle);
+			let label = self.label(u, v)?;
+			if &label == from {
+				low = middle;
+			} else {
+				high = middle;
+				beyond = label;
+			}
+		}
+		return Ok((0.5*(low + high), beyond));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::{MockBackend, MockPhase, MockConstituent, MockResults};
+
+	/// Cu-O with an O2 gas: CU below `log pO2 = -20 + T/100`, CUO above `log pO2 = -2` and CU2O in between; the gas holds more Cu than any condensed phase.
+	fn copper_oxides()->Calculator<MockBackend> {
+		let backend = MockBackend::new()
+			.component("Cu", 63.546)
+			.component("O", 15.999)
+			.phase(MockPhase::new("GAS", "IDMX").constituent(MockConstituent::new("O2", &[0.0, 2.0])))
+			.phase(MockPhase::pure("CU", &[1.0, 0.0]))
+			.phase(MockPhase::pure("CU2O", &[2.0, 1.0]))
+			.phase(MockPhase::pure("CUO", &[1.0, 1.0]))
+			.solver(|input| {
+				let log_p = input.condition("AC", 1, 1).unwrap().log10();
+				let t = input.condition("T", 0, 0).unwrap();
+				let indexp = if log_p < -20.0 + t/100.0 {2} else if log_p < -2.0 {3} else {4};
+				let mut results = MockResults::new();
+				results.insert(("AP".to_owned(), 1, 1), 2.0);
+				results.insert(("AP".to_owned(), indexp, 1), input.condition("IA", 0, 1).unwrap());
+				return Ok(results);
+			});
+		return Calculator::from_backend(backend).unwrap();
+	}
+
+	fn o2_axis()->PredominanceAxis {
+		return PredominanceAxis::Activity("GAS".to_string(), "O2".to_string(), (-15.0, 0.0));
+	}
+
+	#[test]
+	fn oxygen_potential_against_temperature(){
+		let calculator = copper_oxides();
+		let options = PredominanceOptions {columns: 16, rows: 11, ..PredominanceOptions::default()};
+		let diagram = PredominanceDiagram::calculate(&calculator, "Cu", o2_axis(), PredominanceAxis::Temperature((1000.0, 1500.0)), None, &options).unwrap();
+		assert_eq!(diagram.failures, 0);
+		assert_eq!(diagram.axes, ["log a(O2)".to_string(), "T".to_string()]);
+		// the condensed phases predominate over the gas
+		assert!(diagram.region_of("GAS").is_none());
+		assert_eq!(diagram.regions.len(), 3);
+		let (cu, cu2o, cuo) = (diagram.region_of("CU").unwrap(), diagram.region_of("CU2O").unwrap(), diagram.region_of("CUO").unwrap());
+		assert!(cu.position[0] < -20.0 + cu.position[1]/100.0);
+		assert!(cu2o.position[0] > -20.0 + cu2o.position[1]/100.0 && cu2o.position[0] < -2.0);
+		assert!(cuo.position[0] > -2.0);
+		// the metal/oxide boundary follows the line, the oxide/oxide one is vertical
+		let between = |a: &str, b: &str| diagram.boundaries.iter().filter(|boundary| {
+			let names = (boundary.between.0.to_string(), boundary.between.1.to_string());
+			return names == (a.to_string(), b.to_string()) || names == (b.to_string(), a.to_string());
+		}).flat_map(|boundary| boundary.points.iter().cloned()).collect::<Vec<[f64;2]>>();
+		let reduction = between("CU", "CU2O");
+		assert!(reduction.len() >= options.rows);
+		assert!(reduction.iter().all(|p| (p[0] - (-20.0 + p[1]/100.0)).abs() < 1e-3));
+		let oxidation = between("CU2O", "CUO");
+		assert_eq!(oxidation.len(), options.rows);
+		assert!(oxidation.iter().all(|p| (p[0] + 2.0).abs() < 1e-3));
+		assert!(between("CU", "CUO").is_empty());
+	}
+
+	#[test]
+	fn predominance_needs_an_activity_axis_and_a_temperature(){
+		let calculator = copper_oxides();
+		let options = PredominanceOptions::default();
+		let t_axis = PredominanceAxis::Temperature((1000.0, 1500.0));
+		assert!(PredominanceDiagram::calculate(&calculator, "Cu", t_axis.clone(), t_axis, None, &options).is_err());
+		assert!(PredominanceDiagram::calculate(&calculator, "Cu", o2_axis(), o2_axis(), None, &options).is_err());
+	}
+
+}
@@ -0,0 +1,387 @@
+// chemapp_rs::diagram::ternary.rs
+
+//! Isothermal sections of ternary systems in the basis of three formulas set with `Calculator::set_transform` (e.g. CaO-SiO2-FeO).
+//! The Gibbs triangle is covered by a coarse triangular grid; triangles whose corners have different stable assemblages are subdivided up to a maximal depth. The edges of the finest triangles crossing a boundary
+//! are refined with composition-target calculations (`Calculator::calculate_target_x_from_left_with`) for the phase appearing along the edge. Two-phase samples give tie-lines and three-phase regions give tie-triangles.
+//!
+//! Compositions are given as `[x2, x3]`, the fractions of the second and the third basis formula (`x1 = 1 - x2 - x3`); `cartesian` converts them into plotting coordinates.
+
+use std::collections::{HashMap, HashSet};
+use nalgebra::{DVector, DMatrix};
+
+use crate::calculator::{Calculator, TargetXOptions};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{ResultQuantity};
+use crate::diagram::{Assemblage, Boundary, RegionLabel, DiagramBuilder};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Resolution and tolerances of `IsothermalSection::calculate`.
+#[derive(Debug,Clone)]
+pub struct IsothermalSectionOptions {
+	/// number of divisions of each side of the triangle in the initial grid
+	pub divisions : usize,
+	/// number of times a triangle crossing a boundary is halved
+	pub max_depth : usize,
+	/// width of the final bracket of the composition-target search, as a fraction of the edge
+	pub x_tolerance : f64,
+	/// equilibrium amount above which a phase counts as stable
+	pub amount_tolerance : f64,
+}
+
+impl Default for IsothermalSectionOptions {
+	fn default()->IsothermalSectionOptions {
+		return IsothermalSectionOptions {
+			divisions : 10,
+			max_depth : 3,
+			x_tolerance : 1e-4,
+			amount_tolerance : 1e-12,
+		};
+	}
+}
+
+/// A tie-line between the compositions of the two phases of a two-phase equilibrium.
+#[derive(Debug,Clone)]
+pub struct TieLine {
+	/// the two phases
+	pub assemblage : Assemblage,
+	/// the phase compositions, in the order of `assemblage`
+	pub ends : [[f64;2];2],
+}
+
+/// A tie-triangle between the compositions of the three phases of a three-phase equilibrium.
+#[derive(Debug,Clone)]
+pub struct TieTriangle {
+	/// the three phases
+	pub assemblage : Assemblage,
+	/// the phase compositions, in the order of `assemblage`
+	pub corners : [[f64;2];3],
+}
+
+/// A calculated isothermal section.
+#[derive(Debug,Clone)]
+pub struct IsothermalSection {
+	/// the basis formulas
+	pub basis : [String;3],
+	/// the temperature
+	pub temperature : f64,
+	/// phase boundaries
+	pub boundaries : Vec<Boundary>,
+	/// tie-lines of the two-phase regions
+	pub tie_lines : Vec<TieLine>,
+	/// tie-triangles of the three-phase regions
+	pub tie_triangles : Vec<TieTriangle>,
+	/// phase fields
+	pub regions : Vec<RegionLabel>,
+	/// number of calculations which failed (their points are missing from the section)
+	pub failures : usize,
+}
+
+/// Plotting coordinates of a composition `[x2, x3]` in an equilateral Gibbs triangle with the first basis formula at `(0, 0)`, the second at `(1, 0)` and the third at `(0.5, sqrt(3)/2)`.
+pub fn cartesian(point: [f64;2])->[f64;2] {
+	return [point[0] + 0.5*point[1], point[1]*0.75f64.sqrt()];
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The result of an equilibrium at a grid point.
+#[derive(Debug,Clone)]
+struct Sample {
+	assemblage : Assemblage,
+	/// compositions of the stable phases, for two- and three-phase equilibria
+	compositions : Vec<[f64;2]>,
+}
+
+/// A grid point `(i, j)` with `x2 = i/n`, `x3 = j/n`.
+type Node = (usize,usize);
+
+impl IsothermalSection {
+
+	/// Calculate the section at temperature `temp` (current temperature unit); the calculator transform must have three basis formulas.
+	pub fn calculate<B: ChemAppBackend>(calculator: &Calculator<B>, temp: f64, options: &IsothermalSectionOptions)->Result<IsothermalSection,ChemAppError>{
+		if calculator.basis.len() != 3 {
+			return Err(ChemAppError::OtherError(format!("An isothermal section needs three basis formulas, the transform has {:?}", calculator.basis)));
+		}
+		if !(temp.is_finite() && temp > 0.0) {
+			return Err(ChemAppError::OtherError(format!("Temperature must be positive, got {}", temp)));
+		}
+		if options.divisions == 0 {
+			return Err(ChemAppError::OtherError("An isothermal section needs at least one grid division".to_string()));
+		}
+		let mut grid = Grid::new(calculator, temp, options)?;
+		// subdivide the initial triangles crossing a boundary
+		let step = 1usize << options.max_depth;
+		let mut edges : HashSet<(Node,Node)> = HashSet::new();
+		for a in 0..options.divisions {
+			for b in 0..options.divisions - a {
+				grid.refine([(a*step, b*step), ((a+1)*step, b*step), (a*step, (b+1)*step)], step, &mut edges);
+				if a + b + 2 <= options.divisions {
+					grid.refine([((a+1)*step, b*step), (a*step, (b+1)*step), ((a+1)*step, (b+1)*step)], step, &mut edges);
+				}
+			}
+		}
+		// boundary points from composition-target calculations along the edges
+		let mut builder = DiagramBuilder::default();
+		let mut edges : Vec<(Node,Node)> = edges.into_iter().collect();
+		edges.sort();
+		for (a, b) in edges.into_iter() {
+			match grid.boundary(a, b) {
+				Ok(Some((from, to, point))) => {builder.boundary(&from, &to, point);}
+				Ok(None) => {}
+				Err(_) => {grid.failures += 1;}
+			}
+		}
+		// regions, tie-lines and tie-triangles from the samples
+		let mut tie_lines : Vec<TieLine> = Vec::new();
+		let mut tie_triangles : Vec<TieTriangle> = Vec::new();
+		let mut nodes : Vec<&Node> = grid.samples.keys().collect();
+		nodes.sort();
+		for node in nodes {
+			let sample = match &grid.samples[node] {Some(sample) => sample, None => {continue;}};
+			builder.sample(&sample.assemblage, grid.point(*node));
+			match sample.compositions.len() {
+				2 => {tie_lines.push(TieLine {assemblage: sample.assemblage.clone(), ends: [sample.compositions[0], sample.compositions[1]]});}
+				3 => {
+					if !tie_triangles.iter().any(|t| t.assemblage == sample.assemblage) {
+						tie_triangles.push(TieTriangle {assemblage: sample.assemblage.clone(), corners: [sample.compositions[0], sample.compositions[1], sample.compositions[2]]});
+					}
+				}
+				_ => {}
+			}
+		}
+		let gap = 2.5/(options.divisions as f64);
+		let (boundaries, regions) = builder.finish([1.0, 1.0], gap);
+		return Ok(IsothermalSection {
+			basis : [calculator.basis[0].clone(), calculator.basis[1].clone(), calculator.basis[2].clone()],
+			temperature : temp,
+			boundaries,
+			tie_lines,
+			tie_triangles,
+			regions,
+			failures : grid.failures,
+		});
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The adaptive grid over the Gibbs triangle with the samples calculated so far.
+struct Grid<'a, B: ChemAppBackend> {
+	calculator : &'a Calculator<B>,
+	temp       : f64,
+	options    : &'a IsothermalSectionOptions,
+	/// number of divisions of each side at the finest level
+	n          : usize,
+	/// system component amounts of one unit of each basis formula (columns)
+	basis      : DMatrix<f64>,
+	samples    : HashMap<Node, Option<Sample>>,
+	failures   : usize,
+}
+
+impl<'a, B: ChemAppBackend> Grid<'a, B> {
+
+	fn new(calculator: &'a Calculator<B>, temp: f64, options: &'a IsothermalSectionOptions)->Result<Self,ChemAppError>{
+		let columns : Vec<DVector<f64>> = (0..3).map(|k| {
+			let mut unit = DVector::zeros(3);
+			unit[k] = 1.0;
+			return calculator.transform.transform_final2init(&unit, false, false, false).column(0).into_owned();
+		}).collect();
+		return Ok(Self {
+			calculator,
+			temp,
+			options,
+			n        : options.divisions << options.max_depth,
+			basis    : DMatrix::from_columns(&columns),
+			samples  : HashMap::new(),
+			failures : 0,
+		});
+	}
+
+	/// the composition of a grid point
+	fn point(&self, node: Node)->[f64;2] {
+		return [node.0 as f64/self.n as f64, node.1 as f64/self.n as f64];
+	}
+
+	/// the composition of a grid point in the transform basis
+	fn composition(&self, node: Node)->DVector<f64> {
+		let [x2, x3] = self.point(node);
+		return DVector::from_vec(vec![(1.0 - x2 - x3).max(0.0), x2, x3]);
+	}
+
+	/// Calculate the equilibrium at a grid point, once.
+	fn sample(&mut self, node: Node)->Option<Sample> {
+		if let Some(sample) = self.samples.get(&node) {
+			return sample.clone();
+		}
+		let sample = self.evaluate(node).ok();
+		if sample.is_none() {self.failures += 1;}
+		self.samples.insert(node, sample.clone());
+		return sample;
+	}
+
+	fn evaluate(&self, node: Node)->Result<Sample,ChemAppError>{
+		let amounts : DVector<f64> = &self.basis*self.composition(node);
+		self.calculator.calculate_isothermal_(&amounts, self.temp)?;
+		let assemblage = Assemblage::current(self.calculator, self.options.amount_tolerance)?;
+		let mut compositions : Vec<[f64;2]> = Vec::new();
+		if assemblage.len() == 2 || assemblage.len() == 3 {
+			for name in assemblage.phases() {
+				compositions.push(self.phase_composition(self.calculator.phase_index(name)?)?);
+			}
+		}
+		return Ok(Sample {assemblage, compositions});
+	}
+
+	/// The composition of a stable phase in the basis, by least squares over the system component amounts in the phase.
+	fn phase_composition(&self, indexp: usize)->Result<[f64;2],ChemAppError>{
+		let engine = &self.calculator.engine;
+		let amounts : DVector<f64> = DVector::from_vec((1..=self.basis.nrows()).map(|k| engine.get_result(ResultQuantity::AP, indexp, k)).collect::<Result<Vec<f64>,ChemAppError>>()?);
+		let y = self.basis.clone().svd(true, true).solve(&amounts, 1e-12).map_err(|e| ChemAppError::OtherError(e.to_string()))?;
+		let total = y.sum();
+		if !(total.abs() > 0.0) {
+			return Err(ChemAppError::OtherError(format!("Phase {} cannot be expressed in the basis", indexp)));
+		}
+		return Ok([y[1]/total, y[2]/total]);
+	}
+
+	/// Subdivide a triangle while its corners differ and the finest level is not reached; the edges of the finest triangles crossing a boundary are collected.
+	fn refine(&mut self, corners: [Node;3], step: usize, edges: &mut HashSet<(Node,Node)>){
+		let labels : Vec<Option<Assemblage>> = corners.iter().map(|c| self.sample(*c).map(|s| s.assemblage)).collect();
+		if labels[0] == labels[1] && labels[1] == labels[2] {
+			return;
+		}
+		if step > 1 {
+			let middle = |a: Node, b: Node|->Node {((a.0 + b.0)/2, (a.1 + b.1)/2)};
+			let [c0, c1, c2] = corners;
+			let (m01, m12, m20) = (middle(c0, c1), middle(c1, c2), middle(c2, c0));
+			let half = step/2;
+			self.refine([c0, m01, m20], half, edges);
+			self.refine([m01, c1, m12], half, edges);
+			self.refine([m20, m12, c2], half, edges);
+			self.refine([m01, m12, m20], half, edges);
+			return;
+		}
+		for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+			if labels[i].is_some() && labels[j].is_some() && labels[i] != labels[j] {
+				let (a, b) = if corners[i] <= corners[j] {(corners[i], corners[j])} else {(corners[j], corners[i])};
+				edges.insert((a, b));
+			}
+		}
+	}
+
+	/// Locate the boundary on an edge with a composition-target calculation for a phase appearing along it; returns the assemblages on both sides and the boundary composition.
+	fn boundary(&mut self, a: Node, b: Node)->Result<Option<(Assemblage,Assemblage,[f64;2])>,ChemAppError>{
+		let (la, lb) = match (self.sample(a), self.sample(b)) {
+			(Some(sa), Some(sb)) => (sa.assemblage, sb.assemblage),
+			_ => {return Ok(None);}
+		};
+		// search from the side where the phase is absent
+		let (from, to, phase) = match lb.phases().iter().find(|p| !la.contains(p)) {
+			Some(phase) => (a, b, phase.clone()),
+			None => match la.phases().iter().find(|p| !lb.contains(p)) {
+				Some(phase) => (b, a, phase.clone()),
+				None => {return Ok(None);}
+			},
+		};
+		let options = TargetXOptions {
+			scan_steps : 1,
+			tolerance : self.options.x_tolerance,
+			amount_tolerance : self.options.amount_tolerance,
+			..TargetXOptions::default()
+		};
+		let indexp = self.calculator.phase_index(&phase)?;
+		let result = self.calculator.calculate_target_x_from_left_with(&self.composition(from), &self.composition(to), self.temp, indexp, &options)?;
+		let beyond = Assemblage::current(self.calculator, self.options.amount_tolerance)?;
+		let origin = if from == a {la} else {lb};
+		let x = &result.inputs.composition;
+		let total = x.sum();
+		return Ok(Some((origin, beyond, [x[1]/total, x[2]/total])));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::{MockBackend, MockResults};
+	use crate::backend::mock::{cu_ni_fe_with, calculator};
+
+	/// Cu-Ni-Fe at constant temperature: FCC_A1 up to 30 % Fe, BCC_A2 from 60 % Fe and both in between, the two phases sharing the Cu/Ni ratio of the system;
+	/// with `failing` the calculations with more than 90 % Cu fail.
+	fn section_system(failing: bool)->Calculator<MockBackend> {
+		let backend = cu_ni_fe_with(move |input| {
+			let amounts = input.incoming_amounts(3);
+			let total : f64 = amounts.iter().sum();
+			if failing && amounts[0]/total > 0.9 {
+				return Err(704);
+			}
+			let fe = amounts[2]/total;
+			let cu = if amounts[0] + amounts[1] > 0.0 {amounts[0]/(amounts[0] + amounts[1])} else {0.5};
+			// fraction of BCC_A2 and the Fe contents of both phases
+			let (bcc, fe_fcc, fe_bcc) = if fe <= 0.3 {(0.0, fe, fe)} else if fe >= 0.6 {(1.0, fe, fe)} else {((fe - 0.3)/0.3, 0.3, 0.6)};
+			let mut results = MockResults::new();
+			for (indexp, fraction, fe_phase) in [(2, 1.0 - bcc, fe_fcc), (3, bcc, fe_bcc)] {
+				let amount = fraction*total;
+				results.insert(("A".to_owned(), indexp, 0), amount);
+				results.insert(("AP".to_owned(), indexp, 1), amount*(1.0 - fe_phase)*cu);
+				results.insert(("AP".to_owned(), indexp, 2), amount*(1.0 - fe_phase)*(1.0 - cu));
+				results.insert(("AP".to_owned(), indexp, 3), amount*fe_phase);
+			}
+			return Ok(results);
+		});
+		return Calculator::from_backend(backend).unwrap();
+	}
+
+	fn assemblage(phases: &[&str])->Assemblage {
+		return Assemblage(phases.iter().map(|p| p.to_string()).collect());
+	}
+
+	fn options()->IsothermalSectionOptions {
+		return IsothermalSectionOptions {divisions: 5, max_depth: 2, ..IsothermalSectionOptions::default()};
+	}
+
+	#[test]
+	fn section_finds_boundaries_and_tie_lines(){
+		let section = IsothermalSection::calculate(&section_system(false), 1000.0, &options()).unwrap();
+		assert_eq!(section.basis, ["Cu".to_string(), "Ni".to_string(), "Fe".to_string()]);
+		assert_eq!(section.failures, 0);
+		let (fcc, both, bcc) = (assemblage(&["FCC_A1"]), assemblage(&["FCC_A1", "BCC_A2"]), assemblage(&["BCC_A2"]));
+		let mut labels : Vec<Assemblage> = section.regions.iter().map(|r| r.assemblage.clone()).collect();
+		labels.sort();
+		assert_eq!(labels, vec![bcc.clone(), fcc.clone(), both.clone()]);
+		for (between, fe) in [((fcc.clone(), both.clone()), 0.3), ((bcc.clone(), both.clone()), 0.6)] {
+			let lines : Vec<&Boundary> = section.boundaries.iter().filter(|b| b.between == between).collect();
+			assert!(!lines.is_empty(), "no boundary {:?}", between);
+			assert!(lines.iter().all(|b| b.points.iter().all(|p| (p[1] - fe).abs() < 1e-3)), "{:?}", lines);
+		}
+		assert!(section.boundaries.iter().all(|b| b.between.0 != fcc || b.between.1 != bcc));
+		// the tie-lines join the phase compositions at 30 and 60 % Fe with the same Cu/Ni ratio
+		assert!(!section.tie_lines.is_empty());
+		for line in section.tie_lines.iter() {
+			assert_eq!(line.assemblage, both);
+			let [fcc_end, bcc_end] = line.ends;
+			assert!((fcc_end[1] - 0.3).abs() < 1e-9 && (bcc_end[1] - 0.6).abs() < 1e-9, "{:?}", line);
+			assert!((fcc_end[0]/0.7 - bcc_end[0]/0.4).abs() < 1e-9, "{:?}", line);
+		}
+		assert!(section.tie_triangles.is_empty());
+	}
+
+	#[test]
+	fn section_counts_failed_points(){
+		let section = IsothermalSection::calculate(&section_system(true), 1000.0, &options()).unwrap();
+		assert!(section.failures > 0);
+		assert!(section.regions.iter().any(|r| r.assemblage == assemblage(&["FCC_A1"])));
+		assert!(IsothermalSection::calculate(&calculator(), 1000.0, &options()).is_err());
+		assert!(IsothermalSection::calculate(&section_system(false), -1.0, &options()).is_err());
+	}
+
+}
@@ -2,12 +2,14 @@
 //! MQM bond representation (not valid for all phases)
 
 use crate::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 use crate::snapshot::BondSnapshot;
 use crate::entities::species::Species;
 
 /// A structure representing a bond in an MQM model.
-pub struct Bond<'a> {
-	calculator : &'a Calculator,
+pub struct Bond<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	pub(crate) indexp   : usize,
 	pub(crate) indexs1  : usize,
 	pub(crate) indexs2  : usize,
@@ -15,10 +17,10 @@ pub struct Bond<'a> {
 	pub(crate) indexs4  : usize,
 }
 
-impl<'a> Bond<'a> {
+impl<'a, B: ChemAppBackend> Bond<'a, B> {
 	
 	/// create a new instance
-	pub fn new(calculator: &'a Calculator, indexp: usize, indexs1: usize, indexs2: usize, indexs3: usize, indexs4: usize)->Self {
+	pub fn new(calculator: &'a Calculator<B>, indexp: usize, indexs1: usize, indexs2: usize, indexs3: usize, indexs4: usize)->Self {
 		return Self {
 			calculator,
 			indexp,
@@ -40,22 +42,22 @@ impl<'a> Bond<'a> {
 	}
 	
 	/// first cation
-	pub fn species1(&self)->Species<'_> {
+	pub fn species1(&self)->Species<'_, B> {
 		return Species::new(self.calculator, self.indexp, 1, self.indexs1);
 	}
 	
 	/// second cation
-	pub fn species2(&self)->Species<'_> {
+	pub fn species2(&self)->Species<'_, B> {
 		return Species::new(self.calculator, self.indexp, 1, self.indexs2);
 	}
 	
 	/// first anion
-	pub fn species3(&self)->Species<'_> {
+	pub fn species3(&self)->Species<'_, B> {
 		return Species::new(self.calculator, self.indexp, 2, self.indexs3);
 	}
 	
 	/// second anion
-	pub fn species4(&self)->Species<'_> {
+	pub fn species4(&self)->Species<'_, B> {
 		return Species::new(self.calculator, self.indexp, 2, self.indexs4);
 	}
 	
@@ -4,6 +4,9 @@ use std::fmt;
 use nalgebra::{DVector};
 
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
+use crate::options::ResultQuantity;
 use crate::entities::phase::Phase;
 use crate::snapshot::SystemComponentSnapshot;
 
@@ -11,18 +14,18 @@ use crate::snapshot::SystemComponentSnapshot;
 /**********************************************************************************************************************/
 
 /// System component representation
-pub struct SystemComponent<'a> {
-	calculator : &'a Calculator,
+pub struct SystemComponent<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	pub(crate) index : usize,
 }
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> SystemComponent<'a> {
+impl<'a, B: ChemAppBackend> SystemComponent<'a, B> {
 	
 	/// Instantiation
-	pub fn new(calculator: &'a Calculator, index: usize)->Self {
+	pub fn new(calculator: &'a Calculator<B>, index: usize)->Self {
 		return Self {
 			calculator,
 			index,
@@ -58,39 +61,39 @@ impl<'a> SystemComponent<'a> {
 	
 	/// Input amount
 	pub fn ia(&self)->f64 {
-		return self.calculator.engine.tqgetr("IA", 0, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::IA, 0, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// Amount
 	pub fn a(&self)->f64 {
-		return self.calculator.engine.tqgetr("A", 0, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::A, 0, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// Activity
 	pub fn ac(&self)->f64 {
-		return self.calculator.engine.tqgetr("AC", 0, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::AC, 0, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// Chemical potential
 	pub fn mu(&self)->f64 {
-		return self.calculator.engine.tqgetr("MU", 0, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::MU, 0, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// Molar/weight fraction in the system
 	pub fn x(&self)->f64 {
-		return self.calculator.engine.tqgetr("X", 0, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::X, 0, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// Molar/weight fraction in a phase
 	pub fn xp(&self, phase: &Phase)->f64 {
 		// TODO check calculator instance is the same.
-		return self.calculator.engine.tqgetr("XP", phase.index, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::XP, phase.index, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// Amount in a phase
 	pub fn ap(&self, phase: &Phase)->f64 {
 		// TODO check calculator instance is the same.
-		return self.calculator.engine.tqgetr("AP", phase.index, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::AP, phase.index, self.index).unwrap_or(f64::NAN);
 	}
 	
 	pub fn print_header(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
@@ -108,7 +111,7 @@ impl<'a> SystemComponent<'a> {
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> fmt::Debug for SystemComponent<'a> {
+impl<'a, B: ChemAppBackend> fmt::Debug for SystemComponent<'a, B> {
 	
 	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
 		if self.index == 1 {
@@ -3,6 +3,9 @@
 use nalgebra::{DVector};
 
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
+use crate::options::ResultQuantity;
 use crate::snapshot::ConstituentSnapshot;
  
 /**********************************************************************************************************************/
@@ -10,8 +13,8 @@ use crate::snapshot::ConstituentSnapshot;
 
 /// A high-level representation of a phase constituent.
 #[derive(Debug)]
-pub struct Constituent<'a> {
-	calculator : &'a Calculator,
+pub struct Constituent<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	pub(crate) indexp : usize,
 	pub(crate) index  : usize,
 }
@@ -19,10 +22,10 @@ pub struct Constituent<'a> {
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> Constituent<'a> {
+impl<'a, B: ChemAppBackend> Constituent<'a, B> {
 	
 	/// Make a new instance
-	pub fn new(calculator: &'a Calculator, indexp: usize, index: usize)->Self {
+	pub fn new(calculator: &'a Calculator<B>, indexp: usize, index: usize)->Self {
 		return Self {
 			calculator,
 			indexp,
@@ -74,72 +77,72 @@ impl<'a> Constituent<'a> {
 	
 	/// input amount
 	pub fn ia(&self)->f64 {
-		return self.calculator.engine.tqgetr("IA", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::IA, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// amount
 	pub fn a(&self)->f64 {
-		return self.calculator.engine.tqgetr("A", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::A, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// activity
 	pub fn ac(&self)->f64 {
-		return self.calculator.engine.tqgetr("AC", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::AC, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// chemical potential
 	pub fn mu(&self)->f64 {
-		return self.calculator.engine.tqgetr("MU", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::MU, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// enthalpy
 	pub fn h(&self)->f64 {
-		return self.calculator.engine.tqgetr("H", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::H, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// entropy
 	pub fn s(&self)->f64 {
-		return self.calculator.engine.tqgetr("S", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::S, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// gibbs energy
 	pub fn g(&self)->f64 {
-		return self.calculator.engine.tqgetr("G", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::G, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// heat capacity
 	pub fn cp(&self)->f64 {
-		return self.calculator.engine.tqgetr("CP", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::CP, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// volume
 	pub fn v(&self)->f64 {
-		return self.calculator.engine.tqgetr("V", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::V, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// enthalpy per amount unit
 	pub fn hm(&self)->f64 {
-		return self.calculator.engine.tqgetr("HM", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::HM, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// entropy per amount unit
 	pub fn sm(&self)->f64 {
-		return self.calculator.engine.tqgetr("SM", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::SM, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// gibbs energy per amount unit
 	pub fn gm(&self)->f64 {
-		return self.calculator.engine.tqgetr("GM", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::GM, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// heat capacity per amount unit
 	pub fn cpm(&self)->f64 {
-		return self.calculator.engine.tqgetr("CPM", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::CPM, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 	/// volume per amount unit
 	pub fn vm(&self)->f64 {
-		return self.calculator.engine.tqgetr("VM", self.indexp, self.index).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::VM, self.indexp, self.index).unwrap_or(f64::NAN);
 	}
 	
 }
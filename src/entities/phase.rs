@@ -4,6 +4,9 @@ use std::fmt;
 use nalgebra::{DVector};
 
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
+use crate::options::ResultQuantity;
 use crate::snapshot::PhaseSnapshot;
 use crate::iterator::ConstituentIterator;
 use crate::iterator::SpeciesIterator;
@@ -14,18 +17,18 @@ use crate::iterator::BondIterator;
 
 /// Phase representation
 #[derive(Debug)]
-pub struct Phase<'a> {
-	calculator: &'a Calculator,
+pub struct Phase<'a, B: ChemAppBackend = Engine> {
+	calculator: &'a Calculator<B>,
 	pub(crate) index : usize,
 }
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> Phase<'a> {
+impl<'a, B: ChemAppBackend> Phase<'a, B> {
 	
 	/// Make a new instance
-	pub fn new(calculator: &'a Calculator, index: usize)->Self {
+	pub fn new(calculator: &'a Calculator<B>, index: usize)->Self {
 		return Self {
 			calculator,
 			index,
@@ -38,17 +41,17 @@ impl<'a> Phase<'a> {
 	}
 	
 	/// Iterate over species in the phase
-	pub fn species(&self)->SpeciesIterator<'_>{
+	pub fn species(&self)->SpeciesIterator<'_, B>{
 		return SpeciesIterator::new(self.calculator, self.index);
 	}
 	
 	/// Iterate over bonds (if any)
-	pub fn bonds(&self)->BondIterator<'_>{
+	pub fn bonds(&self)->BondIterator<'_, B>{
 		return BondIterator::new(self.calculator, self.index);
 	}
 	
 	/// Iterate over phase constituents in the phase
-	pub fn constituents(&self)->ConstituentIterator<'_>{
+	pub fn constituents(&self)->ConstituentIterator<'_, B>{
 		return ConstituentIterator::new(self.calculator, self.index);
 	}
 	
@@ -79,67 +82,67 @@ impl<'a> Phase<'a> {
 	
 	/// phase amount
 	pub fn a(&self)->f64 {
-		return self.calculator.engine.tqgetr("A", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::A, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// phase activity
 	pub fn ac(&self)->f64 {
-		return self.calculator.engine.tqgetr("AC", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::AC, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// chemical potential
 	pub fn mu(&self)->f64 {
-		return self.calculator.engine.tqgetr("MU", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::MU, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// enthalpy
 	pub fn h(&self)->f64 {
-		return self.calculator.engine.tqgetr("H", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::H, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// entropy
 	pub fn s(&self)->f64 {
-		return self.calculator.engine.tqgetr("S", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::S, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// gibbs energy
 	pub fn g(&self)->f64 {
-		return self.calculator.engine.tqgetr("G", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::G, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// heat capacity
 	pub fn cp(&self)->f64 {
-		return self.calculator.engine.tqgetr("CP", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::CP, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// phase volume
 	pub fn v(&self)->f64 {
-		return self.calculator.engine.tqgetr("V", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::V, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// enthalpy per amount unit
 	pub fn hm(&self)->f64 {
-		return self.calculator.engine.tqgetr("HM", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::HM, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// entropy per amount unit
 	pub fn sm(&self)->f64 {
-		return self.calculator.engine.tqgetr("SM", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::SM, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// gibbs energy per amount unit
 	pub fn gm(&self)->f64 {
-		return self.calculator.engine.tqgetr("GM", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::GM, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// heat capacity per amount unit
 	pub fn cpm(&self)->f64 {
-		return self.calculator.engine.tqgetr("CPM", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::CPM, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// phase volume per amount unit
 	pub fn vm(&self)->f64 {
-		return self.calculator.engine.tqgetr("VM", self.index, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::VM, self.index, 0).unwrap_or(f64::NAN);
 	}
 	
 	pub fn print_header(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
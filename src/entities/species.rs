@@ -3,14 +3,16 @@
 use nalgebra::{DVector};
 
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 use crate::snapshot::SpeciesSnapshot;
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
 /// A high-level entity representing a sublattice species.
-pub struct Species<'a> {
-	calculator: &'a Calculator,
+pub struct Species<'a, B: ChemAppBackend = Engine> {
+	calculator: &'a Calculator<B>,
 	pub(crate) indexp : usize,
 	pub(crate) indexl : usize,
 	pub(crate) indexs : usize,
@@ -19,10 +21,10 @@ pub struct Species<'a> {
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> Species<'a> {
+impl<'a, B: ChemAppBackend> Species<'a, B> {
 	
 	/// make a new instance
-	pub fn new(calculator: &'a Calculator, indexp: usize, indexl: usize, indexs: usize)->Self {
+	pub fn new(calculator: &'a Calculator<B>, indexp: usize, indexl: usize, indexs: usize)->Self {
 		return Self {
 			calculator,
 			indexp,
@@ -1,19 +1,22 @@
 // chemapp_rs::entities::stream.rs
 
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 use crate::error::ChemAppError;
+use crate::options::StreamProperty;
 
-pub struct Stream<'a> {
-	calculator : &'a Calculator,
+pub struct Stream<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	name   : String,
 	temp   : f64,
 	pres   : f64,
 }
 
-impl<'a> Stream<'a> {
+impl<'a, B: ChemAppBackend> Stream<'a, B> {
 	
 	/// create a new stream
-	pub fn new(calculator: &'a Calculator, name: &str, temp: f64, pres : f64)->Result<Self,ChemAppError>{
+	pub fn new(calculator: &'a Calculator<B>, name: &str, temp: f64, pres : f64)->Result<Self,ChemAppError>{
 		/// TODO - what about units?
 		calculator.engine.tqsttp(name, (temp, pres))?;
 		return Ok(Self {
@@ -38,32 +41,32 @@ impl<'a> Stream<'a> {
 	
 	/// heat capacity [current energy unit]/K
 	pub fn cp(&self)->f64 {
-		return self.calculator.engine.tqstxp(&self.name, "CP").unwrap_or(f64::NAN);
+		return self.calculator.engine.stream_property(&self.name, StreamProperty::CP).unwrap_or(f64::NAN);
 	}
 	
 	/// enthalpy [current energy unit]
 	pub fn h(&self)->f64 {
-		return self.calculator.engine.tqstxp(&self.name, "H").unwrap_or(f64::NAN);
+		return self.calculator.engine.stream_property(&self.name, StreamProperty::H).unwrap_or(f64::NAN);
 	}
 	
 	/// entropy [current energy unit]/K
 	pub fn s(&self)->f64 {
-		return self.calculator.engine.tqstxp(&self.name, "S").unwrap_or(f64::NAN);
+		return self.calculator.engine.stream_property(&self.name, StreamProperty::S).unwrap_or(f64::NAN);
 	}
 	
 	/// gibbs energy [current energy unit]
 	pub fn g(&self)->f64 {
-		return self.calculator.engine.tqstxp(&self.name, "G").unwrap_or(f64::NAN);
+		return self.calculator.engine.stream_property(&self.name, StreamProperty::G).unwrap_or(f64::NAN);
 	}
 	
 	/// volume [current volume unit]
 	pub fn v(&self)->f64 {
-		return self.calculator.engine.tqstxp(&self.name, "V").unwrap_or(f64::NAN);
+		return self.calculator.engine.stream_property(&self.name, StreamProperty::V).unwrap_or(f64::NAN);
 	}
 	
 }
 
-impl<'a> Stream<'a> {
+impl<'a, B: ChemAppBackend> Stream<'a, B> {
 	
 	fn drop(&mut self){
 		self.calculator.engine.tqstrm(&self.name);
@@ -4,22 +4,25 @@ use std::fmt;
 use nalgebra::{DVector};
 
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
+use crate::options::ResultQuantity;
 use crate::snapshot::SystemSnapshot;
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 /// Accessor structure for retrieving global system properties
-pub struct System<'a> {
-	calculator : &'a Calculator,
+pub struct System<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 }
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> System<'a> {
+impl<'a, B: ChemAppBackend> System<'a, B> {
 	
 	/// create a new instance
-	pub fn new(calculator: &'a Calculator)-> Self {
+	pub fn new(calculator: &'a Calculator<B>)-> Self {
 		return Self {calculator};
 	}
 	
@@ -30,22 +33,22 @@ impl<'a> System<'a> {
 	
 	/// system temperature
 	pub fn t(&self)->f64 {
-		return self.calculator.engine.tqgetr("T", 0, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::T, 0, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// system pressure
 	pub fn p(&self)->f64 {
-		return self.calculator.engine.tqgetr("P", 0, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::P, 0, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// total volume
 	pub fn vt(&self)->f64 {
-		return self.calculator.engine.tqgetr("VT", 0, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::VT, 0, 0).unwrap_or(f64::NAN);
 	}
 	
 	/// total amount
 	pub fn a(&self)->f64 {
-		return self.calculator.engine.tqgetr("A", 0, 0).unwrap_or(f64::NAN);
+		return self.calculator.engine.get_result(ResultQuantity::A, 0, 0).unwrap_or(f64::NAN);
 	}
 	
 	pub fn print_header(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
@@ -69,7 +72,7 @@ impl<'a> System<'a> {
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> fmt::Debug for System<'a> {
+impl<'a, B: ChemAppBackend> fmt::Debug for System<'a, B> {
 	
 	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
 		self.print_header(f)?;
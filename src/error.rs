@@ -1,4 +1,5 @@
 //! This submodule wraps the native error codes from Fortran (integer values) into a Rust enum to facilitate working with their descriptions + support of non-ChemApp error (library loading, etc) added.
+//! Errors returned by `Engine` carry the failed routine, its arguments and the ChemApp message (`NativeCallError`); `ChemAppError::category` groups the codes by their ranges.
 
 use std::fmt;
 use lazy_static::{lazy_static};
@@ -11,16 +12,120 @@ use bincode::{Encode};
 /*****************************************************************************************************************************************************************************************************/
 /*****************************************************************************************************************************************************************************************************/
 
+/// Groups of ChemApp error codes, following the ranges of `error_descriptions`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Serialize,Deserialize,Encode)]
+pub enum ErrorCategory {
+	/// 1xx, initialization and datafile errors (also the file errors 9xx of older versions)
+	Initialization,
+	/// 2xx, errors in entered character options
+	Option,
+	/// 3xx, errors in entered names of components, phases or constituents
+	Name,
+	/// 4xx, errors in entered index numbers
+	Index,
+	/// 5xx, errors in setting conditions
+	Condition,
+	/// 6xx, errors when getting results
+	Result,
+	/// 701-706, equilibrium composition not obtained
+	Convergence,
+	/// 707-713, target calculation aborted
+	Target,
+	/// 1000 and above, errors in custom subroutines (`tqgdat`, `tqgpar`, `tqcdat`, ...)
+	CustomRoutine,
+	/// a code outside the documented ranges
+	Unknown,
+}
+
+impl ErrorCategory {
+	
+	/// The category of a native error code.
+	pub fn from_code(code: usize)->ErrorCategory {
+		match code {
+			100..=199 => ErrorCategory::Initialization,
+			200..=299 => ErrorCategory::Option,
+			300..=399 => ErrorCategory::Name,
+			400..=499 => ErrorCategory::Index,
+			500..=599 => ErrorCategory::Condition,
+			600..=699 => ErrorCategory::Result,
+			700..=706 => ErrorCategory::Convergence,
+			707..=799 => ErrorCategory::Target,
+			900..=999 => ErrorCategory::Initialization,
+			1000..    => ErrorCategory::CustomRoutine,
+			_         => ErrorCategory::Unknown,
+		}
+	}
+	
+}
+
+/// A failed ChemApp call: the error code with the routine, its arguments and the message ChemApp reported through `tqerr`.
+#[derive(Debug,Clone,Serialize,Deserialize,Encode)]
+pub struct NativeCallError {
+	pub code: usize,
+	/// the routine, e.g. `tqsetc`
+	pub function: String,
+	/// the arguments as `name = value`
+	pub arguments: Vec<String>,
+	/// the text returned by `tqerr` (empty if not available)
+	pub message: String,
+}
+
+impl NativeCallError {
+	
+	pub fn new(code: usize, function: &str, arguments: Vec<String>, message: String)->NativeCallError {
+		return NativeCallError {
+			code,
+			function: function.to_string(),
+			arguments,
+			message,
+		};
+	}
+	
+	/// the category of the error code
+	pub fn category(&self)->ErrorCategory {
+		return ErrorCategory::from_code(self.code);
+	}
+	
+}
+
 /// Custom error struct
 #[derive(Debug,Clone,Serialize,Deserialize,Encode)]
 pub enum ChemAppError{
+	/// a bare native error code, kept for compatibility; the crate itself returns every native code as `NativeCall` (see `ChemAppError::native`)
 	NativeError(usize),
+	/// a native error code with the context of the failed call
+	NativeCall(Box<NativeCallError>),
 	OtherError(String),
 	CustomError(String),
+	/// the worker process hosting the library died or the communication with it failed
+	WorkerError(String),
+	/// a call did not complete before its deadline and was aborted
+	Timeout(String),
+	/// ChemApp reported more entries than the output buffer passed to it can hold; detected after the call, i.e. a diagnostic of an overrun that has already happened rather than a guard against it
+	BufferOverflow(String),
 }
 
 impl ChemAppError {
 	
+	/// A native error code without the context of a call (e.g. a unit number rejected before reaching ChemApp).
+	pub fn native(code: usize)->ChemAppError {
+		return ChemAppError::NativeCall(Box::new(NativeCallError::new(code, "", Vec::new(), String::new())));
+	}
+	
+	/// the native error code, if any
+	pub fn code(&self)->Option<usize> {
+		match self {
+			Self::NativeError(code) => {return Some(*code);}
+			Self::NativeCall(error) => {return Some(error.code);}
+			_ => {return None;}
+		}
+	}
+	
+	/// the category of a native error
+	pub fn category(&self)->Option<ErrorCategory> {
+		return self.code().map(ErrorCategory::from_code);
+	}
+	
 	pub fn description(&self)->String{
 		match self {
 			Self::NativeError(id) => {
@@ -29,8 +134,22 @@ impl ChemAppError {
 				None => {return format!("Unrecognized ChemApp error {}", id);}
 			}
 			}
+			Self::NativeCall(error) => {
+				let desc = error_descriptions.get(&error.code).copied().unwrap_or("unrecognized error");
+				let mut text = match error.function.is_empty() {
+					true  => format!("ChemApp error {} ({:?}), {}", error.code, error.category(), desc),
+					false => format!("ChemApp error {} ({:?}) in {}({}), {}", error.code, error.category(), error.function, error.arguments.join(", "), desc),
+				};
+				if !error.message.is_empty() {
+					text = format!("{}: {}", text, error.message);
+				}
+				return text;
+			}
 			Self::OtherError(desc) => {return format!("{}", &desc);}
 			Self::CustomError(desc)=> {return format!("{}", &desc);}
+			Self::WorkerError(desc)=> {return format!("ChemApp worker error, {}", &desc);}
+			Self::Timeout(desc)    => {return format!("Timeout, {}", &desc);}
+			Self::BufferOverflow(desc) => {return format!("Buffer overflow, {}", &desc);}
 		}
 	}
 }
@@ -183,4 +302,43 @@ lazy_static!{
 }
 
 /*****************************************************************************************************************************************************************************************************/
-/*****************************************************************************************************************************************************************************************************/
\ No newline at end of file
+/*****************************************************************************************************************************************************************************************************/
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn categories_follow_the_code_ranges(){
+		let cases = [
+			(101, ErrorCategory::Initialization), (199, ErrorCategory::Initialization),
+			(201, ErrorCategory::Option), (301, ErrorCategory::Name), (401, ErrorCategory::Index),
+			(501, ErrorCategory::Condition), (601, ErrorCategory::Result),
+			(700, ErrorCategory::Convergence), (706, ErrorCategory::Convergence),
+			(707, ErrorCategory::Target), (713, ErrorCategory::Target),
+			(901, ErrorCategory::Initialization), (999, ErrorCategory::Initialization),
+			(1000, ErrorCategory::CustomRoutine), (1501, ErrorCategory::CustomRoutine),
+			(0, ErrorCategory::Unknown), (99, ErrorCategory::Unknown), (800, ErrorCategory::Unknown), (899, ErrorCategory::Unknown),
+		];
+		for (code, category) in cases {
+			assert_eq!(ErrorCategory::from_code(code), category, "code {}", code);
+		}
+	}
+
+	#[test]
+	fn native_errors_carry_code_and_category(){
+		let call = ChemAppError::NativeCall(Box::new(NativeCallError {
+			code      : 402,
+			function  : "tqgetr".to_string(),
+			arguments : vec!["indexp = 0".to_string()],
+			message   : String::new(),
+		}));
+		assert_eq!(call.code(), Some(402));
+		assert_eq!(call.category(), Some(ErrorCategory::Index));
+		assert_eq!(ChemAppError::native(104).category(), Some(ErrorCategory::Initialization));
+		assert_eq!(ChemAppError::native(104).to_string(), "ChemApp error 104 (Initialization), A thermodynamic data-file must be read first");
+		assert_eq!(ChemAppError::Timeout("tqce".to_string()).code(), None);
+		assert_eq!(ChemAppError::OtherError("no code".to_string()).category(), None);
+	}
+
+}
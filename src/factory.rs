@@ -0,0 +1,130 @@
+// chemapp_rs::factory.rs
+
+//! Independent `Engine` instances within one process. A ChemApp library keeps its state in Fortran global variables, so two engines loaded from the same file share the same state;
+//! `EngineFactory` copies the library into a managed temporary directory under a unique name for every new engine (this works both on Windows and on Linux), loads the copy and initializes it with the same datafile.
+//! A copy is removed once its engine is dropped, the temporary directory - once the factory and all of its engines are dropped.
+
+use std::fs;
+use std::sync::{Arc};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::path::{Path, PathBuf};
+use tempfile::{TempDir};
+
+use crate::{Engine, Calculator};
+use crate::abi::{AbiProfile};
+use crate::error::{ChemAppError};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A copy of a ChemApp library owned by a single `Engine`, the file is removed on drop (after the library has been unloaded).
+#[derive(Debug)]
+pub(crate) struct LibraryCopy {
+	path: PathBuf,
+	// keeps the directory alive while the copy exists
+	#[allow(dead_code)]
+	directory: Arc<TempDir>,
+}
+
+impl Drop for LibraryCopy {
+	
+	fn drop(&mut self){
+		let _ = fs::remove_file(&self.path);
+	}
+	
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Creates independent engines from copies of a single ChemApp library, all of them initialized with the same datafile.
+#[derive(Debug)]
+pub struct EngineFactory {
+	library: PathBuf,
+	datafile: String,
+	abi: AbiProfile,
+	directory: Arc<TempDir>,
+	counter: AtomicUsize,
+}
+
+impl EngineFactory {
+	
+	/// Create a factory for a ChemApp library (e.g. `libLChemAppS.so` or `ca_vc_e_x64.dll`) and a datafile; the library itself is not loaded.
+	pub fn new(library: &str, datafile: &str)->Result<EngineFactory,ChemAppError>{
+		return EngineFactory::with_abi(library, datafile, AbiProfile::platform());
+	}
+	
+	/// Same as `new` for a library built with a non-default Fortran binary interface, every engine is created with `Engine::with_abi`.
+	pub fn with_abi(library: &str, datafile: &str, abi: AbiProfile)->Result<EngineFactory,ChemAppError>{
+		let library = fs::canonicalize(library).map_err(|e| ChemAppError::OtherError(format!("Cannot locate {}: {}", library, e)))?;
+		let directory = tempfile::Builder::new().prefix("chemapp_rs").tempdir().map_err(|e| ChemAppError::OtherError(format!("Cannot create a temporary directory: {}", e)))?;
+		return Ok(EngineFactory {
+			library,
+			datafile: datafile.to_string(),
+			abi,
+			directory: Arc::new(directory),
+			counter: AtomicUsize::new(0),
+		});
+	}
+	
+	/// the original library
+	pub fn library(&self)->&Path {
+		return &self.library;
+	}
+	
+	/// the datafile every engine is initialized with
+	pub fn datafile(&self)->&str {
+		return &self.datafile;
+	}
+	
+	/// the binary interface of the engines
+	pub fn abi(&self)->AbiProfile {
+		return self.abi;
+	}
+	
+	/// the temporary directory holding the library copies
+	pub fn directory(&self)->&Path {
+		return self.directory.path();
+	}
+	
+	/// number of engines created so far
+	pub fn count(&self)->usize {
+		return self.counter.load(Ordering::SeqCst);
+	}
+	
+	/// Copy the library under a unique name, `<stem>_<number>.<extension>`.
+	fn copy_library(&self)->Result<LibraryCopy,ChemAppError>{
+		let number = self.counter.fetch_add(1, Ordering::SeqCst);
+		let stem = self.library.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or("chemapp".to_string());
+		let mut filename = format!("{}_{}", stem, number);
+		if let Some(extension) = self.library.extension() {
+			filename = format!("{}.{}", filename, extension.to_string_lossy());
+		}
+		let path = self.directory.path().join(filename);
+		fs::copy(&self.library, &path).map_err(|e| ChemAppError::OtherError(format!("Cannot copy {} to {}: {}", self.library.display(), path.display(), e)))?;
+		return Ok(LibraryCopy {
+			path,
+			directory: self.directory.clone(),
+		});
+	}
+	
+	/// Load a new independent engine and initialize it with the datafile.
+	pub fn engine(&self)->Result<Engine,ChemAppError>{
+		let copy = self.copy_library()?;
+		let mut engine = Engine::with_abi(&copy.path.to_string_lossy(), self.abi)?;
+		engine.copy = Some(copy);
+		Calculator::init_engine(&engine, &self.datafile)?;
+		return Ok(engine);
+	}
+	
+	/// A `Calculator` over a new independent engine.
+	pub fn calculator(&self)->Result<Calculator,ChemAppError>{
+		let mut calculator = Calculator::from_backend(self.engine()?)?;
+		calculator.file = self.datafile.clone();
+		return Ok(calculator);
+	}
+	
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
@@ -2,31 +2,58 @@
 
 use crate::entities::bond::Bond;
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-pub struct BondIterator<'a> {
-	calculator : &'a Calculator,
+pub struct BondIterator<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	pub(crate) indexp: usize,
+	quadruplets : Vec<(usize,usize,usize,usize)>,
+	current : usize,
 }
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> BondIterator<'a> {
+impl<'a, B: ChemAppBackend> BondIterator<'a, B> {
 	
-	pub fn new(calculator: &'a Calculator, indexp: usize)->Self {
-		todo!();
+	/// quadruplets `(A,B,X,Y)` with `A <= B` on the first (cationic) and `X <= Y` on the second (anionic) sublattice; empty for non-quasichemical phases
+	pub fn new(calculator: &'a Calculator<B>, indexp: usize)->Self {
+		let mut quadruplets : Vec<(usize,usize,usize,usize)> = Vec::new();
+		let model = calculator.engine.tqmodl(indexp).unwrap_or_default();
+		if (model == "SUBG" || model == "SUBQ") && calculator.engine.tqnosl(indexp).unwrap_or(0) == 2 {
+			let ncations = calculator.engine.tqnolc(indexp, 1).unwrap_or(0);
+			let nanions  = calculator.engine.tqnolc(indexp, 2).unwrap_or(0);
+			for a in 1..ncations+1 {
+				for b in a..ncations+1 {
+					for x in 1..nanions+1 {
+						for y in x..nanions+1 {
+							quadruplets.push((a, b, x, y));
+						}
+					}
+				}
+			}
+		}
+		return Self {
+			calculator,
+			indexp,
+			quadruplets,
+			current : 0,
+		};
 	}
 	
 }
 
-impl<'a> Iterator for BondIterator<'a> {
-	type Item = Bond<'a>;
+impl<'a, B: ChemAppBackend> Iterator for BondIterator<'a, B> {
+	type Item = Bond<'a, B>;
 	
 	fn next(&mut self)->Option<Self::Item> {
-		todo!();
+		let (a, b, x, y) = *self.quadruplets.get(self.current)?;
+		self.current += 1;
+		return Some(Bond::new(self.calculator, self.indexp, a, b, x, y));
 	}
 	
 }
@@ -2,20 +2,22 @@
 //! `ComponentIterator` trait facilitating iteration and property retrieval for system components.
 use std::iter::{Filter, Map};
 use crate::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 use crate::entities::component::SystemComponent;
 
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-pub struct SystemComponentIterator<'a> {
-	calculator : &'a Calculator,
+pub struct SystemComponentIterator<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	current : usize,
 	ncomponents : usize,
 }
 
-impl<'a> SystemComponentIterator<'a> {
+impl<'a, B: ChemAppBackend> SystemComponentIterator<'a, B> {
 	
-	pub fn new(calculator: &'a Calculator)->Self {
+	pub fn new(calculator: &'a Calculator<B>)->Self {
 		let ncomponents = calculator.engine.tqnosc().unwrap_or(0);
 		let current = 1;
 		return Self {
@@ -27,8 +29,8 @@ impl<'a> SystemComponentIterator<'a> {
 	
 }
 
-impl<'a> Iterator for SystemComponentIterator<'a> {
-	type Item = SystemComponent<'a>;
+impl<'a, B: ChemAppBackend> Iterator for SystemComponentIterator<'a, B> {
+	type Item = SystemComponent<'a, B>;
 	
 	fn next(&mut self)->Option<Self::Item>{
 		if self.current > self.ncomponents {
@@ -3,21 +3,23 @@
 use std::collections::{HashMap};
 use std::iter::{Filter, Map};
 use crate::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 use crate::entities::constituent::Constituent;
 
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-pub struct ConstituentIterator<'a> {
-	calculator    : &'a Calculator,
+pub struct ConstituentIterator<'a, B: ChemAppBackend = Engine> {
+	calculator    : &'a Calculator<B>,
 	current       : usize,
 	nconstituents : usize,
 	indexp        : usize,
 }
 
-impl<'a> ConstituentIterator<'a> {
+impl<'a, B: ChemAppBackend> ConstituentIterator<'a, B> {
 	
-	pub fn new(calculator: &'a Calculator, indexp: usize)->Self {
+	pub fn new(calculator: &'a Calculator<B>, indexp: usize)->Self {
 		let nconstituents = calculator.engine.tqnopc(indexp).unwrap_or(0);
 		let current = 1;
 		return Self {
@@ -30,8 +32,8 @@ impl<'a> ConstituentIterator<'a> {
 	
 }
 
-impl<'a> Iterator for ConstituentIterator<'a> {
-	type Item = Constituent<'a>;
+impl<'a, B: ChemAppBackend> Iterator for ConstituentIterator<'a, B> {
+	type Item = Constituent<'a, B>;
 	
 	fn next(&mut self)->Option<Self::Item> {
 		if self.current > self.nconstituents {
@@ -4,20 +4,22 @@ use std::iter::{Filter, Map, FlatMap};
 use std::ops::Range;
 use nalgebra::{DVector};
 use crate::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 use crate::entities::phase::Phase;
 
 /*******************************************************************************************************************************************************************************************************************************/
 /*******************************************************************************************************************************************************************************************************************************/
 
-pub struct PhaseIterator<'a> {
-	calculator : &'a Calculator,
+pub struct PhaseIterator<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	current : usize,
 	nphases : usize,
 }
 
-impl<'a> PhaseIterator<'a> {
+impl<'a, B: ChemAppBackend> PhaseIterator<'a, B> {
 	
-	pub fn new(calculator : &'a Calculator)->Self {
+	pub fn new(calculator : &'a Calculator<B>)->Self {
 		let nphases = calculator.engine.tqnop().unwrap_or(0);
 		let current = 1;
 		return Self {
@@ -29,8 +31,8 @@ impl<'a> PhaseIterator<'a> {
 	
 }
 
-impl<'a> Iterator for PhaseIterator<'a> {
-	type Item = Phase<'a>;
+impl<'a, B: ChemAppBackend> Iterator for PhaseIterator<'a, B> {
+	type Item = Phase<'a, B>;
 	
 	fn next(&mut self)->Option<Self::Item>{
 		if self.current > self.nphases {
@@ -2,33 +2,58 @@
 
 use crate::entities::species::Species;
 use crate::calculator::Calculator;
+use crate::Engine;
+use crate::backend::ChemAppBackend;
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-pub struct SpeciesIterator<'a> {
-	calculator : &'a Calculator,
+pub struct SpeciesIterator<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
 	pub(crate) indexp : usize,
+	nsublattices : usize,
+	indexl   : usize,
+	indexs   : usize,
+	nspecies : usize,
 }
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
-impl<'a> SpeciesIterator<'a> {
+impl<'a, B: ChemAppBackend> SpeciesIterator<'a, B> {
 	
-	pub fn new(calculator: &'a Calculator, indexp: usize)->Self {
-		todo!();
+	pub fn new(calculator: &'a Calculator<B>, indexp: usize)->Self {
+		let nsublattices = calculator.engine.tqnosl(indexp).unwrap_or(0);
+		let nspecies = if nsublattices > 0 {calculator.engine.tqnolc(indexp, 1).unwrap_or(0)} else {0};
+		return Self {
+			calculator,
+			indexp,
+			nsublattices,
+			indexl : 1,
+			indexs : 1,
+			nspecies,
+		};
 	}
+	
 }
 
-impl<'a> Iterator for SpeciesIterator<'a> {
-	type Item = Species<'a>;
+impl<'a, B: ChemAppBackend> Iterator for SpeciesIterator<'a, B> {
+	type Item = Species<'a, B>;
 	
 	fn next(&mut self)->Option<Self::Item> {
-		
-		todo!();
-		
+		while self.indexs > self.nspecies {
+			if self.indexl >= self.nsublattices {
+				return None;
+			}
+			self.indexl += 1;
+			self.indexs = 1;
+			self.nspecies = self.calculator.engine.tqnolc(self.indexp, self.indexl).unwrap_or(0);
+		}
+		let current = self.indexs;
+		self.indexs += 1;
+		return Some(Species::new(self.calculator, self.indexp, self.indexl, current));
 	}
+	
 }
 
 /**********************************************************************************************************************/
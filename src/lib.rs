@@ -18,24 +18,55 @@
 //! On the contrary, dynamic dispatch delays locating a DLL until a LoadLibrary function from Windows SDK is explicitly called inside the code. This allows more flexibility, for example, one can pass a dll name (even different versions) as a function parameter in the application; the user has flexibility to switch between different versions of the same DLL without recompilation (nevertheless, the DLL and EXE bitnesses have to match, operating systems do not support loading 32bit DLLs in 64bit applications and otherwise).
 //!   
 //! Dynamic dispatch also offers a way to parallelize a large number of similar calculations. When a shared library is loaded into different applications, the operating system usually keeps only a single copy of its code section for efficiency, since code sections are always read-only. However, for each application, the operating system keeps a separate copy of the library variables (everything that is mutable and changes during the calculations). On Linux, it is possible to load the same library into the same process several times distinctly; Windows officially does not support that, but the author discovered that if a DLL is manually copied with renaming, two idential DLLs CAN be loaded at the same time separately. This allows to split the computational load between different copies of ChemApp.
+//!   
+//! `EngineFactory` automates this: it copies the library into a managed temporary directory under unique names and loads every copy as an independent `Engine` initialized with the same datafile.
 
 extern crate libloading;
 
 
 use std::fmt;
 use libloading::{Library};
+use serde::{Serialize, Deserialize};
 
 pub use crate::error::ChemAppError;
 pub use crate::native::Engine;
-pub use crate::calculator::Calculator;
+pub use crate::abi::{AbiProfile, IntegerWidth, LengthPosition, CallingConvention};
+pub use crate::backend::{ChemAppBackend, MockBackend, RemoteEngine};
+pub use crate::calculator::{Calculator, TargetXOptions};
+pub use crate::result::{EquilibriumResult, CalculationKind, CalculationInputs};
+pub use crate::diagram::{Assemblage, PhaseDiagram, PhaseDiagramOptions, IsothermalSection, IsothermalSectionOptions, LiquidusProjection, LiquidusOptions, PredominanceDiagram, PredominanceOptions, PredominanceAxis, EllinghamDiagram, EllinghamOptions};
+pub use crate::problem::{EquilibriumProblem, TargetCondition, TargetVariation};
+pub use crate::step::{Step, StepVariable, Spacing, StepPoint, StepSeries};
+pub use crate::sweep::{GridSweep, Axis, Output, SweepTable};
+pub use crate::surrogate::{PropertyTable, Interpolation, Interpolated};
+pub use crate::reaction::{Reaction, ReactionSpecies, ReactionProperties};
+pub use crate::scheil::{CoolingMode, ScheilOptions, SolidificationPath, SolidificationStep};
+pub use crate::factory::EngineFactory;
+pub use crate::units::{UnitManager, FortranFile, FileKind};
+pub use crate::pool::CalculatorPool;
 pub use crate::iterator::{SystemComponentIterator, PhaseIterator, ConstituentIterator, SpeciesIterator, BondIterator};
+pub use crate::options::{Condition, ResultQuantity, TargetVariable, MapOption, StreamProperty, ConstituentProperty, Status};
 
 pub mod error;
 pub mod defs;
+pub mod abi;
 pub mod native;
+pub mod backend;
+pub mod options;
 pub mod entities;
 pub mod iterator;
 pub mod calculator;
+pub mod problem;
+pub mod result;
+pub mod diagram;
+pub mod scheil;
+pub mod step;
+pub mod sweep;
+pub mod surrogate;
+pub mod reaction;
+pub mod units;
+pub mod factory;
+pub mod pool;
 pub mod snapshot;
 pub mod parse;
 pub mod cache;
@@ -46,7 +77,7 @@ static DEFAULT_LIBNAME : &str = r"ca_vc_e_local.dll";
 /*****************************************************************************************************************************************************************************************************/
 
 /// An abstraction over system info returned by `tqused` and `tqsize` functions.
-#[derive(Clone)]
+#[derive(Clone,Serialize,Deserialize)]
 pub struct SystemDimensions {
 	pub nconstituents: i32,       // na
 	pub ncomponents: i32,         // nb
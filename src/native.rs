@@ -5,7 +5,11 @@
 
 extern crate libloading;
 
-use libloading::{Library, Symbol};
+use libloading::{Library};
+use std::mem;
+use std::fmt;
+use std::ptr;
+use std::ffi::{c_void};
 use std::str::{from_utf8};
 use std::cmp::{min};
 use std::ffi::{CString};
@@ -14,9 +18,22 @@ use function_name::{named};
 use crate::DEFAULT_LIBNAME;
 use crate::{SystemDimensions, TransparentHeader};
 use crate::defs::{FUNCSWIN32,FUNCSWIN64,FUNCSUNIX32,FUNCSUNIX64};
-use crate::error::{ChemAppError};
+use crate::error::{ChemAppError, NativeCallError};
+use crate::factory::{LibraryCopy};
+use crate::units::{UnitManager, FileKind};
+use crate::abi::{AbiProfile, IntegerWidth, LengthPosition, IntArray, FInt};
 
 const NAME_LENGTH_MAX : usize = 25;
+/// declared length of a `tqlpar` parameter description
+const PARAMETER_LENGTH : usize = 156;
+/// number of `tqgdat` values per Gibbs energy / heat capacity equation
+const EQUATION_VALUES_MAX : usize = 25;
+/// leading dimension of the `tqgpar` value array (values per excess expression)
+const EXPRESSION_VALUES_MAX : usize = 20;
+/// fixed number of `tqlpar` parameters the bindings used to allocate, kept as a floor
+const PARAMETERS_MIN : usize = 1999;
+/// fixed number of `tqgpar` expressions the bindings used to allocate, kept as a floor
+const EXPRESSIONS_MIN : usize = 28;
 
 /*********************************************************************************************************************************************************************************************************/
 /*********************************************************************************************************************************************************************************************************/
@@ -50,11 +67,88 @@ fn cu8array2string(cstring: &[u8])->String {
 	return from_utf8(&cstring[0..clen(&cstring)]).unwrap_or("<UNRECOGNIZED UTF8 SEQUENCE>").to_owned();
 }
 
-fn wrap_result<T>(result: T, errcode: usize)->Result<T, ChemAppError>{
-	match errcode {
-		0 => Ok(result),
-		_ => Err(ChemAppError::NativeError(errcode)),
+/*********************************************************************************************************************************************************************************************************/
+/*********************************************************************************************************************************************************************************************************/
+
+/// An address of an exported ChemApp routine resolved when the library is loaded (null if the library does not export it).
+#[derive(Clone,Copy)]
+pub(crate) struct RawFunction {
+	name: &'static str,
+	address: *const c_void,
+}
+
+// the addresses point into the code section of the library owned by the same `Engine`, they stay valid until it is dropped
+unsafe impl Send for RawFunction {}
+unsafe impl Sync for RawFunction {}
+
+impl RawFunction {
+	
+	fn resolve(library: &Library, name: &'static str)->RawFunction {
+		let address = unsafe {library.get::<*const c_void>(func_alias(name).as_bytes()).map(|symbol| *symbol).unwrap_or(ptr::null())};
+		return RawFunction {name, address};
+	}
+	
+	/// Reinterpret the address as a typed function pointer, `F` must be an `extern "C"` or `extern "system"` fn type.
+	unsafe fn cast<F: Copy>(&self)->Result<F,ChemAppError> {
+		if self.address.is_null() {
+			return Err(ChemAppError::OtherError(format!("{} ({}) is not exported by the loaded library", self.name, func_alias(self.name))));
+		}
+		debug_assert_eq!(mem::size_of::<F>(), mem::size_of::<*const c_void>());
+		return Ok(mem::transmute_copy::<*const c_void, F>(&self.address));
 	}
+	
+}
+
+macro_rules! function_table {
+	($($name:ident),* $(,)?) => {
+		/// Typed-at-call-site function pointers to all ChemApp routines, resolved once in `Engine::new` so that a call does not involve a name lookup.
+		#[derive(Clone,Copy)]
+		pub(crate) struct FunctionTable {
+			$($name: RawFunction,)*
+		}
+		
+		impl FunctionTable {
+			
+			fn resolve(library: &Library)->FunctionTable {
+				return FunctionTable {$($name: RawFunction::resolve(library, stringify!($name)),)*};
+			}
+			
+			fn all(&self)->Vec<&RawFunction> {
+				return vec![$(&self.$name,)*];
+			}
+			
+		}
+	};
+}
+
+function_table!(
+	tqini, tqvers, tqcprt, tqlite, tqgtid, tqgtnm, tqgtpi, tqgthi,
+	tqgted, tqconf, tqsize, tqused, tqgio, tqcio, tqrfil, tqrbin,
+	tqrcst, tqopen, tqwstr, tqopna, tqopnb, tqopnt, tqclos, tqgtrh,
+	tqgsu, tqcsu, tqinsc, tqgnsc, tqcnsc, tqnosc, tqstsc, tqcsc,
+	tqinp, tqgnp, tqmodl, tqnop, tqinpc, tqgnpc, tqpcis, tqnopc,
+	tqstpc, tqchar, tqinlc, tqgnlc, tqnosl, tqnolc, tqgsp, tqcsp,
+	tqgspc, tqcspc, tqsetc, tqremc, tqsttp, tqstca, tqstec, tqstrm,
+	tqce, tqcel, tqcen, tqcenl, tqmap, tqmapl, tqclim, tqshow,
+	tqgetr, tqgdpc, tqstxp, tqgtlc, tqbond, tqerr, tqgdat, tqlpar,
+	tqgpar, tqcdat, tqwasc,
+);
+
+impl FunctionTable {
+	
+	/// names of the routines not exported by the library
+	fn missing(&self)->Vec<&'static str> {
+		return self.all().into_iter().filter(|f| f.address.is_null()).map(|f| f.name).collect();
+	}
+	
+}
+
+impl fmt::Debug for FunctionTable {
+	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
+		return write!(f, "FunctionTable {{ resolved: {}, missing: {:?} }}", self.all().len() - self.missing().len(), self.missing());
+	}
+	
 }
 
 /*********************************************************************************************************************************************************************************************************/
@@ -66,7 +160,14 @@ pub struct Engine {
 	pub n_isothermal: usize,
 	pub n_target: usize,
 	pub(crate) library_name: String,
+	abi: AbiProfile,
+	units: UnitManager,
+	functions: FunctionTable,
+	/// only held to keep the addresses in `functions` valid
+	#[allow(dead_code)]
 	library: Library,
+	/// a private copy of the library made by `EngineFactory`, removed after `library` is unloaded
+	pub(crate) copy: Option<LibraryCopy>,
 }
 
 impl Default for Engine {
@@ -82,560 +183,643 @@ impl Engine {
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// Initializes a new instance of `Engine` from a DLL path or name. In case a name only is used, the DLL has to be discoverable in PATH system variable (modify the system environment variables if it is not the case).
+	/// All ChemApp routines are resolved at this point, the ones missing from the library are reported by `missing_functions` and fail with an error when called.
 	pub fn new(library_name: &str) -> Result<Engine,ChemAppError> {
-		
+		return Engine::with_abi(library_name, AbiProfile::platform());
+	}
+	
+	/// Same as `new`, the `INTEGER` width of the library is also checked against the platform profile (`check_abi`), a mismatch is an error.
+	pub fn new_checked(library_name: &str) -> Result<Engine,ChemAppError> {
+		return Engine::with_abi_checked(library_name, AbiProfile::platform());
+	}
+	
+	/// Initializes a new instance of `Engine` for a library built with a non-default Fortran binary interface (e.g. 4-byte `INTEGER` in a 64-bit build); all the wrappers marshal their arguments through `abi`.
+	/// A profile not matching the library silently corrupts the arguments, see `with_abi_checked`.
+	pub fn with_abi(library_name: &str, abi: AbiProfile) -> Result<Engine,ChemAppError> {
+		let library = unsafe {Library::new(library_name)?};
+		let functions = FunctionTable::resolve(&library);
 		return Ok(Engine {
 			n_isothermal: 0,
 			n_target : 0,
 			library_name: String::from(library_name),
-			library: unsafe {Library::new(library_name)?},
+			abi,
+			units: UnitManager::new(),
+			functions,
+			library,
+			copy: None,
 		});
 	}
 	
+	/// Same as `with_abi`, the `INTEGER` width of the library is also checked against the profile (`check_abi`), a mismatch is an error.
+	pub fn with_abi_checked(library_name: &str, abi: AbiProfile) -> Result<Engine,ChemAppError> {
+		let engine = Engine::with_abi(library_name, abi)?;
+		engine.check_abi()?;
+		return Ok(engine);
+	}
+	
+	/// Names of the ChemApp routines not exported by the loaded library (e.g. routines added in later ChemApp versions).
+	pub fn missing_functions(&self)->Vec<&'static str> {
+		return self.functions.missing();
+	}
+	
+	/// Fortran units in use: the raw `tqopen`/`tqopn*` calls reserve their unit (an error if it is already in use) and `tqclos` releases it, `ChemAppBackend::open_file` takes a free one.
+	pub fn units(&self)->&UnitManager {
+		return &self.units;
+	}
+	
+	/// the binary interface the wrappers use
+	pub fn abi(&self)->AbiProfile {
+		return self.abi;
+	}
+	
+	/// Decode the `na`..`nk` slots written by `tqsize` and `tqused`.
+	fn dimensions(&self, slots: [FInt; 11])->SystemDimensions {
+		let values = slots.map(|slot| self.abi.int32(slot));
+		return SystemDimensions {
+			nconstituents:       values[0],
+			ncomponents:         values[1],
+			nmixtures:           values[2],
+			nexcess_gibbs:       values[3],
+			nexcess_magnetic:    values[4],
+			nsublattices:        values[5],
+			nspecies:            values[6],
+			nconstituents_mqm:   values[7],
+			nranges_constituent: values[8],
+			nranges:             values[9],
+			ndependent:          values[10],
+		};
+	}
+	
+	/// Number of excess parameters a single phase can hold: the array size the library was built with (`tqsize`), the current database (`tqused`) or `minimum`, whichever is largest.
+	fn excess_capacity(&self, minimum: usize)->Result<usize,ChemAppError>{
+		let (size, used) = (self.tqsize()?, self.tqused()?);
+		let bound = |dims: SystemDimensions| (dims.nexcess_gibbs.max(0) + dims.nexcess_magnetic.max(0)) as usize;
+		return Ok(minimum.max(bound(size)).max(bound(used)));
+	}
+	
+	/// Convert an error code written by the library into an error with the call context; every wrapper checks it before reading the other output arguments.
+	fn check(&self, errcode: FInt, function: &'static str, arguments: &[(&str, &dyn fmt::Debug)])->Result<(), ChemAppError>{
+		let code = match self.abi.decode(errcode) {
+			0 => {return Ok(());}
+			code => code.max(0) as usize,
+		};
+		let arguments : Vec<String> = arguments.iter().map(|(name, value)| format!("{} = {:?}", name, value)).collect();
+		let message = if function == "tqerr" {String::new()} else {self.error_message()};
+		return Err(ChemAppError::NativeCall(Box::new(NativeCallError::new(code, function, arguments, message))));
+	}
+	
+	/// The message of the last error (`tqerr`), the lines joined with spaces.
+	fn error_message(&self)->String {
+		match self.tqerr() {
+			Ok(text) => {return text.as_bytes().chunks(80).map(|line| String::from_utf8_lossy(line).replace('\0', "").trim().to_string()).filter(|line| !line.is_empty()).collect::<Vec<String>>().join(" ");}
+			Err(_) => {return String::new();}
+		}
+	}
+	
+	/// Call a routine with the signature `(INTEGER value, INTEGER errcode)` (e.g. `tqvers` or `tqnosc`) with both slots prefilled with a pattern and return the number of bytes written into each of them.
+	fn probe_call(&self, function: RawFunction)->Result<(Option<IntegerWidth>,Option<IntegerWidth>),ChemAppError>{
+		let mut value = AbiProfile::sentinel();
+		let mut errcode = AbiProfile::sentinel();
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(value: &mut FInt, errcode: &mut FInt)->() = function.cast()?;
+				func(&mut value, &mut errcode);
+			},
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(value: &mut FInt, errcode: &mut FInt)->() = function.cast()?;
+				func(&mut value, &mut errcode);
+			},
+		}
+		return Ok((AbiProfile::written_width(value), AbiProfile::written_width(errcode)));
+	}
+	
+	/// Detect the `INTEGER` width of the library from the number of bytes `tqvers` and `tqnosc` write into their arguments (both calls are harmless before `tqini`, `tqnosc` then only sets its error code).
+	pub fn probe_abi(&self)->Result<IntegerWidth,ChemAppError>{
+		let (vers, vers_errcode) = self.probe_call(self.functions.tqvers)?;
+		let (nscom, nscom_errcode) = self.probe_call(self.functions.tqnosc)?;
+		let widths : Vec<IntegerWidth> = [vers, vers_errcode, nscom, nscom_errcode].into_iter().flatten().collect();
+		match widths.first() {
+			None => {return Err(ChemAppError::OtherError("ABI probe failed: tqvers and tqnosc did not write any of their arguments".to_string()));}
+			Some(width) if widths.iter().any(|w| w != width) => {return Err(ChemAppError::OtherError(format!("ABI probe failed: inconsistent INTEGER widths {:?}", widths)));}
+			Some(width) => {return Ok(*width);}
+		}
+	}
+	
+	/// Check that the `INTEGER` width of the library matches the profile the engine was created with.
+	pub fn check_abi(&self)->Result<(),ChemAppError>{
+		let width = self.probe_abi()?;
+		if width != self.abi.integer {
+			return Err(ChemAppError::OtherError(format!("ABI mismatch: {} uses INTEGER*{}, the engine was created with {}", self.library_name, width.bytes(), self.abi)));
+		}
+		return Ok(());
+	}
+	
 	/*****************************************************************************************************************************************************************************************************/
 	/// INITIALIZE-INTERFACE
 	#[named]
 	pub fn tqini(&self) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(errcode: &mut FInt)->() = self.functions.tqini.cast()?;
+				func(&mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(errcode: &mut FInt)->() = self.functions.tqini.cast()?;
+				func(&mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-VERSION-NUMBER
 	#[named]
 	pub fn tqvers(&self) -> Result<i32, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut vers = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(vers: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut vers, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(vers: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut vers, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(vers: &mut FInt, errcode: &mut FInt)->() = self.functions.tqvers.cast()?;
+				func(&mut vers, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(vers: &mut FInt, errcode: &mut FInt)->() = self.functions.tqvers.cast()?;
+				func(&mut vers, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(vers, errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(self.abi.int32(vers));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-COPYRIGHT-MESSAGE
 	#[named]
 	pub fn tqcprt(&self) -> Result<(), ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(errcode: &mut FInt)->()
+					= self.functions.tqcprt.cast()?;
+				func(&mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(errcode: &mut FInt)->() = self.functions.tqcprt.cast()?;
+				func(&mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHECK-IF-CHEMAPP-LIGHT
 	#[named]
 	pub fn tqlite(&self) -> Result<bool, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut lite = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(lite: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut lite, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(lite: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut lite, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(lite: &mut FInt, errcode: &mut FInt)->() = self.functions.tqlite.cast()?;
+				func(&mut lite, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(lite: &mut FInt, errcode: &mut FInt)->() = self.functions.tqlite.cast()?;
+				func(&mut lite, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(lite > 0, errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(self.abi.decode(lite) > 0);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-USER-ID
 	#[named]
 	pub fn tqgtid(&self)->Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let mut cstring: [u8; 256] = [0;256];
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(cstring: &mut u8, length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], 256, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cstring: &mut u8, length: usize, errcode: &mut FInt)->() = self.functions.tqgtid.cast()?;
+				func(&mut cstring[0], 256, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cstring: &mut u8, errcode: &mut FInt, length: usize)->() = self.functions.tqgtid.cast()?;
+				func(&mut cstring[0], &mut errcode, 256);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cstring: &mut u8, errcode: &mut usize, length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], &mut errcode, 256);
-		}
-		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cstring[0..clen(&cstring)])?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(from_utf8(&cstring[0..clen(&cstring)])?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-USER-NAME
 	#[named]
 	pub fn tqgtnm(&self)->Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let mut cstring: [u8; 80] = [0;80];
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(cstring: &mut u8, length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], 80, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cstring: &mut u8, errcode: &mut usize, length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], &mut errcode, 80);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cstring: &mut u8, length: usize, errcode: &mut FInt)->() = self.functions.tqgtnm.cast()?;
+				func(&mut cstring[0], 80, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cstring: &mut u8, errcode: &mut FInt, length: usize)->() = self.functions.tqgtnm.cast()?;
+				func(&mut cstring[0], &mut errcode, 80);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cstring[0..clen(&cstring)])?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(from_utf8(&cstring[0..clen(&cstring)])?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-PROGRAM-ID
 	#[named]
 	pub fn tqgtpi(&self)->Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let mut cstring: [u8; 80] = [0;80];
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(cstring: &mut u8, length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], 80, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cstring: &mut u8, length: usize, errcode: &mut FInt)->() = self.functions.tqgtpi.cast()?;
+				func(&mut cstring[0], 80, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cstring: &mut u8, errcode: &mut FInt, length: usize)->() = self.functions.tqgtpi.cast()?;
+				func(&mut cstring[0], &mut errcode, 80);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cstring: &mut u8, errcode: &mut usize, length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], &mut errcode, 80);
-		}
-		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cstring[0..clen(&cstring)])?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(from_utf8(&cstring[0..clen(&cstring)])?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-HASP-DONGLE-INFO
 	#[named]
 	pub fn tqgthi(&self)->Result<(String,i32), ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let mut hid = 0;
 		let mut cstring: [u8; 80] = [0;80];
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(cstring: &mut u8, length: usize, hid: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], 80, &mut hid, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cstring: &mut u8, hid: &mut i32, errcode: &mut usize, length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cstring[0], &mut hid, &mut errcode, 80);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cstring: &mut u8, length: usize, hid: &mut FInt, errcode: &mut FInt)->() = self.functions.tqgthi.cast()?;
+				func(&mut cstring[0], 80, &mut hid, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cstring: &mut u8, hid: &mut FInt, errcode: &mut FInt, length: usize)->() = self.functions.tqgthi.cast()?;
+				func(&mut cstring[0], &mut hid, &mut errcode, 80);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((from_utf8(&cstring[0..clen(&cstring)])?.to_owned(),hid), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok((from_utf8(&cstring[0..clen(&cstring)])?.to_owned(),self.abi.int32(hid)));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-EXPIRATION-MONTH-AND-YEAR
 	#[named]
 	pub fn tqgted(&self)->Result<(u32,u32), ChemAppError>{
-		let fname = func_alias(function_name!());
-		let mut month : u32 = 0;
-		let mut year  : u32 = 0;
+		let mut month = 0;
+		let mut year  = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(month: &mut u32, year: &mut u32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut month, &mut year, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(month: &mut FInt, year: &mut FInt, errcode: &mut FInt)->() = self.functions.tqgted.cast()?;
+				func(&mut month, &mut year, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(month: &mut FInt, year: &mut FInt, errcode: &mut FInt)->() = self.functions.tqgted.cast()?;
+				func(&mut month, &mut year, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(month: &mut u32, year: &mut u32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut month, &mut year, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result((month, year), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok((self.abi.decode(month) as u32, self.abi.decode(year) as u32));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// SET-CONFIGURATION-OPTION
 	#[named]
 	pub fn tqconf(&self, option: &str, valuea: usize, valueb: usize, valuec: usize)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption : CString = CString::new(option)?;
 		let coption_length = option.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(coption: &u8, coption_length: usize, valuea: &usize, valueb: &usize, valuec: &usize, errcode: &mut usize)->()>
-			= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], coption_length, &valuea, &valueb, &valuec, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(coption: &u8, valuea: &usize, valueb: &usize, valuec: &usize, errcode: &mut usize, coption_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &valuea, &valueb, &valuec, &mut errcode, coption_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(coption: &u8, coption_length: usize, valuea: &FInt, valueb: &FInt, valuec: &FInt, errcode: &mut FInt)->()
+				= self.functions.tqconf.cast()?;
+				func(&coption.as_bytes()[0], coption_length, &self.abi.encode(valuea), &self.abi.encode(valueb), &self.abi.encode(valuec), &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(coption: &u8, valuea: &FInt, valueb: &FInt, valuec: &FInt, errcode: &mut FInt, coption_length: usize)->() = self.functions.tqconf.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(valuea), &self.abi.encode(valueb), &self.abi.encode(valuec), &mut errcode, coption_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("valuea", &valuea), ("valueb", &valueb), ("valuec", &valuec)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-ARRAY-SIZES
 	#[named]
 	pub fn tqsize(&self)->Result<SystemDimensions, ChemAppError>{
-		let fname = func_alias(function_name!());
-		let mut dims : SystemDimensions =  SystemDimensions::new();
+		let mut slots : [FInt; 11] = [0; 11];
+		let [na, nb, nc, nd, ne, nf, ng, nh, ni, nj, nk] = &mut slots;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(na: &mut i32, nb: &mut i32, nc: &mut i32, nd: &mut i32, ne: &mut i32, nf: &mut i32, ng: &mut i32, nh: &mut i32, ni: &mut i32, nj: &mut i32, nk: &mut i32, errcode: &mut usize)->()>
-			= self.library.get(fname.as_bytes())?;
-			func(&mut dims.nconstituents, &mut dims.ncomponents, &mut dims.nmixtures, &mut dims.nexcess_gibbs, &mut dims.nexcess_magnetic, &mut dims.nsublattices, &mut dims.nspecies, &mut dims.nconstituents_mqm, &mut dims.nranges_constituent, &mut dims.nranges, &mut dims.ndependent, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(na: &mut FInt, nb: &mut FInt, nc: &mut FInt, nd: &mut FInt, ne: &mut FInt, nf: &mut FInt, ng: &mut FInt, nh: &mut FInt, ni: &mut FInt, nj: &mut FInt, nk: &mut FInt, errcode: &mut FInt)->()
+				= self.functions.tqsize.cast()?;
+				func(na, nb, nc, nd, ne, nf, ng, nh, ni, nj, nk, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(na: &mut FInt, nb: &mut FInt, nc: &mut FInt, nd: &mut FInt, ne: &mut FInt, nf: &mut FInt, ng: &mut FInt, nh: &mut FInt, ni: &mut FInt, nj: &mut FInt, nk: &mut FInt, errcode: &mut FInt)->() = self.functions.tqsize.cast()?;
+				func(na, nb, nc, nd, ne, nf, ng, nh, ni, nj, nk, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(na: &mut i32, nb: &mut i32, nc: &mut i32, nd: &mut i32, ne: &mut i32, nf: &mut i32, ng: &mut i32, nh: &mut i32, ni: &mut i32, nj: &mut i32, nk: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut dims.nconstituents, &mut dims.ncomponents, &mut dims.nmixtures, &mut dims.nexcess_gibbs, &mut dims.nexcess_magnetic, &mut dims.nsublattices, &mut dims.nspecies, &mut dims.nconstituents_mqm, &mut dims.nranges_constituent, &mut dims.nranges, &mut dims.ndependent, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result(dims, errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(self.dimensions(slots));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-CURRENT-DIMENSIONS
 	#[named]
 	pub fn tqused(&self)->Result<SystemDimensions, ChemAppError>{
-		let fname = func_alias(function_name!());
-		let mut dims : SystemDimensions =  SystemDimensions::new();
+		let mut slots : [FInt; 11] = [0; 11];
+		let [na, nb, nc, nd, ne, nf, ng, nh, ni, nj, nk] = &mut slots;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(na: &mut i32, nb: &mut i32, nc: &mut i32, nd: &mut i32, ne: &mut i32, nf: &mut i32, ng: &mut i32, nh: &mut i32, ni: &mut i32, nj: &mut i32, nk: &mut i32, errcode: &mut usize)->()>
-			= self.library.get(fname.as_bytes())?;
-			func(&mut dims.nconstituents, &mut dims.ncomponents, &mut dims.nmixtures, &mut dims.nexcess_gibbs, &mut dims.nexcess_magnetic, &mut dims.nsublattices, &mut dims.nspecies, &mut dims.nconstituents_mqm, &mut dims.nranges_constituent, &mut dims.nranges, &mut dims.ndependent, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(na: &mut i32, nb: &mut i32, nc: &mut i32, nd: &mut i32, ne: &mut i32, nf: &mut i32, ng: &mut i32, nh: &mut i32, ni: &mut i32, nj: &mut i32, nk: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut dims.nconstituents, &mut dims.ncomponents, &mut dims.nmixtures, &mut dims.nexcess_gibbs, &mut dims.nexcess_magnetic, &mut dims.nsublattices, &mut dims.nspecies, &mut dims.nconstituents_mqm, &mut dims.nranges_constituent, &mut dims.nranges, &mut dims.ndependent, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(na: &mut FInt, nb: &mut FInt, nc: &mut FInt, nd: &mut FInt, ne: &mut FInt, nf: &mut FInt, ng: &mut FInt, nh: &mut FInt, ni: &mut FInt, nj: &mut FInt, nk: &mut FInt, errcode: &mut FInt)->()
+				= self.functions.tqused.cast()?;
+				func(na, nb, nc, nd, ne, nf, ng, nh, ni, nj, nk, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(na: &mut FInt, nb: &mut FInt, nc: &mut FInt, nd: &mut FInt, ne: &mut FInt, nf: &mut FInt, ng: &mut FInt, nh: &mut FInt, ni: &mut FInt, nj: &mut FInt, nk: &mut FInt, errcode: &mut FInt)->() = self.functions.tqused.cast()?;
+				func(na, nb, nc, nd, ne, nf, ng, nh, ni, nj, nk, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(dims, errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(self.dimensions(slots));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-VALUE-OF-INPUT-OUTPUT-OPTION
 	#[named]
 	pub fn tqgio(&self, option: &str)->Result<usize,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let mut num = 0;
 		let coption: CString = CString::new(option)?;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(option: &u8, option_len: usize, num: &mut usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option.len(), &mut num, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_len: usize, num: &mut FInt, errcode: &mut FInt)->()
+					= self.functions.tqgio.cast()?;
+				func(&coption.as_bytes()[0], option.len(), &mut num, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, num: &mut FInt, errcode: &mut FInt, option_len: usize)->() = self.functions.tqgio.cast()?;
+				func(&coption.as_bytes()[0], &mut num, &mut errcode, option.len());
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, num: &mut usize, errcode: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &mut num, &mut errcode, option.len());
-		}
-		/******************************************************************************************************/
-		return wrap_result(num, errcode);
+		self.check(errcode, function_name!(), &[("option", &option)])?;
+		return Ok(self.abi.index(num));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHANGE-INPUT-OPTION
 	#[named]
 	pub fn tqcio(&self, option: &str, unit: usize)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_len: usize, unit: &usize, errcode: &mut usize)->()> 
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option.len(), &unit, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, unit: &usize, errcode: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &unit, &mut errcode, option.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_len: usize, unit: &FInt, errcode: &mut FInt)->() 
+					= self.functions.tqcio.cast()?;
+				func(&coption.as_bytes()[0], option.len(), &self.abi.encode(unit), &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, unit: &FInt, errcode: &mut FInt, option_len: usize)->() = self.functions.tqcio.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(unit), &mut errcode, option.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("unit", &unit)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// READ-DATA-FILE
 	#[named]
 	pub fn tqrfil(&self) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(errcode: &mut usize)->()>
-			= self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(errcode: &mut FInt)->()
+				= self.functions.tqrfil.cast()?;
+				func(&mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(errcode: &mut FInt)->() = self.functions.tqrfil.cast()?;
+				func(&mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// READ-BINARY-DATA-FILE
 	#[named]
 	pub fn tqrbin(&self) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(errcode: &mut usize)->()>
-			= self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(errcode: &mut FInt)->()
+				= self.functions.tqrbin.cast()?;
+				func(&mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(errcode: &mut FInt)->() = self.functions.tqrbin.cast()?;
+				func(&mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// READ-TRANSPARENT-DATA-FILE
 	#[named]
 	pub fn tqrcst(&self) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(errcode: &mut usize)->()>
-			= self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(errcode: &mut FInt)->()
+				= self.functions.tqrcst.cast()?;
+				func(&mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(errcode: &mut FInt)->() = self.functions.tqrcst.cast()?;
+				func(&mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// OPEN-FILE
-	#[named]
 	pub fn tqopen(&self, filename: &str, unit: usize)->Result<(), ChemAppError>{
-		let fname = func_alias(function_name!());
-		let cfilename: CString = CString::new(filename)?;
-		let cfilename_length = filename.len();
-		let mut errcode = 0;
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(cfilename: &u8, filename_length: usize, unit: &usize, errcode: &mut usize)>
-			= self.library.get(fname.as_bytes())?;
-			func(&cfilename.as_bytes()[0], cfilename_length, &unit, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cfilename: &u8, unit: &usize, errcode: &mut usize, filename_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cfilename.as_bytes()[0], &unit, &mut errcode, cfilename_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		// the unit is reserved while the file is open
+		return self.units.open(unit, || self.open_unit(filename, unit, FileKind::Other));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// TODO WRITE-STRING
 	#[named]
 	pub fn tqwstr(&self, option: &str, text: &str)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let ctext : CString = CString::new(text)?;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_len: usize, text: &u8, text_len: usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option.len(), &ctext.as_bytes()[0], text.len(), &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, text: &u8, errcode: &mut usize, option_len: usize, text_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &ctext.as_bytes()[0], &mut errcode, option.len(), text.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_len: usize, text: &u8, text_len: usize, errcode: &mut FInt)->()
+					= self.functions.tqwstr.cast()?;
+				func(&coption.as_bytes()[0], option.len(), &ctext.as_bytes()[0], text.len(), &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, text: &u8, errcode: &mut FInt, option_len: usize, text_len: usize)->() = self.functions.tqwstr.cast()?;
+				func(&coption.as_bytes()[0], &ctext.as_bytes()[0], &mut errcode, option.len(), text.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("text", &text)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// OPEN-ASCII-DATA-FILE
-	#[named]
 	pub fn tqopna(&self, name: &str, unit: usize) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
-		let cname: CString = CString::new(name)?;
-		let cname_length = name.len();
-		let mut errcode = 0;
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(cname: &u8, cfilename_length: usize, unit: &usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], cname_length, &unit, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cname: &u8, unit: &usize, errcode: &mut usize, cfilename_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], &unit, &mut errcode, cname_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		// the unit is reserved while the file is open
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Ascii));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// OPEN-BINARY-DATA-FILE
-	#[named]
 	pub fn tqopnb(&self, name: &str, unit: usize) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
-		let cname: CString = CString::new(name)?;
-		let cname_length = name.len();
-		let mut errcode = 0;
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(cname: &u8, cname_length: usize, unit: &usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], cname_length, &unit, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cname: &u8, unit: &usize, errcode: &mut usize, cname_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], &unit, &mut errcode, cname_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		// the unit is reserved while the file is open
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Binary));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// OPEN-TRANSPARENT-DATA-FILE
-	#[named]
 	pub fn tqopnt(&self, name: &str, unit: usize) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
+		// the unit is reserved while the file is open
+		return self.units.open(unit, || self.open_unit(name, unit, FileKind::Transparent));
+	}
+	
+	/*****************************************************************************************************************************************************************************************************/
+	/// Open `name` on `unit` with the routine of `kind` (`tqopen`, `tqopna`, `tqopnb` and `tqopnt` share the signature) without reserving the unit, which the caller has taken from `units` (see `FortranFile::open`).
+	pub fn open_unit(&self, name: &str, unit: usize, kind: FileKind) -> Result<(),ChemAppError>{
+		let function = match kind {
+			FileKind::Ascii       => self.functions.tqopna,
+			FileKind::Binary      => self.functions.tqopnb,
+			FileKind::Transparent => self.functions.tqopnt,
+			FileKind::Other       => self.functions.tqopen,
+		};
 		let cname: CString = CString::new(name)?;
 		let cname_length = name.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(cname: &u8, cname_length: usize, unit: &usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], cname_length, &unit, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cname: &u8, cname_length: usize, unit: &FInt, errcode: &mut FInt)->() = function.cast()?;
+				func(&cname.as_bytes()[0], cname_length, &self.abi.encode(unit), &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cname: &u8, unit: &FInt, errcode: &mut FInt, cname_length: usize)->() = function.cast()?;
+				func(&cname.as_bytes()[0], &self.abi.encode(unit), &mut errcode, cname_length);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cname: &u8, unit: &usize, errcode: &mut usize, cname_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], &unit, &mut errcode, cname_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, kind.routine(), &[("name", &name), ("unit", &unit)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CLOSE-FILE
 	#[named]
 	pub fn tqclos(&self, unit: usize) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
-		let mut errcode = 0;
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(unit: &usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&unit, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(unit: &usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&unit, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		// the unit is released also when closing fails
+		return self.units.close(unit, || {
+			let mut errcode = 0;
+			/******************************************************************************************************/
+			match self.abi.position {
+				LengthPosition::AfterString => unsafe {
+					let func: extern "system" fn(unit: &FInt, errcode: &mut FInt)->() = self.functions.tqclos.cast()?;
+					func(&self.abi.encode(unit), &mut errcode);
+				},
+				/******************************************************************************************************/
+				LengthPosition::Trailing => unsafe {
+					let func: extern "C" fn(unit: &FInt, errcode: &mut FInt)->() = self.functions.tqclos.cast()?;
+					func(&self.abi.encode(unit), &mut errcode);
+				},
+			}
+			/******************************************************************************************************/
+			self.check(errcode, function_name!(), &[("unit", &unit)])?;
+			return Ok(());
+		});
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
@@ -645,227 +829,226 @@ impl Engine {
 		const SLENGTH0 : usize = 41;
 		const SLENGTH1 : usize = 81;
 		const SLENGTH2 : usize = 256;
-		let fname = func_alias(function_name!());
 		let mut cver = 0;
 		let mut cnwp : [u8; SLENGTH0] = [0; SLENGTH0];
-		let mut cvnw : [i32; 3] = [0; 3];
+		let mut cvnw = IntArray::zeros(&self.abi, 3);
 		let mut cnrp : [u8; SLENGTH0] = [0; SLENGTH0];
-		let mut cvnr : [i32; 3] = [0; 3];
-		let mut cdtc : [i32; 6] = [0; 6];
-		let mut cdte : [i32; 6] = [0; 6];
+		let mut cvnr = IntArray::zeros(&self.abi, 3);
+		let mut cdtc = IntArray::zeros(&self.abi, 6);
+		let mut cdte = IntArray::zeros(&self.abi, 6);
 		let mut cid  : [u8; SLENGTH2] = [0; SLENGTH2];
 		let mut cusr : [u8; SLENGTH1] = [0; SLENGTH1];
 		let mut crem : [u8; SLENGTH1] = [0; SLENGTH1];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(&mut i32, &mut u8, usize, &mut i32, &mut u8, usize, &mut i32, &mut i32, &mut i32, &mut u8, usize, &mut u8, usize, &mut u8, usize, &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cver, &mut cnwp[0], SLENGTH0, &mut cvnw[0], &mut cnrp[0], SLENGTH0, &mut cvnr[0], &mut cdtc[0], &mut cdte[0], &mut cid[0], SLENGTH2, &mut cusr[0], SLENGTH1, &mut crem[0], SLENGTH1, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(&mut i32, &mut u8, &mut i32, &mut u8, &mut i32, &mut i32, &mut i32, &mut u8, &mut u8, &mut u8, &mut usize, usize, usize, usize, usize, usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cver, &mut cnwp[0], &mut cvnw[0], &mut cnrp[0], &mut cvnr[0], &mut cdtc[0], &mut cdte[0], &mut cid[0], &mut cusr[0], &mut crem[0], &mut errcode, SLENGTH0, SLENGTH0, SLENGTH2, SLENGTH1, SLENGTH1);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(&mut FInt, &mut u8, usize, *mut FInt, &mut u8, usize, *mut FInt, *mut FInt, *mut FInt, &mut u8, usize, &mut u8, usize, &mut u8, usize, &mut FInt)->() = self.functions.tqgtrh.cast()?;
+				func(&mut cver, &mut cnwp[0], SLENGTH0, cvnw.as_mut_ptr(), &mut cnrp[0], SLENGTH0, cvnr.as_mut_ptr(), cdtc.as_mut_ptr(), cdte.as_mut_ptr(), &mut cid[0], SLENGTH2, &mut cusr[0], SLENGTH1, &mut crem[0], SLENGTH1, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(&mut FInt, &mut u8, *mut FInt, &mut u8, *mut FInt, *mut FInt, *mut FInt, &mut u8, &mut u8, &mut u8, &mut FInt, usize, usize, usize, usize, usize)->() = self.functions.tqgtrh.cast()?;
+				func(&mut cver, &mut cnwp[0], cvnw.as_mut_ptr(), &mut cnrp[0], cvnr.as_mut_ptr(), cdtc.as_mut_ptr(), cdte.as_mut_ptr(), &mut cid[0], &mut cusr[0], &mut crem[0], &mut errcode, SLENGTH0, SLENGTH0, SLENGTH2, SLENGTH1, SLENGTH1);
+			},
 		}
 		/******************************************************************************************************/
+		self.check(errcode, function_name!(), &[])?;
 		let header : TransparentHeader = TransparentHeader {
-			version : cver,
+			version : self.abi.int32(cver),
 			name_writing_program       : cu8array2string(&cnwp),
-			version_writing_program    : cvnw,
+			version_writing_program    : std::array::from_fn(|k| cvnw.get(k) as i32),
 			name_reading_program       : cu8array2string(&cnrp),
-			minversion_reading_program : cvnr,
-			creation_date              : cdtc,
-			expiry_date                : cdte,
+			minversion_reading_program : std::array::from_fn(|k| cvnr.get(k) as i32),
+			creation_date              : std::array::from_fn(|k| cdtc.get(k) as i32),
+			expiry_date                : std::array::from_fn(|k| cdte.get(k) as i32),
 			user_ids_allowed           : cu8array2string(&cid),
 			license_holders_allowed    : cu8array2string(&cusr),
 			remark                     : cu8array2string(&crem),
 		};
-		return wrap_result(header, errcode);
+		return Ok(header);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-SYSTEM-UNIT
 	#[named]
 	pub fn tqgsu(&self, option: &str) -> Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len()-1;
 		let mut cunit: [u8; NAME_LENGTH_MAX] = [0;NAME_LENGTH_MAX];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, unit: &mut u8, unit_length: usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &mut cunit[0], NAME_LENGTH_MAX, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, unit: &mut u8, unit_length: usize, errcode: &mut FInt)->()
+					= self.functions.tqgsu.cast()?;
+				func(&coption.as_bytes()[0], option_length, &mut cunit[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, unit: &mut u8, errcode: &mut FInt, option_length: usize, unit_length: usize)->() = self.functions.tqgsu.cast()?;
+				func(&coption.as_bytes()[0], &mut cunit[0], &mut errcode, option_length, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, unit: &mut u8, errcode: &mut usize, option_length: usize, unit_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &mut cunit[0], &mut errcode, option_length, NAME_LENGTH_MAX);
-		}
-		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cunit[0..clen(&cunit)])?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[("option", &option)])?;
+		return Ok(from_utf8(&cunit[0..clen(&cunit)])?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHANGE-SYSTEM-UNIT
 	#[named]
 	pub fn tqcsu(&self, option: &str, unit: &str) -> Result<(), ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let cunit:   CString = CString::new(unit)?;
 		let option_length = option.len();
 		let unit_length = unit.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, unit: &u8, unit_length: usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &cunit.as_bytes()[0], unit_length, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, unit: &u8, errcode: &mut usize, option_length: usize, unit_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &cunit.as_bytes()[0], &mut errcode, option_length, unit_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, unit: &u8, unit_length: usize, errcode: &mut FInt)->()
+					= self.functions.tqcsu.cast()?;
+				func(&coption.as_bytes()[0], option_length, &cunit.as_bytes()[0], unit_length, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, unit: &u8, errcode: &mut FInt, option_length: usize, unit_length: usize)->() = self.functions.tqcsu.cast()?;
+				func(&coption.as_bytes()[0], &cunit.as_bytes()[0], &mut errcode, option_length, unit_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("unit", &unit)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-INDEX-NUMBER-OF-SYSTEM-COMPONENT
 	#[named]
 	pub fn tqinsc(&self, name: &str) -> Result<usize, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cname: CString = CString::new(name)?;
 		let name_length = name.len();
 		let mut indexs = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(name: &u8, name_length: usize, indexs: &mut usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], name_length, &mut indexs, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(name: &u8, name_length: usize, indexs: &mut FInt, errcode: &mut FInt)->()
+					= self.functions.tqinsc.cast()?;
+				func(&cname.as_bytes()[0], name_length, &mut indexs, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(name: &u8, indexs: &mut FInt, errcode: &mut FInt, name_length: usize)->() = self.functions.tqinsc.cast()?;
+				func(&cname.as_bytes()[0], &mut indexs, &mut errcode, name_length);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(name: &u8, indexs: &mut usize, errcode: &mut usize, name_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], &mut indexs, &mut errcode, name_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result(indexs, errcode);
+		self.check(errcode, function_name!(), &[("name", &name)])?;
+		return Ok(self.abi.index(indexs));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NAME-OF-SYSTEM-COMPONENT
 	#[named]
 	pub fn tqgnsc(&self, indexs: usize) -> Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cname: [u8; NAME_LENGTH_MAX] = [0; NAME_LENGTH_MAX];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexs: &usize, name: &mut u8, name_length: usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&indexs, &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexs: &usize, name: &mut u8, errcode: &mut usize, name_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexs, &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexs: &FInt, name: &mut u8, name_length: usize, errcode: &mut FInt)->()
+					= self.functions.tqgnsc.cast()?;
+				func(&self.abi.encode(indexs), &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexs: &FInt, name: &mut u8, errcode: &mut FInt, name_length: usize)->() = self.functions.tqgnsc.cast()?;
+				func(&self.abi.encode(indexs), &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cname[0..clen(&cname)])?.replace('\0', "").to_owned(), errcode);
+		self.check(errcode, function_name!(), &[("indexs", &indexs)])?;
+		return Ok(from_utf8(&cname[0..clen(&cname)])?.replace('\0', "").to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHANGE-NAME-OF-SYSTEM-COMPONENT
 	#[named]
 	pub fn tqcnsc(&self, indexs: usize, name: &str) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cname: CString = CString::new(name)?;
 		let name_length = name.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexs: &usize, name: &u8, name_length: usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&indexs, &cname.as_bytes()[0], name_length, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexs: &FInt, name: &u8, name_length: usize, errcode: &mut FInt)->()
+					= self.functions.tqcnsc.cast()?;
+				func(&self.abi.encode(indexs), &cname.as_bytes()[0], name_length, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexs: &FInt, name: &u8, errcode: &mut FInt, name_length: usize)->() = self.functions.tqcnsc.cast()?;
+				func(&self.abi.encode(indexs), &cname.as_bytes()[0], &mut errcode, name_length);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexs: &usize, name: &u8, errcode: &mut usize, name_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexs, &cname.as_bytes()[0], &mut errcode, name_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("indexs", &indexs), ("name", &name)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NUMBER-OF-SYSTEM-COMPONENTS
 	#[named]
 	pub fn tqnosc(&self) -> Result<usize, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut nscom = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(nscom: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut nscom, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(nscom: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut nscom, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(nscom: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnosc.cast()?;
+				func(&mut nscom, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(nscom: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnosc.cast()?;
+				func(&mut nscom, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(nscom, errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(self.abi.index(nscom));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-STOICHIOMETRY-OF-SYSTEM-COMPONENT
 	#[named]
 	pub fn tqstsc(&self,indexs: usize)->Result<(Vec<f64>,f64),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let ncomp = self.tqnosc()?;
 		let mut stoi : Vec<f64> = vec![0.0;ncomp];
 		let mut wmass = 0.0f64;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexs: &usize, stoi: &mut f64, wmass: &mut f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexs, &mut stoi[0], &mut wmass, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexs: &FInt, stoi: &mut f64, wmass: &mut f64, errcode: &mut FInt)->() = self.functions.tqstsc.cast()?;
+				func(&self.abi.encode(indexs), &mut stoi[0], &mut wmass, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexs: &FInt, stoi: &mut f64, wmass: &mut f64, errcode: &mut FInt)->() = self.functions.tqstsc.cast()?;
+				func(&self.abi.encode(indexs), &mut stoi[0], &mut wmass, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexs: &usize, stoi: &mut f64, wmass: &mut f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexs, &mut stoi[0], &mut wmass, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result((stoi, wmass), errcode);
+		self.check(errcode, function_name!(), &[("indexs", &indexs)])?;
+		return Ok((stoi, wmass));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHANGE-SYSTEM-COMPONENTS
 	#[named]
 	pub fn tqcsc(&self, names: &[&str])->Result<(), ChemAppError>{
-		let fname = func_alias(function_name!());
 		let nsyscom = self.tqnosc()?;
 		let length: usize = 24;
 		let mut namememory : Vec::<u8> = vec![32; (nsyscom+1)*length];
@@ -889,209 +1072,210 @@ impl Engine {
 		//println!("namememory = {:?}", namememory);
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(names: &u8, names_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&namememory[0], length, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(names: &u8, errcode: &mut usize, names_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&namememory[0], &mut errcode, length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(names: &u8, names_length: usize, errcode: &mut FInt)->() = self.functions.tqcsc.cast()?;
+				func(&namememory[0], length, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(names: &u8, errcode: &mut FInt, names_length: usize)->() = self.functions.tqcsc.cast()?;
+				func(&namememory[0], &mut errcode, length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("names", &names)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-INDEX-NUMBER-OF-PHASE
 	#[named]
 	pub fn tqinp(&self, name: &str) -> Result<usize,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cname: CString = CString::new(name)?;
 		let cname_length = name.len();
 		let mut indexp  = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(cname: &u8, cname_length: usize, indexp: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], cname_length, &mut indexp, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cname: &u8, indexp: &mut usize, errcode: &mut usize, cname_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], &mut indexp, &mut errcode, cname_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cname: &u8, cname_length: usize, indexp: &mut FInt, errcode: &mut FInt)->() = self.functions.tqinp.cast()?;
+				func(&cname.as_bytes()[0], cname_length, &mut indexp, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cname: &u8, indexp: &mut FInt, errcode: &mut FInt, cname_length: usize)->() = self.functions.tqinp.cast()?;
+				func(&cname.as_bytes()[0], &mut indexp, &mut errcode, cname_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(indexp, errcode);
+		self.check(errcode, function_name!(), &[("name", &name)])?;
+		return Ok(self.abi.index(indexp));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NAME-OF-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqgnp(&self, indexp: usize) -> Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cname: [u8; NAME_LENGTH_MAX] = [0;NAME_LENGTH_MAX];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, cname: &mut u8, cname_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, cname: &mut u8, errcode: &mut usize, cname_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, cname: &mut u8, cname_length: usize, errcode: &mut FInt)->() = self.functions.tqgnp.cast()?;
+				func(&self.abi.encode(indexp), &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, cname: &mut u8, errcode: &mut FInt, cname_length: usize)->() = self.functions.tqgnp.cast()?;
+				func(&self.abi.encode(indexp), &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cname[0..clen(&cname)])?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp)])?;
+		return Ok(from_utf8(&cname[0..clen(&cname)])?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-MODEL-NAME-OF-PHASE
 	#[named]
 	pub fn tqmodl(&self, indexp: usize) -> Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cname: [u8; NAME_LENGTH_MAX] = [0;NAME_LENGTH_MAX];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, cname: &mut u8, cname_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, cname: &mut u8, errcode: &mut usize, cname_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, cname: &mut u8, cname_length: usize, errcode: &mut FInt)->() = self.functions.tqmodl.cast()?;
+				func(&self.abi.encode(indexp), &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, cname: &mut u8, errcode: &mut FInt, cname_length: usize)->() = self.functions.tqmodl.cast()?;
+				func(&self.abi.encode(indexp), &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cname)?.to_owned().trim().to_string(), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp)])?;
+		return Ok(from_utf8(&cname)?.to_owned().trim().to_string());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NUMBER-OF-PHASES
 	#[named]
 	pub fn tqnop(&self) -> Result<usize, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut nphase  = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(nphase: &mut usize, errcode: &mut usize)> = self.library.get(fname.as_bytes())?;
-			func(&mut nphase, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(nphase: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut nphase, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(nphase: &mut FInt, errcode: &mut FInt) = self.functions.tqnop.cast()?;
+				func(&mut nphase, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(nphase: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnop.cast()?;
+				func(&mut nphase, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(nphase, errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(self.abi.index(nphase));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-INDEX-NUMBER-OF-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqinpc(&self, indexp: usize, name: &str)-> Result<usize, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut indexc  = 0;
 		let mut errcode = 0;
 		let cname : CString = CString::new(name)?;
 		let cname_length = name.len();
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(cname: &u8, name_length: usize, indexp: &usize, indexc: &mut usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], cname_length, &indexp, &mut indexc, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cname: &u8, indexp: &usize, indexc: &mut usize, errcode: &mut usize, name_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], &indexp, &mut indexc, &mut errcode, cname_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cname: &u8, name_length: usize, indexp: &FInt, indexc: &mut FInt, errcode: &mut FInt)->()
+					= self.functions.tqinpc.cast()?;
+				func(&cname.as_bytes()[0], cname_length, &self.abi.encode(indexp), &mut indexc, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cname: &u8, indexp: &FInt, indexc: &mut FInt, errcode: &mut FInt, name_length: usize)->() = self.functions.tqinpc.cast()?;
+				func(&cname.as_bytes()[0], &self.abi.encode(indexp), &mut indexc, &mut errcode, cname_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(indexc, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("name", &name)])?;
+		return Ok(self.abi.index(indexc));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NAME-OF-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqgnpc(&self, indexp: usize, indexc: usize)->Result<String, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cname: [u8; NAME_LENGTH_MAX] = [0; NAME_LENGTH_MAX];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, indexc: &usize, cname: &mut u8, cname_length: usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexc: &usize, cname: &mut u8, errcode: &mut usize, cname_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexc: &FInt, cname: &mut u8, cname_length: usize, errcode: &mut FInt)->()
+					= self.functions.tqgnpc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexc: &FInt, cname: &mut u8, errcode: &mut FInt, cname_length: usize)->() = self.functions.tqgnpc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cname)?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexc", &indexc)])?;
+		return Ok(from_utf8(&cname)?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// PHASE-CONSTITUENT-IS-INCOMING-SPECIES
 	#[named]
 	pub fn tqpcis(&self, indexp: usize, indexc: usize)->Result<bool,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut value = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, indexc: &usize, value: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut value, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexc: &usize, value: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut value, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexc: &FInt, value: &mut FInt, errcode: &mut FInt)->() = self.functions.tqpcis.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut value, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexc: &FInt, value: &mut FInt, errcode: &mut FInt)->() = self.functions.tqpcis.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut value, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(value > 0, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexc", &indexc)])?;
+		return Ok(self.abi.decode(value) > 0);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NUMBER-OF-PHASE-CONSTITUENTS
 	#[named]
 	pub fn tqnopc(&self, indexp: usize)->Result<usize, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut nconst  = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, nconst: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut nconst, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, nconst: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnopc.cast()?;
+				func(&self.abi.encode(indexp), &mut nconst, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, nconst: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnopc.cast()?;
+				func(&self.abi.encode(indexp), &mut nconst, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, nconst: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut nconst, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result(nconst, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp)])?;
+		return Ok(self.abi.index(nconst));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
@@ -1099,357 +1283,357 @@ impl Engine {
 	#[named]
 	pub fn tqstpc(&self, indexp: usize, indexc: usize)->Result<(Vec<f64>,f64),ChemAppError>{
 		//todo!();
-		let fname = func_alias(function_name!());
 		let ncomp = self.tqnosc()?;
 		let mut stoi : Vec<f64> = vec![0.0;ncomp];
 		let mut wmass = 0.0f64;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(indexp: &usize, indexc: &usize, stoi: &mut f64, wmass: &mut f64, errcode: &mut usize)> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut stoi[0], &mut wmass, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexc: &usize, stoi: &mut f64, wmass: &mut f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut stoi[0], &mut wmass, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexc: &FInt, stoi: &mut f64, wmass: &mut f64, errcode: &mut FInt) = self.functions.tqstpc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut stoi[0], &mut wmass, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexc: &FInt, stoi: &mut f64, wmass: &mut f64, errcode: &mut FInt)->() = self.functions.tqstpc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut stoi[0], &mut wmass, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((stoi, wmass), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexc", &indexc)])?;
+		return Ok((stoi, wmass));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-CHARGE-OF-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqchar(&self, indexp: usize, indexc: usize)->Result<i32, ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut charge = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, indexc: &usize, charge: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut charge, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexc: &usize, charge: &mut i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut charge, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexc: &FInt, charge: &mut FInt, errcode: &mut FInt)->() = self.functions.tqchar.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut charge, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexc: &FInt, charge: &mut FInt, errcode: &mut FInt)->() = self.functions.tqchar.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut charge, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(charge, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexc", &indexc)])?;
+		return Ok(self.abi.int32(charge));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-INDEX-NUMBER-OF-SUBLATTICE-CONSTITUENT
 	#[named]
 	pub fn tqinlc(&self, name: &str, indexp: usize, indexl: usize)->Result<usize,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cname: CString = CString::new(name)?;
 		let mut errcode = 0;
-		let mut indexc: usize = 0;
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(name: &u8, name_len: usize, indexp: &usize, indexl: &usize, indexc: &mut usize, errcode: &mut usize)->()> 
-				= self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], name.len(), &indexp, &indexl, &mut indexc, &mut errcode);
-		}
+		let mut indexc = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(name: &u8, indexp: &usize, indexl: &usize, indexc: &mut usize, errcode: &mut usize, name_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cname.as_bytes()[0], &indexp, &indexl, &mut indexc, &mut errcode, name.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(name: &u8, name_len: usize, indexp: &FInt, indexl: &FInt, indexc: &mut FInt, errcode: &mut FInt)->() 
+					= self.functions.tqinlc.cast()?;
+				func(&cname.as_bytes()[0], name.len(), &self.abi.encode(indexp), &self.abi.encode(indexl), &mut indexc, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(name: &u8, indexp: &FInt, indexl: &FInt, indexc: &mut FInt, errcode: &mut FInt, name_len: usize)->() = self.functions.tqinlc.cast()?;
+				func(&cname.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexl), &mut indexc, &mut errcode, name.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(indexc, errcode);
+		self.check(errcode, function_name!(), &[("name", &name), ("indexp", &indexp), ("indexl", &indexl)])?;
+		return Ok(self.abi.index(indexc));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NAME-OF-SUBLATTICE-CONSTITUENT
 	#[named]
 	pub fn tqgnlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<String,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cname : [u8; NAME_LENGTH_MAX] = [0; NAME_LENGTH_MAX];
-		let mut errcode = 0usize;
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(indexp: &usize, indexl: &usize, indexc: &usize, cname: &mut u8, cname_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexl, &indexc, &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
-		}
+		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexl: &usize, indexc: &usize, cname: &mut u8, errcode: &mut usize, cname_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexl, &indexc, &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexl: &FInt, indexc: &FInt, cname: &mut u8, cname_length: usize, errcode: &mut FInt)->() = self.functions.tqgnlc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexl), &self.abi.encode(indexc), &mut cname[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexl: &FInt, indexc: &FInt, cname: &mut u8, errcode: &mut FInt, cname_length: usize)->() = self.functions.tqgnlc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexl), &self.abi.encode(indexc), &mut cname[0], &mut errcode, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cname)?.trim().to_owned(), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexl", &indexl), ("indexc", &indexc)])?;
+		return Ok(from_utf8(&cname)?.trim().to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NUMBER-OF-SUBLATTICES
 	#[named]
 	pub fn tqnosl(&self, indexp: usize)->Result<usize,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
-		let mut nosl: usize = 0;
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, nosl: &mut usize, errcode: &mut usize)->()> 
-				= self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut nosl, &mut errcode);
-		}
+		let mut nosl = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, nosl: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut nosl, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, nosl: &mut FInt, errcode: &mut FInt)->() 
+					= self.functions.tqnosl.cast()?;
+				func(&self.abi.encode(indexp), &mut nosl, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, nosl: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnosl.cast()?;
+				func(&self.abi.encode(indexp), &mut nosl, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(nosl, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp)])?;
+		return Ok(self.abi.index(nosl));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-NUMBER-OF-SUBLATTICE-SPECIES
 	#[named]
 	pub fn tqnolc(&self, indexp: usize, index: usize)->Result<usize,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let mut nosc = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, index: &usize, nosc: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &index, &mut nosc, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, index: &FInt, nosc: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnolc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(index), &mut nosc, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, index: &FInt, nosc: &mut FInt, errcode: &mut FInt)->() = self.functions.tqnolc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(index), &mut nosc, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, index: &usize, nosc: &mut usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &index, &mut nosc, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result(nosc, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("index", &index)])?;
+		return Ok(self.abi.index(nosc));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-STATUS-OF-PHASE
 	#[named]
 	pub fn tqgsp(&self, indexp: usize)->Result<String,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cstatus: [u8;NAME_LENGTH_MAX] = [0;NAME_LENGTH_MAX];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, cstatus: &mut u8, cstatus_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut cstatus[0], NAME_LENGTH_MAX, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, cstatus: &mut u8, errcode: &mut usize, cstatus_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &mut cstatus[0], &mut errcode, NAME_LENGTH_MAX);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, cstatus: &mut u8, cstatus_length: usize, errcode: &mut FInt)->() = self.functions.tqgsp.cast()?;
+				func(&self.abi.encode(indexp), &mut cstatus[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, cstatus: &mut u8, errcode: &mut FInt, cstatus_length: usize)->() = self.functions.tqgsp.cast()?;
+				func(&self.abi.encode(indexp), &mut cstatus[0], &mut errcode, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cstatus)?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp)])?;
+		return Ok(from_utf8(&cstatus)?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHANGE-STATUS-OF-PHASE
 	#[named]
 	pub fn tqcsp(&self, indexp: usize, status: &str)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cstatus: CString = CString::new(status)?;
 		let cstatus_length = status.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, cstatus: &u8, cstatus_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &cstatus.as_bytes()[0], cstatus_length, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, cstatus: &u8, errcode: &mut usize, cstatus_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &cstatus.as_bytes()[0], &mut errcode, cstatus_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, cstatus: &u8, cstatus_length: usize, errcode: &mut FInt)->() = self.functions.tqcsp.cast()?;
+				func(&self.abi.encode(indexp), &cstatus.as_bytes()[0], cstatus_length, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, cstatus: &u8, errcode: &mut FInt, cstatus_length: usize)->() = self.functions.tqcsp.cast()?;
+				func(&self.abi.encode(indexp), &cstatus.as_bytes()[0], &mut errcode, cstatus_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("status", &status)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-STATUS-OF-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqgspc(&self, indexp: usize, indexc: usize)->Result<String,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cstatus: [u8;NAME_LENGTH_MAX] = [0;NAME_LENGTH_MAX];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, indexc: &usize, cstatus: &u8, cstatus_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut cstatus[0], NAME_LENGTH_MAX, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexc: &usize, cstatus: &u8, errcode: &mut usize, cstatus_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &mut cstatus[0], &mut errcode, NAME_LENGTH_MAX);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexc: &FInt, cstatus: &u8, cstatus_length: usize, errcode: &mut FInt)->() = self.functions.tqgspc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut cstatus[0], NAME_LENGTH_MAX, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexc: &FInt, cstatus: &u8, errcode: &mut FInt, cstatus_length: usize)->() = self.functions.tqgspc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &mut cstatus[0], &mut errcode, NAME_LENGTH_MAX);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cstatus)?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexc", &indexc)])?;
+		return Ok(from_utf8(&cstatus)?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHANGE-STATUS-OF-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqcspc(&self, indexp: usize, indexc: usize, status: &str)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cstatus: CString = CString::new(status)?;
 		let cstatus_length = status.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, indexc: &usize, status: &u8, status_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &cstatus.as_bytes()[0], cstatus_length, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexc: &usize, status: &u8, errcode: &mut usize, status_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &cstatus.as_bytes()[0], &mut errcode, cstatus_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexc: &FInt, status: &u8, status_length: usize, errcode: &mut FInt)->() = self.functions.tqcspc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &cstatus.as_bytes()[0], cstatus_length, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexc: &FInt, status: &u8, errcode: &mut FInt, status_length: usize)->() = self.functions.tqcspc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &cstatus.as_bytes()[0], &mut errcode, cstatus_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexc", &indexc), ("status", &status)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// SET-EQUILIBRIUM-CONDITION
 	#[named]
 	pub fn tqsetc(&self, option: &str, indexp: usize, indexc: usize, val: f64) -> Result<i32,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len();
 		let mut numcon  = 0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, indexp: &usize, indexc: &usize, val: &f64, numcon: &mut i32, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &indexp, &indexc, &val, &mut numcon, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, val: &f64, numcon: &mut i32, errcode: &mut usize, option_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &val, &mut numcon, &mut errcode, option_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, indexp: &FInt, indexc: &FInt, val: &f64, numcon: &mut FInt, errcode: &mut FInt)->()
+					= self.functions.tqsetc.cast()?;
+				func(&coption.as_bytes()[0], option_length, &self.abi.encode(indexp), &self.abi.encode(indexc), &val, &mut numcon, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, val: &f64, numcon: &mut FInt, errcode: &mut FInt, option_length: usize)->() = self.functions.tqsetc.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &val, &mut numcon, &mut errcode, option_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(numcon, errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc), ("val", &val)])?;
+		return Ok(self.abi.int32(numcon));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// REMOVE-EQUILIBRIUM-CONDITION
 	#[named]
 	pub fn tqremc(&self, numcon: i32) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(numcon: &i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&numcon, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(numcon: &i32, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&numcon, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(numcon: &FInt, errcode: &mut FInt)->() = self.functions.tqremc.cast()?;
+				func(&self.abi.encode(numcon), &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(numcon: &FInt, errcode: &mut FInt)->() = self.functions.tqremc.cast()?;
+				func(&self.abi.encode(numcon), &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("numcon", &numcon)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// SET-NAME-TEMPERATURE-PRESSURE-FOR-A-STREAM
 	#[named]
 	pub fn tqsttp(&self, idents: &str, vals: (f64,f64))->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let vals_ = [vals.0, vals.1];
 		let cidents: CString = CString::new(idents)?;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(idents: &u8, idents_len: usize, vals: &f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], idents.len(), &vals_[0], &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(idents: &u8, vals: &f64, errcode: &mut usize, idents_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], &vals_[0], &mut errcode, idents.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(idents: &u8, idents_len: usize, vals: &f64, errcode: &mut FInt)->() = self.functions.tqsttp.cast()?;
+				func(&cidents.as_bytes()[0], idents.len(), &vals_[0], &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(idents: &u8, vals: &f64, errcode: &mut FInt, idents_len: usize)->() = self.functions.tqsttp.cast()?;
+				func(&cidents.as_bytes()[0], &vals_[0], &mut errcode, idents.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("idents", &idents), ("vals", &vals)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// SET-CONSTITUENT-AMOUNTS-FOR-A-STREAM
 	#[named]
 	pub fn tqstca(&self, idents: &str, indexp: usize, indexc: usize, val: f64)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cidents: CString = CString::new(idents)?;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(idents: &u8, idents_len: usize, indexp: &usize, indexc: &usize, val: &f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], idents.len(), &indexp, &indexc, &val, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(idents: &u8, indexp: &usize, indexc: &usize, val: &f64, errcode: &mut usize, idents_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], &indexp, &indexc, &val, &mut errcode, idents.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(idents: &u8, idents_len: usize, indexp: &FInt, indexc: &FInt, val: &f64, errcode: &mut FInt)->()
+					= self.functions.tqstca.cast()?;
+				func(&cidents.as_bytes()[0], idents.len(), &self.abi.encode(indexp), &self.abi.encode(indexc), &val, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(idents: &u8, indexp: &FInt, indexc: &FInt, val: &f64, errcode: &mut FInt, idents_len: usize)->() = self.functions.tqstca.cast()?;
+				func(&cidents.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &val, &mut errcode, idents.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("idents", &idents), ("indexp", &indexp), ("indexc", &indexc), ("val", &val)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// SET-EQUILIBRIUM-CONDITION-WHEN-STREAM-INPUT
 	#[named]
 	pub fn tqstec(&self, option: &str, indexp: usize, val: f64)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_len: usize, indexp: &usize, val: &f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option.len(), &indexp, &val, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_len: usize, indexp: &FInt, val: &f64, errcode: &mut FInt)->()
+					= self.functions.tqstec.cast()?;
+				func(&coption.as_bytes()[0], option.len(), &self.abi.encode(indexp), &val, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, val: &f64, errcode: &mut FInt, option_len: usize)->() = self.functions.tqstec.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &val, &mut errcode, option.len());
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, val: &f64, errcode: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &val, &mut errcode, option.len());
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("val", &val)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
@@ -1457,460 +1641,474 @@ impl Engine {
 	#[named]
 	pub fn tqstrm(&self, idents: &str)->Result<(),ChemAppError>{
 		//todo!();
-		let fname = func_alias(function_name!());
 		let cidents: CString = CString::new(idents)?;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(idents: &u8, idents_len: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], idents.len(), &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(idents: &u8, errcode: &mut usize, idents_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], &mut errcode, idents.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(idents: &u8, idents_len: usize, errcode: &mut FInt)->() = self.functions.tqstrm.cast()?;
+				func(&cidents.as_bytes()[0], idents.len(), &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(idents: &u8, errcode: &mut FInt, idents_len: usize)->() = self.functions.tqstrm.cast()?;
+				func(&cidents.as_bytes()[0], &mut errcode, idents.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("idents", &idents)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CALCULATE-EQUILIBRIUM
 	#[named]
 	pub fn tqce(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64)) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len();
 		let vals_ : [f64;2] = [vals.0, vals.1];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &indexp, &indexc, &vals_[0], &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize, option_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &vals_[0], &mut errcode, option_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt)->()
+					= self.functions.tqce.cast()?;
+				func(&coption.as_bytes()[0], option_length, &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt, option_length: usize)->() = self.functions.tqce.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode, option_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc), ("vals", &vals)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CALCULATE-EQUILIBRIUM-AND-LIST-RESULTS
 	#[named]
 	pub fn tqcel(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64)) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len();
 		let vals_ : [f64;2] = [vals.0, vals.1];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &indexp, &indexc, &vals_[0], &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize, option_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &vals_[0], &mut errcode, option_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt)->()
+					= self.functions.tqcel.cast()?;
+				func(&coption.as_bytes()[0], option_length, &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt, option_length: usize)->() = self.functions.tqcel.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode, option_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc), ("vals", &vals)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CALCULATE-EQUILIBRIUM-FROM-PREVIOUS
 	#[named]
 	pub fn tqcen(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64)) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len();
 		let vals_ : [f64;2] = [vals.0, vals.1];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &indexp, &indexc, &vals_[0], &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt)->()
+					= self.functions.tqcen.cast()?;
+				func(&coption.as_bytes()[0], option_length, &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt, option_length: usize)->() = self.functions.tqcen.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode, option_length);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize, option_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &vals_[0], &mut errcode, option_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc), ("vals", &vals)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CALCULATE-EQUILIBRIUM-FROM-PREVIOUS-AND-LIST-RESULTS
 	#[named]
 	pub fn tqcenl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64, f64)) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len();
 		let vals_ : [f64;2] = [vals.0, vals.1];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &indexp, &indexc, &vals_[0], &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, vals: &f64, errcode: &mut usize, option_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &vals_[0], &mut errcode, option_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt)->()
+					= self.functions.tqcenl.cast()?;
+				func(&coption.as_bytes()[0], option_length, &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, vals: &f64, errcode: &mut FInt, option_length: usize)->() = self.functions.tqcenl.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut errcode, option_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc), ("vals", &vals)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	// CALCULATE-ONE-DIMENSIONAL-PHASE-MAP
 	#[named]
 	pub fn tqmap(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let mut errcode = 0;
-		let mut icont : usize = 0;
+		let mut icont = 0;
 		let vals_: [f64;2] = [vals.0, vals.1];
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_len: usize, indexp: &usize, indexc: &usize, vals: &f64, icont: &mut usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option.len(), &indexp, &indexc, &vals_[0], &mut icont, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_len: usize, indexp: &FInt, indexc: &FInt, vals: &f64, icont: &mut FInt, errcode: &mut FInt)->()
+					= self.functions.tqmap.cast()?;
+				func(&coption.as_bytes()[0], option.len(), &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut icont, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, vals: &f64, icont: &mut FInt, errcode: &mut FInt, option_len: usize)->() = self.functions.tqmap.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut icont, &mut errcode, option.len());
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, vals: &f64, icont: &mut usize, errcode: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &vals_[0], &mut icont, &mut errcode, option.len());
-		}
-		/******************************************************************************************************/
-		return wrap_result(icont, errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc), ("vals", &vals)])?;
+		return Ok(self.abi.index(icont));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	// CALCULATE-ONE-DIMENSIONAL-PHASE-MAP-AND-LIST-RESULTS
 	#[named]
 	pub fn tqmapl(&self, option: &str, indexp: usize, indexc: usize, vals: (f64,f64))->Result<usize,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let mut errcode = 0;
-		let mut icont : usize = 0;
+		let mut icont = 0;
 		let vals_: [f64;2] = [vals.0, vals.1];
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_len: usize, indexp: &usize, indexc: &usize, vals: &f64, icont: &mut usize, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option.len(), &indexp, &indexc, &vals_[0], &mut icont, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, vals: &f64, icont: &mut usize, errcode: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &vals_[0], &mut icont, &mut errcode, option.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_len: usize, indexp: &FInt, indexc: &FInt, vals: &f64, icont: &mut FInt, errcode: &mut FInt)->()
+					= self.functions.tqmapl.cast()?;
+				func(&coption.as_bytes()[0], option.len(), &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut icont, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, vals: &f64, icont: &mut FInt, errcode: &mut FInt, option_len: usize)->() = self.functions.tqmapl.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &vals_[0], &mut icont, &mut errcode, option.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(icont, errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc), ("vals", &vals)])?;
+		return Ok(self.abi.index(icont));
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// CHANGE-LIMIT-OF-TARGET-VARIABLE
 	#[named]
 	pub fn tqclim(&self, option: &str, val: f64) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, val: &f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &val, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, val: &f64, errcode: &mut FInt)->()
+					= self.functions.tqclim.cast()?;
+				func(&coption.as_bytes()[0], option_length, &val, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, val: &f64, errcode: &mut FInt, option_length: usize)->() = self.functions.tqclim.cast()?;
+				func(&coption.as_bytes()[0], &val, &mut errcode, option_length);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, val: &f64, errcode: &mut usize, option_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &val, &mut errcode, option_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("val", &val)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// SHOW-PRESENT-SETTINGS
 	#[named]
 	pub fn tqshow(&self) -> Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(errcode: &mut FInt)->() = self.functions.tqshow.cast()?;
+				func(&mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(errcode: &mut FInt)->() = self.functions.tqshow.cast()?;
+				func(&mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-RESULT
 	#[named]
 	pub fn tqgetr(&self, option: &str, indexp: usize, indexc: usize)->Result<f64,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let option_length = option.len();
 		let mut value = 0.0f64;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_length: usize, indexp: &usize, indexc: &usize, value: &mut f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option_length, &indexp, &indexc, &mut value, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_length: usize, indexp: &FInt, indexc: &FInt, value: &mut f64, errcode: &mut FInt)->()
+					= self.functions.tqgetr.cast()?;
+				func(&coption.as_bytes()[0], option_length, &self.abi.encode(indexp), &self.abi.encode(indexc), &mut value, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, indexc: &FInt, value: &mut f64, errcode: &mut FInt, option_length: usize)->() = self.functions.tqgetr.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(indexc), &mut value, &mut errcode, option_length);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, indexc: &usize, value: &mut f64, errcode: &mut usize, option_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &indexc, &mut value, &mut errcode, option_length);
-		}
-		/******************************************************************************************************/
-		return wrap_result(value, errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("indexc", &indexc)])?;
+		return Ok(value);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-PROPERTY-OF-A-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqgdpc(&self, option: &str, indexp: usize, index: usize)->Result<f64,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let coption: CString = CString::new(option)?;
 		let mut fval = 0.0f64;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(option: &u8, option_len: usize, indexp: &usize, index: &usize, fval: &mut f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], option.len(), &indexp, &index, &mut fval, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(option: &u8, indexp: &usize, index: &usize, fval: &mut f64, errcode: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&coption.as_bytes()[0], &indexp, &index, &mut fval, &mut errcode, option.len());
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(option: &u8, option_len: usize, indexp: &FInt, index: &FInt, fval: &mut f64, errcode: &mut FInt)->()
+					= self.functions.tqgdpc.cast()?;
+				func(&coption.as_bytes()[0], option.len(), &self.abi.encode(indexp), &self.abi.encode(index), &mut fval, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(option: &u8, indexp: &FInt, index: &FInt, fval: &mut f64, errcode: &mut FInt, option_len: usize)->() = self.functions.tqgdpc.cast()?;
+				func(&coption.as_bytes()[0], &self.abi.encode(indexp), &self.abi.encode(index), &mut fval, &mut errcode, option.len());
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(fval, errcode);
+		self.check(errcode, function_name!(), &[("option", &option), ("indexp", &indexp), ("index", &index)])?;
+		return Ok(fval);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-THERMODYNAMIC-PROPERTY-OF-A-STREAM
 	#[named]
 	pub fn tqstxp(&self, idents: &str, option: &str)->Result<f64,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cidents : CString = CString::new(idents)?;
 		let coption : CString = CString::new(option)?;
 		let mut errcode = 0;
 		let mut fval = 0.0f64;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(idents: &u8, idents_len: usize, option: &u8, option_len: usize, fval: &mut f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], idents.len(), &coption.as_bytes()[0], option.len(), &mut fval, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(idents: &u8, idents_len: usize, option: &u8, option_len: usize, fval: &mut f64, errcode: &mut FInt)->()
+					= self.functions.tqstxp.cast()?;
+				func(&cidents.as_bytes()[0], idents.len(), &coption.as_bytes()[0], option.len(), &mut fval, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(idents: &u8, option: &u8, fval: &mut f64, errcode: &mut FInt, idents_len: usize, option_len: usize)->() = self.functions.tqstxp.cast()?;
+				func(&cidents.as_bytes()[0], &coption.as_bytes()[0], &mut fval, &mut errcode, idents.len(), option.len());
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(idents: &u8, option: &u8, fval: &mut f64, errcode: &mut usize, idents_len: usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cidents.as_bytes()[0], &coption.as_bytes()[0], &mut fval, &mut errcode, idents.len(), option.len());
-		}
-		/******************************************************************************************************/
-		return wrap_result(fval, errcode);
+		self.check(errcode, function_name!(), &[("idents", &idents), ("option", &option)])?;
+		return Ok(fval);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-CALCULATED-EQUILIBRIUM-SUBLATTICE-SITE-FRACTION
 	#[named]
 	pub fn tqgtlc(&self, indexp: usize, indexl: usize, indexc: usize)->Result<f64,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		let mut fval = 0.0f64;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, indexl: &usize, indexc: &usize, fval: &mut f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexl, &indexc, &mut fval, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexl: &usize, indexc: &usize, fval: &mut f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexl, &indexc, &mut fval, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexl: &FInt, indexc: &FInt, fval: &mut f64, errcode: &mut FInt)->()
+					= self.functions.tqgtlc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexl), &self.abi.encode(indexc), &mut fval, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexl: &FInt, indexc: &FInt, fval: &mut f64, errcode: &mut FInt)->() = self.functions.tqgtlc.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexl), &self.abi.encode(indexc), &mut fval, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(fval, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexl", &indexl), ("indexc", &indexc)])?;
+		return Ok(fval);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-CALCULATED-QUADRUPLET-OR-PAIR-FRACTION
 	#[named]
 	pub fn tqbond(&self, indexp: usize, indexa: usize, indexb: usize, indexc: usize, indexd: usize)->Result<f64,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut value = 0.0;
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(indexp: &usize, indexa: &usize, indexb: &usize, indexc: &usize, indexd: &usize, value: &mut f64, errcode: &mut usize)->()>
-				= self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexa, &indexb, &indexc, &indexd, &mut value, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexa: &FInt, indexb: &FInt, indexc: &FInt, indexd: &FInt, value: &mut f64, errcode: &mut FInt)->()
+					= self.functions.tqbond.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexa), &self.abi.encode(indexb), &self.abi.encode(indexc), &self.abi.encode(indexd), &mut value, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexa: &FInt, indexb: &FInt, indexc: &FInt, indexd: &FInt, value: &mut f64, errcode: &mut FInt)->() = self.functions.tqbond.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexa), &self.abi.encode(indexb), &self.abi.encode(indexc), &self.abi.encode(indexd), &mut value, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexa: &usize, indexb: &usize, indexc: &usize, indexd: &usize, value: &mut f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexa, &indexb, &indexc, &indexd, &mut value, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result(value, errcode);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexa", &indexa), ("indexb", &indexb), ("indexc", &indexc), ("indexd", &indexd)])?;
+		return Ok(value);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-ERROR-MESSAGE
 	#[named]
 	pub fn tqerr(&self)->Result<String,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut cmess : [u8; 80 * 3] = [0; 80 * 3];
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(message: &mut u8, message_len: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cmess[0], 80 * 3, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(message: &mut u8, errcode: &mut usize, message_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&mut cmess[0], &mut errcode, 80 * 3);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(message: &mut u8, message_len: usize, errcode: &mut FInt)->() = self.functions.tqerr.cast()?;
+				func(&mut cmess[0], 80 * 3, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(message: &mut u8, errcode: &mut FInt, message_len: usize)->() = self.functions.tqerr.cast()?;
+				func(&mut cmess[0], &mut errcode, 80 * 3);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result(from_utf8(&cmess)?.to_owned(), errcode);
+		self.check(errcode, function_name!(), &[])?;
+		return Ok(from_utf8(&cmess)?.to_owned());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-INPUT-THERMODYNAMIC-DATA-OF-PHASE-CONSTITUENT
 	#[named]
 	pub fn tqgdat(&self, indexp: usize, indexc: usize, option: &str, indexr: usize)->Result<Vec<f64>,ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
-		let mut fval = [0.0;25];
-		let mut nfval = 0usize;
+		let capacity = EQUATION_VALUES_MAX * (self.tqused()?.nranges_constituent.max(1) as usize);
+		let mut fval : Vec<f64> = vec![0.0; capacity];
+		let mut nfval = 0;
 		let coption: CString = CString::new(option)?;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(indexp: &usize, indexc: &usize, option: &u8, option_len: usize, indexr: &usize, nfval: &mut usize, fval: &mut f64, errcode: &mut usize)->()> 
-				= self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &coption.as_bytes()[0], option.len(), &indexr, &mut nfval, &mut fval[0], &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, indexc: &FInt, option: &u8, option_len: usize, indexr: &FInt, nfval: &mut FInt, fval: &mut f64, errcode: &mut FInt)->() 
+					= self.functions.tqgdat.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &coption.as_bytes()[0], option.len(), &self.abi.encode(indexr), &mut nfval, &mut fval[0], &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, indexc: &FInt, option: &u8, indexr: &FInt, nfval: &mut FInt, fval: &mut f64, errcode: &mut FInt, option_len: usize)->() = self.functions.tqgdat.cast()?;
+				func(&self.abi.encode(indexp), &self.abi.encode(indexc), &coption.as_bytes()[0], &self.abi.encode(indexr), &mut nfval, &mut fval[0], &mut errcode, option.len());
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, indexc: &usize, option: &u8, indexr: &usize, nfval: &mut usize, fval: &mut f64, errcode: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &indexc, &coption.as_bytes()[0], &indexr, &mut nfval, &mut fval[0], &mut errcode, option.len());
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("indexc", &indexc), ("option", &option), ("indexr", &indexr)])?;
+		let nfval = self.abi.index(nfval);
+		if nfval > capacity {
+			return Err(ChemAppError::BufferOverflow(format!("tqgdat reported {} values for phase {}, constituent {}, the buffer holds {}", nfval, indexp, indexc, capacity)));
 		}
-		/******************************************************************************************************/
-		return wrap_result(fval[0..nfval].into_iter().copied().collect(), errcode);
+		fval.truncate(nfval);
+		return Ok(fval);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// LIST-EXCESS-PARAMETERS-OF-PHASE
 	#[named]
 	pub fn tqlpar(&self, indexp: usize, option: &str)->Result<Vec<String>,ChemAppError>{
-		let fname = func_alias(function_name!());
-		let mut errcode = 0usize;
-		let mut nopar = 0usize;
+		let mut errcode = 0;
+		let mut nopar = 0;
 		let coption: CString = CString::new(option)?;
-		let mut lgtpar: [usize;1999] = [0usize;1999];
-		let mut chrpar: [[u8;156];1999] = [[0u8;156];1999];
+		let capacity = self.excess_capacity(PARAMETERS_MIN)?;
+		let mut lgtpar = IntArray::zeros(&self.abi, capacity);
+		let mut chrpar : Vec<u8> = vec![b' '; capacity * PARAMETER_LENGTH];
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(indexp: &usize, option: &u8, option_len: usize, nopar: &mut usize, chrpar: &mut u8, chrpar_len: usize, lgtpar: &mut usize, noerr: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &coption.as_bytes()[0],option.len(),&mut nopar, &mut chrpar[0][0], 156, &mut lgtpar[0], &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, option: &u8, option_len: usize, nopar: &mut FInt, chrpar: &mut u8, chrpar_len: usize, lgtpar: *mut FInt, noerr: &mut FInt)->() = self.functions.tqlpar.cast()?;
+				func(&self.abi.encode(indexp), &coption.as_bytes()[0],option.len(),&mut nopar, &mut chrpar[0], PARAMETER_LENGTH, lgtpar.as_mut_ptr(), &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, option: &u8, nopar: &mut FInt, chrpar: &mut u8, lgtpar: *mut FInt, noerr: &mut FInt, option_len: usize, chrpar_len: usize)->() = self.functions.tqlpar.cast()?;
+				func(&self.abi.encode(indexp), &coption.as_bytes()[0], &mut nopar, &mut chrpar[0], lgtpar.as_mut_ptr(), &mut errcode, option.len(), PARAMETER_LENGTH);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, option: &u8, nopar: &mut usize, chrpar: &mut u8, lgtpar: &mut usize, noerr: &mut usize, option_len: usize, chrpar_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &coption.as_bytes()[0], &mut nopar, &mut chrpar[0][0], &mut lgtpar[0], &mut errcode, option.len(), 156);
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("option", &option)])?;
+		let nopar = self.abi.index(nopar);
+		// the buffers are sized for any phase of this library build, a larger count means the sizes could not be trusted
+		if nopar > capacity {
+			return Err(ChemAppError::BufferOverflow(format!("tqlpar reported {} parameters for phase {}, the buffer holds {}", nopar, indexp, capacity)));
 		}
-		/******************************************************************************************************/
-		let vec : Vec<String> = chrpar.iter().take(nopar).map(|bytes| {String::from_utf8_lossy(bytes).trim().to_string()}).collect();
-		return wrap_result(vec, errcode);
+		let vec : Vec<String> = chrpar.chunks(PARAMETER_LENGTH).enumerate().take(nopar).map(|(k, bytes)| {
+			let length = min(lgtpar.index(k), PARAMETER_LENGTH);
+			return String::from_utf8_lossy(&bytes[0..length]).trim().to_string();
+		}).collect();
+		return Ok(vec);
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// GET-EXCESS-PARAMETERS-OF-PHASE
 	#[named]
 	pub fn tqgpar(&self, indexp: usize, option: &str, indexx: usize)->Result<Vec<Vec<f64>>,ChemAppError>{
-		let fname = func_alias(function_name!());
-		let mut errcode = 0usize;
+		let mut errcode = 0;
 		let coption: CString = CString::new(option)?;
-		let mut noexpr = 0usize;
-		let mut nvala = 0usize;
-		let mut vala : [[f64;20];28] = [[0.0;20];28];
-		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func : Symbol<extern "system" fn(indexp: &usize, option: &u8, option_len: usize, indexx: &usize, noexpr: &mut usize, nvala: &mut usize, vala: &mut f64, noerr: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &coption.as_bytes()[0], option.len(), &indexx, &mut noexpr, &mut nvala, &mut vala[0][0], &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(indexp: &usize, option: &u8, indexx: &usize, noexpr: &mut usize, nvala: &mut usize, vala: &mut f64, noerr: &mut usize, option_len: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&indexp, &coption.as_bytes()[0], &indexx, &mut noexpr, &mut nvala, &mut vala[0][0], &mut errcode, option.len());
-		}
-		/******************************************************************************************************/
-		let mut vecc : Vec<Vec<f64>> = Vec::new();
-		for k in 0..noexpr {
-			let mut vec : Vec<f64> = Vec::new();
-			for m in 0..nvala {
-				vec.push(vala[k][m]);
-			}
-			vecc.push(vec);
-		}
+		let mut noexpr = 0;
+		let mut nvala = 0;
+		let capacity = self.excess_capacity(EXPRESSIONS_MIN)?;
+		let mut vala : Vec<f64> = vec![0.0; capacity * EXPRESSION_VALUES_MAX];
+		/******************************************************************************************************/
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(indexp: &FInt, option: &u8, option_len: usize, indexx: &FInt, noexpr: &mut FInt, nvala: &mut FInt, vala: &mut f64, noerr: &mut FInt)->() = self.functions.tqgpar.cast()?;
+				func(&self.abi.encode(indexp), &coption.as_bytes()[0], option.len(), &self.abi.encode(indexx), &mut noexpr, &mut nvala, &mut vala[0], &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(indexp: &FInt, option: &u8, indexx: &FInt, noexpr: &mut FInt, nvala: &mut FInt, vala: &mut f64, noerr: &mut FInt, option_len: usize)->() = self.functions.tqgpar.cast()?;
+				func(&self.abi.encode(indexp), &coption.as_bytes()[0], &self.abi.encode(indexx), &mut noexpr, &mut nvala, &mut vala[0], &mut errcode, option.len());
+			},
+		}
+		/******************************************************************************************************/
+		self.check(errcode, function_name!(), &[("indexp", &indexp), ("option", &option), ("indexx", &indexx)])?;
+		let (noexpr, nvala) = (self.abi.index(noexpr), self.abi.index(nvala));
+		// the buffer is sized for any phase of this library build, a larger count means the sizes could not be trusted
+		if noexpr > capacity || nvala > EXPRESSION_VALUES_MAX {
+			return Err(ChemAppError::BufferOverflow(format!("tqgpar reported {} expressions of {} values for phase {}, the buffer holds {} of {}", noexpr, nvala, indexp, capacity, EXPRESSION_VALUES_MAX)));
+		}
+		let vecc : Vec<Vec<f64>> = vala.chunks(EXPRESSION_VALUES_MAX).take(noexpr).map(|values| values[0..nvala].to_vec()).collect();
 		return Ok(vecc);
 	}
 	
@@ -1918,46 +2116,46 @@ impl Engine {
 	/// CHANGES-DATA-OF-THERMODYNAMIC-DATA-FILE
 	#[named]
 	pub fn tqcdat(&self, i1: usize, i2: usize, i3: usize, i4: usize, i5: usize, val: f64)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe {
-			let func: Symbol<extern "system" fn(i1: &usize, i2: &usize, i3: &usize, i4: &usize, i5: &usize, val: &f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&i1, &i2, &i3, &i4, &i5, &val, &mut errcode);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(i1: &FInt, i2: &FInt, i3: &FInt, i4: &FInt, i5: &FInt, val: &f64, errcode: &mut FInt)->() = self.functions.tqcdat.cast()?;
+				func(&self.abi.encode(i1), &self.abi.encode(i2), &self.abi.encode(i3), &self.abi.encode(i4), &self.abi.encode(i5), &val, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(i1: &FInt, i2: &FInt, i3: &FInt, i4: &FInt, i5: &FInt, val: &f64, errcode: &mut FInt)->() = self.functions.tqcdat.cast()?;
+				func(&self.abi.encode(i1), &self.abi.encode(i2), &self.abi.encode(i3), &self.abi.encode(i4), &self.abi.encode(i5), &val, &mut errcode);
+			},
 		}
 		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(i1: &usize, i2: &usize, i3: &usize, i4: &usize, i5: &usize, val: &f64, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&i1, &i2, &i3, &i4, &i5, &val, &mut errcode);
-		}
-		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("i1", &i1), ("i2", &i2), ("i3", &i3), ("i4", &i4), ("i5", &i5), ("val", &val)])?;
+		return Ok(());
 	}
 	
 	/*****************************************************************************************************************************************************************************************************/
 	/// WRITE-DATA-FILE-IN-ASCII-FORMAT
 	#[named]
 	pub fn tqwasc(&self, file: &str)->Result<(),ChemAppError>{
-		let fname = func_alias(function_name!());
 		let cfile = CString::new(file)?;
 		let cfile_length = file.len();
 		let mut errcode = 0;
 		/******************************************************************************************************/
-		#[cfg(target_family="windows")]
-		unsafe{
-			let func: Symbol<extern "system" fn(cfile: &u8, cfile_length: usize, errcode: &mut usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cfile.as_bytes()[0], cfile_length, &mut errcode);
-		}
-		/******************************************************************************************************/
-		#[cfg(target_family="unix")]
-		unsafe {
-			let func: Symbol<extern "C" fn(cfile: &u8, errcode: &mut usize, cfile_length: usize)->()> = self.library.get(fname.as_bytes())?;
-			func(&cfile.as_bytes()[0], &mut errcode, cfile_length);
+		match self.abi.position {
+			LengthPosition::AfterString => unsafe {
+				let func: extern "system" fn(cfile: &u8, cfile_length: usize, errcode: &mut FInt)->() = self.functions.tqwasc.cast()?;
+				func(&cfile.as_bytes()[0], cfile_length, &mut errcode);
+			},
+			/******************************************************************************************************/
+			LengthPosition::Trailing => unsafe {
+				let func: extern "C" fn(cfile: &u8, errcode: &mut FInt, cfile_length: usize)->() = self.functions.tqwasc.cast()?;
+				func(&cfile.as_bytes()[0], &mut errcode, cfile_length);
+			},
 		}
 		/******************************************************************************************************/
-		return wrap_result((), errcode);
+		self.check(errcode, function_name!(), &[("file", &file)])?;
+		return Ok(());
 	}
 }
 
@@ -0,0 +1,473 @@
+// chemapp_rs::options.rs
+
+//! Typed ChemApp option codes. Instead of passing raw strings ("IA", "XP", "T ", "TF") into the native `tq...` routines, the enums below name every supported option and know which index arguments (phase, constituent, system component) it accepts.
+//! The indices are checked before the FFI call, so a wrong combination is reported as a Rust error rather than a native error 201/4xx at runtime.
+//!
+//! The native routines taking raw strings remain available in `native`; the typed wrappers (`set_condition`, `get_result`, `calculate`, `map`, ...) are provided methods of `ChemAppBackend`.
+
+use std::fmt;
+
+use crate::error::{ChemAppError};
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// The way a pair of ChemApp index arguments `(indexp, indexc)` is interpreted, a zero index means "not used".
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum IndexUsage {
+	/// `(0, 0)` - the entire system
+	System,
+	/// `(indexp, 0)` - a phase
+	Phase,
+	/// `(indexp, indexc)` - a phase constituent (for `XP` and `AP` results - a system component within a phase)
+	Constituent,
+	/// `(0, indexc)` - a system component
+	Component,
+}
+
+impl IndexUsage {
+
+	/// Classify a pair of index arguments.
+	pub fn classify(indexp: usize, indexc: usize)->IndexUsage {
+		match (indexp, indexc) {
+			(0, 0) => IndexUsage::System,
+			(_, 0) => IndexUsage::Phase,
+			(0, _) => IndexUsage::Component,
+			(_, _) => IndexUsage::Constituent,
+		}
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Common behaviour of the typed option enums.
+pub trait ChemAppOption : fmt::Debug + Copy {
+
+	/// The option string passed to ChemApp.
+	fn code(&self)->&'static str;
+
+	/// The index combinations accepted by ChemApp for this option.
+	fn usages(&self)->&'static [IndexUsage];
+
+	/// Check the index arguments before calling into the library.
+	fn check_indices(&self, indexp: usize, indexc: usize)->Result<(),ChemAppError>{
+		let usage = IndexUsage::classify(indexp, indexc);
+		if self.usages().contains(&usage) {
+			return Ok(());
+		}
+		return Err(ChemAppError::OtherError(format!("Option {:?} ('{}') does not accept indices ({}, {}) interpreted as {:?}, expected one of {:?}", self, self.code(), indexp, indexc, usage, self.usages())));
+	}
+
+}
+
+const SYSTEM : &[IndexUsage] = &[IndexUsage::System];
+const PHASE : &[IndexUsage] = &[IndexUsage::Phase];
+const CONSTITUENT : &[IndexUsage] = &[IndexUsage::Constituent];
+const INCOMING : &[IndexUsage] = &[IndexUsage::Constituent, IndexUsage::Component];
+const POTENTIAL : &[IndexUsage] = &[IndexUsage::Phase, IndexUsage::Constituent, IndexUsage::Component];
+const EXTENSIVE : &[IndexUsage] = &[IndexUsage::System, IndexUsage::Phase, IndexUsage::Constituent];
+const STATUS : &[IndexUsage] = &[IndexUsage::Phase, IndexUsage::Constituent];
+const AMOUNT : &[IndexUsage] = &[IndexUsage::System, IndexUsage::Phase, IndexUsage::Constituent, IndexUsage::Component];
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Equilibrium conditions set with `tqsetc`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum Condition {
+	/// temperature, `(0, 0)`
+	T,
+	/// pressure, `(0, 0)`
+	P,
+	/// total volume (constant volume calculations), `(0, 0)`
+	VT,
+	/// incoming amount of a system component `(0, indexc)` or a phase constituent `(indexp, indexc)`
+	IA,
+	/// equilibrium amount of a phase `(indexp, 0)`, used to define formation (0) and precipitation (-0.5) targets
+	A,
+	/// activity of a phase `(indexp, 0)`, a phase constituent `(indexp, indexc)` or a system component `(0, indexc)`
+	AC,
+	/// chemical potential of a phase `(indexp, 0)`, a phase constituent `(indexp, indexc)` or a system component `(0, indexc)`
+	MU,
+	/// extensive property target - heat capacity, `(0, 0)`
+	CP,
+	/// extensive property target - enthalpy, `(0, 0)`
+	H,
+	/// extensive property target - entropy, `(0, 0)`
+	S,
+	/// extensive property target - Gibbs energy, `(0, 0)`
+	G,
+	/// extensive property target - volume, `(0, 0)`
+	V,
+}
+
+impl ChemAppOption for Condition {
+
+	fn code(&self)->&'static str {
+		match self {
+			Self::T  => "T",
+			Self::P  => "P",
+			Self::VT => "VT",
+			Self::IA => "IA",
+			Self::A  => "A",
+			Self::AC => "AC",
+			Self::MU => "MU",
+			Self::CP => "CP",
+			Self::H  => "H",
+			Self::S  => "S",
+			Self::G  => "G",
+			Self::V  => "V",
+		}
+	}
+
+	fn usages(&self)->&'static [IndexUsage] {
+		match self {
+			Self::T | Self::P | Self::VT => SYSTEM,
+			Self::IA => INCOMING,
+			Self::A  => PHASE,
+			Self::AC | Self::MU => POTENTIAL,
+			Self::CP | Self::H | Self::S | Self::G | Self::V => SYSTEM,
+		}
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Calculated results retrieved with `tqgetr`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum ResultQuantity {
+	/// temperature, `(0, 0)`
+	T,
+	/// pressure, `(0, 0)`
+	P,
+	/// total volume, `(0, 0)`
+	VT,
+	/// equilibrium amount of the system `(0, 0)`, a phase `(indexp, 0)`, a phase constituent `(indexp, indexc)` or a system component `(0, indexc)`
+	A,
+	/// incoming amount of a phase constituent `(indexp, indexc)` or a system component `(0, indexc)`
+	IA,
+	/// amount of a system component in a phase, `(indexp, indexc)` where `indexc` is a system component index
+	AP,
+	/// fraction of a phase constituent in its phase `(indexp, indexc)` or of a system component in the system `(0, indexc)`
+	X,
+	/// fraction of a system component in a phase, `(indexp, indexc)` where `indexc` is a system component index
+	XP,
+	/// activity of a phase `(indexp, 0)`, a phase constituent `(indexp, indexc)` or a system component `(0, indexc)`
+	AC,
+	/// chemical potential of a phase `(indexp, 0)`, a phase constituent `(indexp, indexc)` or a system component `(0, indexc)`
+	MU,
+	/// heat capacity of the system, a phase or a phase constituent
+	CP,
+	/// enthalpy of the system, a phase or a phase constituent
+	H,
+	/// entropy of the system, a phase or a phase constituent
+	S,
+	/// Gibbs energy of the system, a phase or a phase constituent
+	G,
+	/// volume of the system, a phase or a phase constituent
+	V,
+	/// heat capacity per amount unit
+	CPM,
+	/// enthalpy per amount unit
+	HM,
+	/// entropy per amount unit
+	SM,
+	/// Gibbs energy per amount unit
+	GM,
+	/// volume per amount unit
+	VM,
+}
+
+impl ChemAppOption for ResultQuantity {
+
+	fn code(&self)->&'static str {
+		match self {
+			Self::T   => "T",
+			Self::P   => "P",
+			Self::VT  => "VT",
+			Self::A   => "A",
+			Self::IA  => "IA",
+			Self::AP  => "AP",
+			Self::X   => "X",
+			Self::XP  => "XP",
+			Self::AC  => "AC",
+			Self::MU  => "MU",
+			Self::CP  => "CP",
+			Self::H   => "H",
+			Self::S   => "S",
+			Self::G   => "G",
+			Self::V   => "V",
+			Self::CPM => "CPM",
+			Self::HM  => "HM",
+			Self::SM  => "SM",
+			Self::GM  => "GM",
+			Self::VM  => "VM",
+		}
+	}
+
+	fn usages(&self)->&'static [IndexUsage] {
+		match self {
+			Self::T | Self::P | Self::VT => SYSTEM,
+			Self::A  => AMOUNT,
+			Self::IA | Self::X => INCOMING,
+			Self::AP | Self::XP => CONSTITUENT,
+			Self::AC | Self::MU => POTENTIAL,
+			Self::CP | Self::H | Self::S | Self::G | Self::V => EXTENSIVE,
+			Self::CPM | Self::HM | Self::SM | Self::GM | Self::VM => EXTENSIVE,
+		}
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Target variables of `tqce`, `tqcel`, `tqcen` and `tqcenl`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum TargetVariable {
+	/// no target variable, a plain equilibrium calculation, `(0, 0)`
+	None,
+	/// temperature is varied to meet the target, `(0, 0)`
+	T,
+	/// pressure is varied to meet the target, `(0, 0)`
+	P,
+	/// incoming amount of a system component `(0, indexc)` or a phase constituent `(indexp, indexc)` is varied to meet the target
+	IA,
+}
+
+impl ChemAppOption for TargetVariable {
+
+	fn code(&self)->&'static str {
+		match self {
+			Self::None => " ",
+			Self::T    => "T",
+			Self::P    => "P",
+			Self::IA   => "IA",
+		}
+	}
+
+	fn usages(&self)->&'static [IndexUsage] {
+		match self {
+			Self::None | Self::T | Self::P => SYSTEM,
+			Self::IA => INCOMING,
+		}
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// One-dimensional phase mapping options of `tqmap` and `tqmapl`, all of them take `(0, 0)` indices.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum MapOption {
+	/// temperature mapping, first call
+	TF,
+	/// temperature mapping, subsequent calls
+	TN,
+	/// pressure mapping, first call
+	PF,
+	/// pressure mapping, subsequent calls
+	PN,
+}
+
+impl ChemAppOption for MapOption {
+
+	fn code(&self)->&'static str {
+		match self {
+			Self::TF => "TF",
+			Self::TN => "TN",
+			Self::PF => "PF",
+			Self::PN => "PN",
+		}
+	}
+
+	fn usages(&self)->&'static [IndexUsage] {
+		return SYSTEM;
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Status of a phase (`tqcsp`, `(indexp, 0)`) or a phase constituent (`tqcspc`, `(indexp, indexc)`).
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum Status {
+	/// considered in the equilibrium calculation
+	Entered,
+	/// activity calculated, but not allowed to form
+	Dormant,
+	/// not considered at all
+	Eliminated,
+}
+
+impl Status {
+
+	/// Parse a status returned by `tqgsp`/`tqgspc`.
+	pub fn from_code(code: &str)->Result<Status,ChemAppError>{
+		match code.trim().to_uppercase().as_ref() {
+			"ENTERED"    => Ok(Status::Entered),
+			"DORMANT"    => Ok(Status::Dormant),
+			"ELIMINATED" => Ok(Status::Eliminated),
+			other => Err(ChemAppError::OtherError(format!("'{}' is not a phase or constituent status", other))),
+		}
+	}
+
+}
+
+impl ChemAppOption for Status {
+
+	fn code(&self)->&'static str {
+		match self {
+			Self::Entered    => "ENTERED",
+			Self::Dormant    => "DORMANT",
+			Self::Eliminated => "ELIMINATED",
+		}
+	}
+
+	fn usages(&self)->&'static [IndexUsage] {
+		return STATUS;
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Thermodynamic properties of a stream retrieved with `tqstxp`; streams are identified by name, so no index arguments are used.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum StreamProperty {
+	/// heat capacity [current energy unit]/K
+	CP,
+	/// enthalpy [current energy unit]
+	H,
+	/// entropy [current energy unit]/K
+	S,
+	/// Gibbs energy [current energy unit]
+	G,
+	/// volume [current volume unit]
+	V,
+}
+
+impl ChemAppOption for StreamProperty {
+
+	fn code(&self)->&'static str {
+		match self {
+			Self::CP => "CP",
+			Self::H  => "H",
+			Self::S  => "S",
+			Self::G  => "G",
+			Self::V  => "V",
+		}
+	}
+
+	fn usages(&self)->&'static [IndexUsage] {
+		return SYSTEM;
+	}
+
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Properties of a single phase constituent retrieved with `tqgdpc`, all of them take `(indexp, indexc)` indices.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum ConstituentProperty {
+	/// heat capacity
+	CP,
+	/// enthalpy
+	H,
+	/// entropy
+	S,
+	/// Gibbs energy
+	G,
+	/// volume
+	V,
+}
+
+impl ChemAppOption for ConstituentProperty {
+
+	fn code(&self)->&'static str {
+		match self {
+			Self::CP => "CP",
+			Self::H  => "H",
+			Self::S  => "S",
+			Self::G  => "G",
+			Self::V  => "V",
+		}
+	}
+
+	fn usages(&self)->&'static [IndexUsage] {
+		return CONSTITUENT;
+	}
+
+}
+
+/*********************************************************************************************************************************************************************************************/
+/*********************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	/// one index pair per usage: S(ystem), P(hase), C(onstituent), (compo)N(ent)
+	const PAIRS : [(char, (usize, usize)); 4] = [('S', (0, 0)), ('P', (2, 0)), ('C', (2, 3)), ('N', (0, 3))];
+
+	/// check the accepted (`accepted`) and the rejected (the others) index pairs of every option
+	fn check<O: ChemAppOption>(table: &[(O, &str)]){
+		for (option, accepted) in table {
+			for (usage, (indexp, indexc)) in PAIRS {
+				let result = option.check_indices(indexp, indexc);
+				assert_eq!(result.is_ok(), accepted.contains(usage), "{:?} with ({}, {})", option, indexp, indexc);
+				if let Err(error) = result {
+					assert!(matches!(error, ChemAppError::OtherError(_)));
+					assert!(error.to_string().contains(option.code()));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn classify_index_pairs(){
+		assert_eq!(IndexUsage::classify(0, 0), IndexUsage::System);
+		assert_eq!(IndexUsage::classify(1, 0), IndexUsage::Phase);
+		assert_eq!(IndexUsage::classify(1, 1), IndexUsage::Constituent);
+		assert_eq!(IndexUsage::classify(0, 1), IndexUsage::Component);
+	}
+
+	#[test]
+	fn condition_indices(){
+		check(&[
+			(Condition::T, "S"), (Condition::P, "S"), (Condition::VT, "S"),
+			(Condition::IA, "CN"), (Condition::A, "P"), (Condition::AC, "PCN"), (Condition::MU, "PCN"),
+			(Condition::CP, "S"), (Condition::H, "S"), (Condition::S, "S"), (Condition::G, "S"), (Condition::V, "S"),
+		]);
+	}
+
+	#[test]
+	fn result_quantity_indices(){
+		check(&[
+			(ResultQuantity::T, "S"), (ResultQuantity::P, "S"), (ResultQuantity::VT, "S"),
+			(ResultQuantity::A, "SPCN"), (ResultQuantity::IA, "CN"), (ResultQuantity::X, "CN"),
+			(ResultQuantity::AP, "C"), (ResultQuantity::XP, "C"), (ResultQuantity::AC, "PCN"), (ResultQuantity::MU, "PCN"),
+			(ResultQuantity::CP, "SPC"), (ResultQuantity::H, "SPC"), (ResultQuantity::S, "SPC"), (ResultQuantity::G, "SPC"), (ResultQuantity::V, "SPC"),
+			(ResultQuantity::CPM, "SPC"), (ResultQuantity::HM, "SPC"), (ResultQuantity::SM, "SPC"), (ResultQuantity::GM, "SPC"), (ResultQuantity::VM, "SPC"),
+		]);
+	}
+
+	#[test]
+	fn other_option_indices(){
+		check(&[(TargetVariable::None, "S"), (TargetVariable::T, "S"), (TargetVariable::P, "S"), (TargetVariable::IA, "CN")]);
+		check(&[(MapOption::TF, "S"), (MapOption::TN, "S"), (MapOption::PF, "S"), (MapOption::PN, "S")]);
+		check(&[(Status::Entered, "PC"), (Status::Dormant, "PC"), (Status::Eliminated, "PC")]);
+		check(&[(StreamProperty::CP, "S"), (StreamProperty::H, "S"), (StreamProperty::S, "S"), (StreamProperty::G, "S"), (StreamProperty::V, "S")]);
+		check(&[(ConstituentProperty::CP, "C"), (ConstituentProperty::H, "C"), (ConstituentProperty::S, "C"), (ConstituentProperty::G, "C"), (ConstituentProperty::V, "C")]);
+	}
+
+}
@@ -9,7 +9,7 @@ bytes::complete::{tag},
 multi::{separated_list1}
 };
 
-use crate::Engine;
+use crate::backend::ChemAppBackend;
 
 /************************************************************************************************************************************************************************************/
 /************************************************************************************************************************************************************************************/
@@ -48,7 +48,7 @@ fn parse_interaction_type(s: &str) -> IResult<&str, &str> {
 /************************************************************************************************************************************************************************************/
 /************************************************************************************************************************************************************************************/
 
-pub fn convert_ge_interaction_species<'a>(engine: &'a Engine, indexp: usize, s: &'a str)->IResult<&'a str,Vec<String>>{
+pub fn convert_ge_interaction_species<'a, B: ChemAppBackend>(engine: &'a B, indexp: usize, s: &'a str)->IResult<&'a str,Vec<String>>{
 	match tuple(( parse_interaction_index, separated_list1(char('-'), parse_speciespower), multispace1, tag(":"), multispace1, parse_ending_species, multispace1, parse_interaction_type  ))(s) {
 		Ok((s, ((index,nterms), vecc, _, _, _, species0, _, itype))) => {
 			let nspecies1 = engine.tqnolc(indexp, 1).unwrap();
@@ -86,8 +86,8 @@ fn parse_interaction_reciprocal<'a>(s: &'a str)->IResult<&'a str, (usize,usize,u
 	return Ok((s,(index,species1,species2,species3,species4,itype)));
 }
 
-/// retrieve a species name from `Engine`
-fn species_name(engine: &Engine, indexp: usize, sindex: usize)->String {
+/// retrieve a species name from a backend
+fn species_name<B: ChemAppBackend>(engine: &B, indexp: usize, sindex: usize)->String {
 	let nspecies1 = engine.tqnolc(indexp, 1).unwrap();
 	if sindex < nspecies1 {
 		return engine.tqgnlc(indexp, 1, sindex).unwrap();
@@ -96,7 +96,7 @@ fn species_name(engine: &Engine, indexp: usize, sindex: usize)->String {
 }
 
 /// convert number-based interaction to name-based interaction
-pub fn convert_ge_interaction<'a>(engine: &'a Engine, indexp: usize, s: &'a str)->IResult<&'a str,String> {
+pub fn convert_ge_interaction<'a, B: ChemAppBackend>(engine: &'a B, indexp: usize, s: &'a str)->IResult<&'a str,String> {
 	match parse_interaction(s) {
 		Ok((s, ((index,nterms), vecc, _, _, _, species0, _, itype))) => {
 			// convert the indices into species names
@@ -128,10 +128,10 @@ pub fn convert_ge_interaction<'a>(engine: &'a Engine, indexp: usize, s: &'a str)
 /************************************************************************************************************************************************************************************/
 /************************************************************************************************************************************************************************************/
 
-pub fn convert_magn_interaction_species<'a>(engine: &'a Engine, indexp: usize, s: &'a str)->IResult<&'a str, Vec<String>>{
+pub fn convert_magn_interaction_species<'a, B: ChemAppBackend>(engine: &'a B, indexp: usize, s: &'a str)->IResult<&'a str, Vec<String>>{
 	todo!();
 }
 
-pub fn convert_magn_interaction<'a>(engine: &'a Engine, indexp: usize, s: &'a str)->IResult<&'a str, String>{
+pub fn convert_magn_interaction<'a, B: ChemAppBackend>(engine: &'a B, indexp: usize, s: &'a str)->IResult<&'a str, String>{
 	todo!();
 }
@@ -0,0 +1,120 @@
+// chemapp_rs::pool.rs
+
+//! Parallel batch evaluation. `CalculatorPool` owns a number of independent calculators (each over its own library copy created by `EngineFactory`, with the datafile loaded and the same transform and unit settings);
+//! a batch of inputs is split between worker threads, one thread per calculator, and the results are returned in the input order.
+
+use std::thread;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use nalgebra::{DVector};
+
+use crate::{Calculator};
+use crate::factory::{EngineFactory};
+use crate::abi::{AbiProfile};
+use crate::result::{EquilibriumResult};
+use crate::error::{ChemAppError};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A pool of independent calculators for parallel batch calculations.
+#[derive(Debug)]
+pub struct CalculatorPool {
+	calculators: Vec<Calculator>,
+	// declared after the calculators, so that the library copies are released before the temporary directory
+	factory: EngineFactory,
+}
+
+impl CalculatorPool {
+	
+	/// Create `size` independent calculators from a ChemApp library and a datafile.
+	pub fn new(library: &str, datafile: &str, size: usize)->Result<CalculatorPool,ChemAppError>{
+		return CalculatorPool::with_abi(library, datafile, size, AbiProfile::platform());
+	}
+	
+	/// Same as `new` for a library built with a non-default Fortran binary interface (see `EngineFactory::with_abi`).
+	pub fn with_abi(library: &str, datafile: &str, size: usize, abi: AbiProfile)->Result<CalculatorPool,ChemAppError>{
+		if size == 0 {
+			return Err(ChemAppError::OtherError("A calculator pool must contain at least one calculator".to_string()));
+		}
+		let factory = EngineFactory::with_abi(library, datafile, abi)?;
+		let mut calculators : Vec<Calculator> = Vec::with_capacity(size);
+		for _ in 0..size {
+			calculators.push(factory.calculator()?);
+		}
+		return Ok(CalculatorPool {
+			calculators,
+			factory,
+		});
+	}
+	
+	/// number of calculators (and worker threads)
+	pub fn size(&self)->usize {
+		return self.calculators.len();
+	}
+	
+	/// the calculators of the pool
+	pub fn calculators(&self)->&[Calculator] {
+		return &self.calculators;
+	}
+	
+	/// the factory the engines were created with
+	pub fn factory(&self)->&EngineFactory {
+		return &self.factory;
+	}
+	
+	/// Set the same formula transform for input compositions on all calculators.
+	pub fn set_transform<T: AsRef<str>>(&mut self, basis: &[T])->Result<(),ChemAppError>{
+		for calculator in self.calculators.iter_mut() {
+			calculator.set_transform(basis)?;
+		}
+		return Ok(());
+	}
+	
+	/// Change a system unit (`tqcsu`) on all calculators, e.g. `("Temperature", "C")`.
+	pub fn set_unit(&self, option: &str, unit: &str)->Result<(),ChemAppError>{
+		for calculator in self.calculators.iter() {
+			calculator.engine.tqcsu(option, unit)?;
+		}
+		return Ok(());
+	}
+	
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+	
+	/// Apply `func` to every input in parallel; every worker thread is pinned to one calculator and takes the next unprocessed input until the batch is exhausted. The results are returned in the input order.
+	pub fn map<T: Sync, R: Send, F: Fn(&Calculator, &T)->R + Sync>(&mut self, inputs: &[T], func: F)->Vec<R> {
+		let next = AtomicUsize::new(0);
+		let mut results : Vec<Option<R>> = (0..inputs.len()).map(|_| None).collect();
+		thread::scope(|scope| {
+			let handles : Vec<_> = self.calculators.iter_mut().map(|calculator| {
+				let next = &next;
+				let func = &func;
+				scope.spawn(move || {
+					let calculator : &Calculator = calculator;
+					let mut done : Vec<(usize,R)> = Vec::new();
+					loop {
+						let index = next.fetch_add(1, Ordering::SeqCst);
+						if index >= inputs.len() {break;}
+						done.push((index, func(calculator, &inputs[index])));
+					}
+					return done;
+				})
+			}).collect();
+			for handle in handles {
+				for (index, result) in handle.join().expect("A calculator pool worker thread panicked") {
+					results[index] = Some(result);
+				}
+			}
+		});
+		return results.into_iter().map(|r| r.expect("Every input is processed by a worker thread")).collect();
+	}
+	
+	/// Isothermal calculations for a batch of `(composition, temperature)` inputs (compositions in the transform basis), one result or error per input in the input order.
+	pub fn map_isothermal(&mut self, inputs: &[(DVector<f64>,f64)])->Vec<Result<EquilibriumResult,ChemAppError>> {
+		return self.map(inputs, |calculator, (composition, temp)| calculator.calculate_isothermal(composition, *temp));
+	}
+	
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
@@ -0,0 +1,418 @@
+// chemapp_rs::problem.rs
+
+//! `EquilibriumProblem` collects the inputs of a single equilibrium calculation by name - temperature, pressure, incoming amounts (system components, phase constituents or formulas), status overrides, activity conditions and an optional target.
+//! Nothing is passed to ChemApp until `apply` or `solve`: all names and values are first validated against the loaded datafile, then the conditions are replaced in one go; if ChemApp rejects any of them, the conditions are removed and the statuses restored.
+
+use nalgebra::{DVector};
+use chemformula::{Transform};
+
+use crate::{Engine};
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{Condition, TargetVariable, Status};
+use crate::snapshot::{CalculatorSnapshot};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The condition a target calculation has to meet.
+#[derive(Debug,Clone,PartialEq)]
+pub enum TargetCondition {
+	/// a phase starts to form (equilibrium amount `A` = 0)
+	Formation(String),
+	/// a phase starts to precipitate (equilibrium amount `A` = -0.5)
+	Precipitation(String),
+	/// an extensive property of the system (`Condition::CP`, `H`, `S`, `G` or `V`) reaches the value
+	Property(Condition, f64),
+}
+
+/// The variable ChemApp adjusts to meet the target condition.
+#[derive(Debug,Clone,PartialEq)]
+pub enum TargetVariation {
+	/// temperature within `(low, high)` limits
+	Temperature(f64, f64),
+	/// pressure, an initial estimate
+	Pressure(f64),
+	/// incoming amount of a system component, an initial estimate
+	Component(String, f64),
+	/// incoming amount of a phase constituent `(phase, constituent)`, an initial estimate
+	Constituent(String, String, f64),
+}
+
+/// A system component, a phase or a phase constituent identified by name.
+#[derive(Debug,Clone,PartialEq)]
+enum Entity {
+	Component(String),
+	Phase(String),
+	Constituent(String, String),
+}
+
+/// The validated inputs with the names replaced by indices.
+#[derive(Debug,Clone,Default)]
+struct Plan {
+	statuses   : Vec<(Status,usize,usize)>,
+	conditions : Vec<(Condition,usize,usize,f64)>,
+	target     : Option<(TargetVariable,usize,usize,(f64,f64))>,
+	limits     : Option<(f64,f64)>,
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A builder of an equilibrium calculation with name-based inputs, created by [`Calculator::problem`]:
+/// `calculator.problem().temperature(1873.0).pressure(1.0).formula("CaO", 0.4).formula("SiO2", 0.6).phase_status("gas_ideal", Status::Eliminated).solve()?`.
+/// Incoming amounts entered several times for the same component or constituent are summed; statuses and activities entered several times keep the last value.
+/// Status overrides stay in effect after the calculation, as with `tqcsp`/`tqcspc`.
+#[derive(Debug)]
+pub struct EquilibriumProblem<'a, B: ChemAppBackend = Engine> {
+	calculator  : &'a Calculator<B>,
+	temperature : Option<f64>,
+	pressure    : Option<f64>,
+	amounts     : Vec<(Entity,f64)>,
+	formulas    : Vec<(String,f64)>,
+	statuses    : Vec<(Entity,Status)>,
+	activities  : Vec<(Entity,f64)>,
+	target      : Option<(TargetCondition,TargetVariation)>,
+}
+
+impl<'a, B: ChemAppBackend> EquilibriumProblem<'a, B> {
+
+	/// An empty problem.
+	pub fn new(calculator: &'a Calculator<B>)->Self {
+		return Self {
+			calculator,
+			temperature : None,
+			pressure    : None,
+			amounts     : Vec::new(),
+			formulas    : Vec::new(),
+			statuses    : Vec::new(),
+			activities  : Vec::new(),
+			target      : None,
+		};
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// temperature [current temperature unit]
+	pub fn temperature(mut self, value: f64)->Self {
+		self.temperature = Some(value);
+		return self;
+	}
+
+	/// pressure [current pressure unit]
+	pub fn pressure(mut self, value: f64)->Self {
+		self.pressure = Some(value);
+		return self;
+	}
+
+	/// incoming amount of a system component
+	pub fn component(mut self, name: &str, amount: f64)->Self {
+		self.amounts.push((Entity::Component(name.to_string()), amount));
+		return self;
+	}
+
+	/// incoming amount of a phase constituent
+	pub fn constituent(mut self, phase: &str, constituent: &str, amount: f64)->Self {
+		self.amounts.push((Entity::Constituent(phase.to_string(), constituent.to_string()), amount));
+		return self;
+	}
+
+	/// incoming amount of a formula (e.g. "CaO", "Fe2O3"), converted into system component amounts
+	pub fn formula(mut self, formula: &str, amount: f64)->Self {
+		self.formulas.push((formula.to_string(), amount));
+		return self;
+	}
+
+	/// status of a phase
+	pub fn phase_status(mut self, phase: &str, status: Status)->Self {
+		self.statuses.push((Entity::Phase(phase.to_string()), status));
+		return self;
+	}
+
+	/// status of a phase constituent
+	pub fn constituent_status(mut self, phase: &str, constituent: &str, status: Status)->Self {
+		self.statuses.push((Entity::Constituent(phase.to_string(), constituent.to_string()), status));
+		return self;
+	}
+
+	/// activity of a system component
+	pub fn component_activity(mut self, name: &str, value: f64)->Self {
+		self.activities.push((Entity::Component(name.to_string()), value));
+		return self;
+	}
+
+	/// activity of a phase
+	pub fn phase_activity(mut self, phase: &str, value: f64)->Self {
+		self.activities.push((Entity::Phase(phase.to_string()), value));
+		return self;
+	}
+
+	/// activity of a phase constituent
+	pub fn constituent_activity(mut self, phase: &str, constituent: &str, value: f64)->Self {
+		self.activities.push((Entity::Constituent(phase.to_string(), constituent.to_string()), value));
+		return self;
+	}
+
+	/// a target calculation: `variation` is adjusted until `condition` is met
+	pub fn target(mut self, condition: TargetCondition, variation: TargetVariation)->Self {
+		self.target = Some((condition, variation));
+		return self;
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// Validate the inputs without changing the calculator state.
+	pub fn validate(&self)->Result<(),ChemAppError>{
+		self.resolve()?;
+		return Ok(());
+	}
+
+	/// Validate the inputs and replace the current conditions with them, without calculating the equilibrium.
+	pub fn apply(&self)->Result<(),ChemAppError>{
+		let plan = self.resolve()?;
+		return self.apply_plan(&plan);
+	}
+
+	/// Validate and apply the inputs, calculate the equilibrium and return a snapshot of the result.
+	pub fn solve(&self)->Result<CalculatorSnapshot,ChemAppError>{
+		let plan = self.resolve()?;
+		self.apply_plan(&plan)?;
+		let engine = &self.calculator.engine;
+		match plan.target {
+			Some((variable, indexp, indexc, vals)) => {
+				engine.calculate(variable, indexp, indexc, vals)?;
+			}
+			None => {
+				engine.calculate(TargetVariable::None, 0, 0, (0.0, 0.0))?;
+			}
+		}
+		return Ok(self.calculator.snapshot());
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// Indices `(indexp, indexc)` of a named entity.
+	fn indices(&self, entity: &Entity)->Result<(usize,usize),ChemAppError>{
+		let calculator = self.calculator;
+		match entity {
+			Entity::Component(name) => {
+				return Ok((0, calculator.component_index(name)?));
+			}
+			Entity::Phase(phase) => {
+				return Ok((calculator.phase_index(phase)?, 0));
+			}
+			Entity::Constituent(phase, constituent) => {
+				let indexp = calculator.phase_index(phase)?;
+				return Ok((indexp, calculator.constituent_index(indexp, constituent)?));
+			}
+		}
+	}
+
+	/// System component amounts of `amount` units of a formula.
+	fn formula_amounts(&self, formula: &str, amount: f64)->Result<DVector<f64>,ChemAppError>{
+		let components : Vec<String> = self.calculator.components().map(|c| c.name()).collect();
+		let transform = Transform::new(&components, &vec![formula.to_string()], true).map_err(|e| ChemAppError::OtherError(format!("Formula '{}' cannot be expressed in the system components {:?}: {:?}", formula, components, e)))?;
+		return Ok(transform.transform_final2init(&DVector::from_element(1, amount), false, false, false).column(0).into_owned());
+	}
+
+	/// Check every input against the loaded datafile and translate the names into indices.
+	fn resolve(&self)->Result<Plan,ChemAppError>{
+		let mut plan = Plan::default();
+		// temperature and pressure
+		let varies_t = matches!(self.target, Some((_, TargetVariation::Temperature(..))));
+		let varies_p = matches!(self.target, Some((_, TargetVariation::Pressure(..))));
+		if let Some(value) = self.temperature {
+			if !(value.is_finite() && value > 0.0) {
+				return Err(ChemAppError::OtherError(format!("Temperature must be positive, got {}", value)));
+			}
+			if varies_t {
+				return Err(ChemAppError::OtherError("Temperature cannot be set when it is the target variable".to_string()));
+			}
+			plan.conditions.push((Condition::T, 0, 0, value));
+		}
+		if let Some(value) = self.pressure {
+			if !(value.is_finite() && value > 0.0) {
+				return Err(ChemAppError::OtherError(format!("Pressure must be positive, got {}", value)));
+			}
+			if varies_p {
+				return Err(ChemAppError::OtherError("Pressure cannot be set when it is the target variable".to_string()));
+			}
+			plan.conditions.push((Condition::P, 0, 0, value));
+		}
+		// incoming amounts, summed per component and per constituent
+		let ncomponents = self.calculator.engine.tqnosc()?;
+		let mut components : Vec<Option<f64>> = vec![None; ncomponents];
+		let mut constituents : Vec<((usize,usize),f64)> = Vec::new();
+		for (entity, amount) in self.amounts.iter() {
+			if !(amount.is_finite() && *amount >= 0.0) {
+				return Err(ChemAppError::OtherError(format!("Incoming amount of {:?} must be non-negative, got {}", entity, amount)));
+			}
+			let (indexp, indexc) = self.indices(entity)?;
+			if indexp == 0 {
+				components[indexc-1] = Some(components[indexc-1].unwrap_or(0.0) + amount);
+			} else {
+				match constituents.iter_mut().find(|(key, _)| *key == (indexp, indexc)) {
+					Some((_, total)) => {*total += amount;}
+					None => {constituents.push(((indexp, indexc), *amount));}
+				}
+			}
+		}
+		for (formula, amount) in self.formulas.iter() {
+			if !(amount.is_finite() && *amount >= 0.0) {
+				return Err(ChemAppError::OtherError(format!("Incoming amount of '{}' must be non-negative, got {}", formula, amount)));
+			}
+			let x = self.formula_amounts(formula, *amount)?;
+			for k in 0..ncomponents.min(x.len()) {
+				if x[k] != 0.0 {
+					components[k] = Some(components[k].unwrap_or(0.0) + x[k]);
+				}
+			}
+		}
+		let total : f64 = components.iter().flatten().sum::<f64>() + constituents.iter().map(|(_, a)| a).sum::<f64>();
+		if !(total > 0.0) {
+			return Err(ChemAppError::OtherError("No incoming amounts entered".to_string()));
+		}
+		for (k, amount) in components.iter().enumerate() {
+			if let Some(amount) = amount {plan.conditions.push((Condition::IA, 0, k+1, *amount));}
+		}
+		for ((indexp, indexc), amount) in constituents.iter() {
+			plan.conditions.push((Condition::IA, *indexp, *indexc, *amount));
+		}
+		// statuses and activities, the last entry wins
+		for (entity, status) in self.statuses.iter() {
+			let (indexp, indexc) = self.indices(entity)?;
+			plan.statuses.retain(|(_, p, c)| (*p, *c) != (indexp, indexc));
+			plan.statuses.push((*status, indexp, indexc));
+		}
+		let mut activities : Vec<(usize,usize,f64)> = Vec::new();
+		for (entity, value) in self.activities.iter() {
+			if !(value.is_finite() && *value > 0.0) {
+				return Err(ChemAppError::OtherError(format!("Activity of {:?} must be positive, got {}", entity, value)));
+			}
+			let (indexp, indexc) = self.indices(entity)?;
+			activities.retain(|(p, c, _)| (*p, *c) != (indexp, indexc));
+			activities.push((indexp, indexc, *value));
+		}
+		for (indexp, indexc, value) in activities.iter() {
+			plan.conditions.push((Condition::AC, *indexp, *indexc, *value));
+		}
+		// target
+		if let Some((condition, variation)) = &self.target {
+			if !activities.is_empty() {
+				// ChemApp error 708
+				return Err(ChemAppError::OtherError("Activity conditions are not permitted in target calculations".to_string()));
+			}
+			match condition {
+				TargetCondition::Formation(phase) => {
+					plan.conditions.push((Condition::A, self.calculator.phase_index(phase)?, 0, 0.0));
+				}
+				TargetCondition::Precipitation(phase) => {
+					plan.conditions.push((Condition::A, self.calculator.phase_index(phase)?, 0, -0.5));
+				}
+				TargetCondition::Property(property, value) => {
+					if ![Condition::CP, Condition::H, Condition::S, Condition::G, Condition::V].contains(property) {
+						return Err(ChemAppError::OtherError(format!("{:?} is not an extensive property target", property)));
+					}
+					if !value.is_finite() {
+						return Err(ChemAppError::OtherError(format!("Target value of {:?} must be finite, got {}", property, value)));
+					}
+					plan.conditions.push((*property, 0, 0, *value));
+				}
+			}
+			match variation {
+				TargetVariation::Temperature(low, high) => {
+					if !(low.is_finite() && high.is_finite() && *low > 0.0 && low < high) {
+						return Err(ChemAppError::OtherError(format!("Temperature limits must satisfy 0 < low < high, got ({}, {})", low, high)));
+					}
+					plan.target = Some((TargetVariable::T, 0, 0, (*low, *high)));
+					plan.limits = Some((*low, *high));
+				}
+				TargetVariation::Pressure(estimate) => {
+					if !(estimate.is_finite() && *estimate > 0.0) {
+						return Err(ChemAppError::OtherError(format!("Pressure estimate must be positive, got {}", estimate)));
+					}
+					plan.target = Some((TargetVariable::P, 0, 0, (*estimate, 0.0)));
+				}
+				TargetVariation::Component(name, estimate) => {
+					let (indexp, indexc) = self.indices(&Entity::Component(name.clone()))?;
+					plan.target = Some((TargetVariable::IA, indexp, indexc, (*estimate, 0.0)));
+				}
+				TargetVariation::Constituent(phase, constituent, estimate) => {
+					let (indexp, indexc) = self.indices(&Entity::Constituent(phase.clone(), constituent.clone()))?;
+					plan.target = Some((TargetVariable::IA, indexp, indexc, (*estimate, 0.0)));
+				}
+			}
+		}
+		return Ok(plan);
+	}
+
+	/// Replace the current conditions and temperature limits with the plan; on failure the conditions are removed and the changed statuses restored.
+	fn apply_plan(&self, plan: &Plan)->Result<(),ChemAppError>{
+		let engine = &self.calculator.engine;
+		let mut previous : Vec<(Status,usize,usize)> = Vec::with_capacity(plan.statuses.len());
+		for (_, indexp, indexc) in plan.statuses.iter() {
+			previous.push((engine.get_status(*indexp, *indexc)?, *indexp, *indexc));
+		}
+		let result = (|| -> Result<(),ChemAppError> {
+			self.calculator.reset()?;
+			for (status, indexp, indexc) in plan.statuses.iter() {
+				engine.set_status(*status, *indexp, *indexc)?;
+			}
+			for (condition, indexp, indexc, value) in plan.conditions.iter() {
+				engine.set_condition(*condition, *indexp, *indexc, *value)?;
+			}
+			if let Some(limits) = plan.limits {
+				self.calculator.try_set_clim(limits, true)?;
+			}
+			return Ok(());
+		})();
+		if result.is_err() {
+			let _ = self.calculator.reset();
+			for (status, indexp, indexc) in previous.iter() {
+				let _ = engine.set_status(*status, *indexp, *indexc);
+			}
+		}
+		return result;
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::mock::{calculator};
+
+	#[test]
+	fn problem_solves_to_a_snapshot(){
+		let calculator = calculator();
+		let snapshot = calculator.problem().temperature(1600.0).component("Cu", 0.3).component("Ni", 0.7).solve().unwrap();
+		assert_eq!(snapshot.phases().len(), 2);
+		assert_eq!(snapshot.components().len(), 2);
+		let liquid = snapshot.phase("LIQUID").unwrap();
+		assert_eq!(liquid.a, 1.0);
+		assert!((liquid.constituents[0].a - 0.3).abs() < 1e-12 && (liquid.constituents[1].a - 0.7).abs() < 1e-12);
+		assert_eq!(snapshot.phase("FCC_A1").unwrap().a, 0.0);
+		assert!(snapshot.phase("BCC_A2").is_none());
+	}
+
+	#[test]
+	fn problem_validation_leaves_the_conditions(){
+		let calculator = calculator();
+		calculator.problem().temperature(1200.0).component("Cu", 1.0).apply().unwrap();
+		let conditions = calculator.engine.conditions();
+		assert_eq!(conditions.len(), 2);
+		assert!(calculator.problem().temperature(1600.0).component("Co", 1.0).validate().is_err());
+		assert!(calculator.problem().temperature(-1.0).component("Cu", 1.0).apply().is_err());
+		assert!(calculator.problem().temperature(1600.0).apply().is_err());
+		assert_eq!(calculator.engine.conditions(), conditions);
+	}
+
+}
@@ -0,0 +1,426 @@
+// chemapp_rs::reaction.rs
+
+//! Thermodynamics of user-written reactions, e.g. `2 Fe + 1.5 O2 = Fe2O3`: the changes of the standard Gibbs energy, enthalpy, entropy and heat capacity and `log K` over temperature, from the standard-state data of the loaded datafile (`tqgdpc`).
+//! Every species is matched to the phase constituents of the datafile by name, or else by formula (its stoichiometry in the system components, obtained with a `chemformula` transform, compared with `tqstpc`);
+//! a species found in several phases (e.g. `Fe` in BCC, FCC and LIQUID) is taken in the phase with the lowest standard Gibbs energy at every temperature, unless the phase is given as `Fe[BCC_A2]`.
+//! The reaction must be balanced in the system components and in charge (`tqchar`).
+
+use nalgebra::{dvector};
+use chemformula::{Transform};
+
+use crate::{Engine};
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{Condition, ConstituentProperty};
+
+/// the gas constant [J/(mol K)]
+const R : f64 = 8.314462618;
+
+/// relative tolerance of the stoichiometry comparisons and the balance checks
+const BALANCE_TOLERANCE : f64 = 1e-9;
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Standard-state properties of phase constituents in J/mol and J/(mol K), converted from the dimensionless values returned by `tqgdpc` (G/RT, H/RT, S/R, Cp/R).
+pub(crate) struct StandardState<'a, B: ChemAppBackend> {
+	calculator : &'a Calculator<B>,
+	/// the temperature unit, for the conversion to kelvin
+	unit : String,
+}
+
+impl<'a, B: ChemAppBackend> StandardState<'a, B> {
+
+	pub(crate) fn new(calculator: &'a Calculator<B>)->Result<Self,ChemAppError>{
+		return Ok(Self {calculator, unit: calculator.engine.tqgsu("Temperature")?.trim().to_uppercase()});
+	}
+
+	/// the absolute temperature [K] of `t` in the current temperature unit
+	pub(crate) fn kelvin(&self, t: f64)->f64 {
+		return match self.unit.as_str() {
+			"C" => t + 273.15,
+			"F" => (t - 32.0)*5.0/9.0 + 273.15,
+			"R" => t*5.0/9.0,
+			_ => t,
+		};
+	}
+
+	/// a property of a constituent at `t` (current temperature unit); the temperature is entered as a condition for the query only and removed again afterwards,
+	/// together with a temperature condition entered before (it is replaced by the query one)
+	pub(crate) fn property(&self, property: ConstituentProperty, indexp: usize, indexc: usize, t: f64)->Result<f64,ChemAppError>{
+		let engine = &self.calculator.engine;
+		let numcon = engine.set_condition(Condition::T, 0, 0, t)?;
+		let value = engine.constituent_property(property, indexp, indexc);
+		let removed = engine.tqremc(numcon);
+		let value = value?;
+		removed?;
+		return Ok(match property {
+			ConstituentProperty::G | ConstituentProperty::H => value*R*self.kelvin(t),
+			ConstituentProperty::S | ConstituentProperty::CP => value*R,
+			ConstituentProperty::V => value,
+		});
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A species of a reaction.
+#[derive(Debug,Clone)]
+pub struct ReactionSpecies {
+	/// the stoichiometric coefficient, negative for the reactants
+	pub coefficient : f64,
+	/// the species as written
+	pub name : String,
+	/// the phases holding the species
+	pub phases : Vec<String>,
+	/// `(indexp, indexc)` of the matching constituents
+	constituents : Vec<(usize,usize)>,
+}
+
+/// The property changes of a reaction at one temperature.
+#[derive(Debug,Clone)]
+pub struct ReactionProperties {
+	/// temperature [current temperature unit]
+	pub temperature : f64,
+	/// ΔG° [J]
+	pub delta_g : f64,
+	/// ΔH° [J]
+	pub delta_h : f64,
+	/// ΔS° [J/K]
+	pub delta_s : f64,
+	/// ΔCp° [J/K]
+	pub delta_cp : f64,
+	/// log10 of the equilibrium constant, -ΔG°/(RT ln 10)
+	pub log_k : f64,
+	/// the phase each species is taken in, in the order of `Reaction::species`
+	pub phases : Vec<String>,
+}
+
+/// A balanced reaction between phase constituents of the loaded datafile, created by [`Calculator::reaction`].
+#[derive(Debug)]
+pub struct Reaction<'a, B: ChemAppBackend = Engine> {
+	calculator : &'a Calculator<B>,
+	equation   : String,
+	species    : Vec<ReactionSpecies>,
+}
+
+impl<'a, B: ChemAppBackend> Reaction<'a, B> {
+
+	/// Parse `equation` (`reactants = products`, the terms separated by `+`, each an optional coefficient followed by a species name or formula, optionally with `[phase]`), match the species and check the balance.
+	/// A `+` directly after a species and followed by a space, the end of the side or only a number (`Na+ + Cl-`, `Fe+2[LIQUID]`) is read as the charge of the species.
+	pub fn new(calculator: &'a Calculator<B>, equation: &str)->Result<Self,ChemAppError>{
+		let sides : Vec<&str> = equation.split('=').collect();
+		if sides.len() != 2 {
+			return Err(ChemAppError::OtherError(format!("A reaction must have the form 'reactants = products', got '{}'", equation)));
+		}
+		let mut species : Vec<ReactionSpecies> = Vec::new();
+		for (side, sign) in sides.iter().zip([-1.0, 1.0]) {
+			for term in split_terms(side) {
+				let (coefficient, name, phase) = parse_term(term)?;
+				let constituents = match_species(calculator, name, phase)?;
+				let phases = constituents.iter().map(|(indexp, _)| calculator.engine.tqgnp(*indexp).map(|p| p.trim().to_string())).collect::<Result<Vec<String>,ChemAppError>>()?;
+				species.push(ReactionSpecies {coefficient: sign*coefficient, name: name.to_string(), phases, constituents});
+			}
+		}
+		if !species.iter().any(|s| s.coefficient < 0.0) || !species.iter().any(|s| s.coefficient > 0.0) {
+			return Err(ChemAppError::OtherError(format!("A reaction needs reactants and products, got '{}'", equation)));
+		}
+		let reaction = Self {calculator, equation: equation.trim().to_string(), species};
+		reaction.check_balance()?;
+		return Ok(reaction);
+	}
+
+	/// the equation as written
+	pub fn equation(&self)->&str {
+		return &self.equation;
+	}
+
+	/// the species, reactants with negative coefficients
+	pub fn species(&self)->&[ReactionSpecies] {
+		return &self.species;
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// The property changes at the temperature `t` (current temperature unit).
+	/// The temperature is entered as a condition for the standard-state queries, so a temperature condition of the calculator is removed; enter it again for a following calculation.
+	pub fn properties(&self, t: f64)->Result<ReactionProperties,ChemAppError>{
+		let state = StandardState::new(self.calculator)?;
+		return self.properties_(&state, t);
+	}
+
+	/// The property changes at every temperature of `temperatures`; removes a temperature condition as `properties` does.
+	pub fn table(&self, temperatures: &[f64])->Result<Vec<ReactionProperties>,ChemAppError>{
+		let state = StandardState::new(self.calculator)?;
+		return temperatures.iter().map(|&t| self.properties_(&state, t)).collect();
+	}
+
+	fn properties_(&self, state: &StandardState<'_, B>, t: f64)->Result<ReactionProperties,ChemAppError>{
+		let kelvin = state.kelvin(t);
+		if !(kelvin > 0.0) {
+			return Err(ChemAppError::OtherError(format!("Temperature must be positive, got {}", t)));
+		}
+		let mut result = ReactionProperties {temperature: t, delta_g: 0.0, delta_h: 0.0, delta_s: 0.0, delta_cp: 0.0, log_k: 0.0, phases: Vec::with_capacity(self.species.len())};
+		for species in self.species.iter() {
+			// the most stable standard state
+			let mut best : Option<(f64,usize)> = None;
+			for (i, (indexp, indexc)) in species.constituents.iter().enumerate() {
+				let g = state.property(ConstituentProperty::G, *indexp, *indexc, t)?;
+				if best.map(|b| g < b.0).unwrap_or(true) {best = Some((g, i));}
+			}
+			let (g, i) = best.ok_or(ChemAppError::OtherError(format!("No constituent matches {}", species.name)))?;
+			let (indexp, indexc) = species.constituents[i];
+			let nu = species.coefficient;
+			result.delta_g += nu*g;
+			result.delta_h += nu*state.property(ConstituentProperty::H, indexp, indexc, t)?;
+			result.delta_s += nu*state.property(ConstituentProperty::S, indexp, indexc, t)?;
+			result.delta_cp += nu*state.property(ConstituentProperty::CP, indexp, indexc, t)?;
+			result.phases.push(species.phases[i].clone());
+		}
+		result.log_k = -result.delta_g/(R*kelvin*std::f64::consts::LN_10);
+		return Ok(result);
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// the reaction must conserve the system components and the charge
+	fn check_balance(&self)->Result<(),ChemAppError>{
+		let engine = &self.calculator.engine;
+		let mut components : Vec<f64> = Vec::new();
+		let mut charge = 0.0;
+		let mut scale = 1.0f64;
+		for species in self.species.iter() {
+			let (indexp, indexc) = species.constituents[0];
+			let (stoichiometry, _) = engine.tqstpc(indexp, indexc)?;
+			if components.is_empty() {components = vec![0.0; stoichiometry.len()];}
+			for (total, amount) in components.iter_mut().zip(stoichiometry.iter()) {
+				*total += species.coefficient*amount;
+				scale = scale.max((species.coefficient*amount).abs());
+			}
+			charge += species.coefficient*engine.tqchar(indexp, indexc)? as f64;
+		}
+		let names : Vec<String> = self.calculator.components().map(|c| c.name()).collect();
+		let unbalanced : Vec<String> = components.iter().zip(names.iter()).filter(|(total, _)| total.abs() > BALANCE_TOLERANCE*scale).map(|(total, name)| format!("{} {:+}", name.trim(), total)).collect();
+		if !unbalanced.is_empty() {
+			return Err(ChemAppError::OtherError(format!("Reaction '{}' is not balanced: {}", self.equation, unbalanced.join(", "))));
+		}
+		if charge.abs() > BALANCE_TOLERANCE*scale {
+			return Err(ChemAppError::OtherError(format!("Reaction '{}' is not balanced in charge: {:+}", self.equation, charge)));
+		}
+		return Ok(());
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Split a side of an equation into its terms at the `+` signs which are not charges: `2Fe+1.5O2`, `Na+ + Cl-`.
+fn split_terms(side: &str)->Vec<&str>{
+	let mut terms : Vec<&str> = Vec::new();
+	let mut depth = 0usize;
+	let mut start = 0usize;
+	for (i, c) in side.char_indices() {
+		match c {
+			'[' => {depth += 1;}
+			']' => {depth = depth.saturating_sub(1);}
+			'+' if depth == 0 => {
+				let after_species = side[start..i].chars().last().map(|p| !p.is_whitespace()).unwrap_or(false);
+				let rest = side[i+1..].trim_start_matches(|d: char| d.is_ascii_digit());
+				let charge = rest.is_empty() || rest.starts_with(|d: char| d.is_whitespace() || d == '[' || d == '+');
+				if !(after_species && charge) {
+					terms.push(&side[start..i]);
+					start = i + 1;
+				}
+			}
+			_ => {}
+		}
+	}
+	terms.push(&side[start..]);
+	return terms.into_iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+}
+
+/// Split a term into the coefficient (1 if missing), the species and the optional phase: `1.5 O2`, `2Fe`, `Fe[BCC_A2]`.
+fn parse_term(term: &str)->Result<(f64,&str,Option<&str>),ChemAppError>{
+	let split = term.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(term.len());
+	let coefficient = if split == 0 {1.0} else {
+		term[..split].parse::<f64>().map_err(|_| ChemAppError::OtherError(format!("Invalid coefficient in '{}'", term)))?
+	};
+	if !(coefficient.is_finite() && coefficient > 0.0) {
+		return Err(ChemAppError::OtherError(format!("Coefficient must be positive in '{}'", term)));
+	}
+	let species = term[split..].trim();
+	let (name, phase) = match (species.find('['), species.ends_with(']')) {
+		(Some(open), true) => (species[..open].trim(), Some(species[open+1..species.len()-1].trim())),
+		(None, false) => (species, None),
+		_ => {return Err(ChemAppError::OtherError(format!("Invalid phase qualifier in '{}'", term)));}
+	};
+	if name.is_empty() {
+		return Err(ChemAppError::OtherError(format!("Missing species in '{}'", term)));
+	}
+	return Ok((coefficient, name, phase));
+}
+
+/// The constituents matching a species: by name, or else by the stoichiometry of the formula; all of them with the stoichiometry of the first one.
+fn match_species<B: ChemAppBackend>(calculator: &Calculator<B>, name: &str, phase: Option<&str>)->Result<Vec<(usize,usize)>,ChemAppError>{
+	let engine = &calculator.engine;
+	let phases : Vec<usize> = match phase {
+		Some(phase) => vec![calculator.phase_index(phase)?],
+		None => (1..=engine.tqnop()?).collect(),
+	};
+	let mut constituents : Vec<(usize,usize)> = Vec::new();
+	for &indexp in phases.iter() {
+		for indexc in 1..=engine.tqnopc(indexp)? {
+			if engine.tqgnpc(indexp, indexc)?.trim() == name {constituents.push((indexp, indexc));}
+		}
+	}
+	if constituents.is_empty() {
+		let components : Vec<String> = calculator.components().map(|c| c.name()).collect();
+		let basis : Vec<String> = vec![name.to_string()];
+		let transform = Transform::new(&components, &basis, true).map_err(|e| ChemAppError::OtherError(format!("{} is neither a constituent name nor a formula of the system components {:?}: {:?}", name, components, e)))?;
+		let target : Vec<f64> = transform.transform_final2init(&dvector![1.0], false, false, false).column(0).iter().cloned().collect();
+		for &indexp in phases.iter() {
+			for indexc in 1..=engine.tqnopc(indexp)? {
+				if same_stoichiometry(&engine.tqstpc(indexp, indexc)?.0, &target) {constituents.push((indexp, indexc));}
+			}
+		}
+	}
+	if let Some(&(indexp, indexc)) = constituents.first() {
+		let first = engine.tqstpc(indexp, indexc)?.0;
+		let mut matching : Vec<(usize,usize)> = Vec::with_capacity(constituents.len());
+		for (indexp, indexc) in constituents.into_iter() {
+			if same_stoichiometry(&engine.tqstpc(indexp, indexc)?.0, &first) {matching.push((indexp, indexc));}
+		}
+		return Ok(matching);
+	}
+	return Err(ChemAppError::OtherError(format!("No phase constituent matches {}{}", name, phase.map(|p| format!(" in {}", p)).unwrap_or_default())));
+}
+
+fn same_stoichiometry(a: &[f64], b: &[f64])->bool {
+	return a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() <= BALANCE_TOLERANCE*x.abs().max(y.abs()).max(1.0));
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::{MockBackend, MockPhase, MockConstituent};
+
+	/// Fe-O: an O2 gas, solid and liquid Fe melting at 1000 K (no constituent named Fe, matched by formula) and Fe2O3; with `ions` also an aqueous phase holding Fe+2.
+	/// The Gibbs energies are in J/mol and entered as G/RT.
+	fn iron(ions: bool)->Calculator<MockBackend> {
+		let mut backend = MockBackend::new()
+			.component("Fe", 55.845)
+			.component("O", 15.999)
+			.phase(MockPhase::new("GAS", "IDMX").constituent(MockConstituent::new("O2", &[0.0, 2.0])))
+			.phase(MockPhase::pure("FE(S)", &[1.0, 0.0]))
+			.phase(MockPhase::pure("FE(L)", &[1.0, 0.0]))
+			.phase(MockPhase::pure("FE2O3(S)", &[2.0, 3.0]))
+			.standard_property("G", 3, 1, |t| (10000.0 - 10.0*t)/(R*t))
+			.standard_property("G", 4, 1, |t| (-8.2e5 + 250.0*t)/(R*t))
+			.standard_property("S", 4, 1, |_| -250.0/R);
+		if ions {
+			backend = backend.phase(MockPhase::new("AQUEOUS", "IDMX").constituent(MockConstituent::new("Fe+2", &[1.0, 0.0]).charge(2)));
+		}
+		return Calculator::from_backend(backend).unwrap();
+	}
+
+	#[test]
+	fn species_are_matched_by_name_and_formula(){
+		let calculator = iron(false);
+		let reaction = calculator.reaction("4 Fe + 3 O2 = 2 FE2O3(S)").unwrap();
+		let phases : Vec<Vec<String>> = reaction.species().iter().map(|s| s.phases.clone()).collect();
+		assert_eq!(phases, vec![vec!["FE(S)".to_string(), "FE(L)".to_string()], vec!["GAS".to_string()], vec!["FE2O3(S)".to_string()]]);
+		assert_eq!(reaction.species().iter().map(|s| s.coefficient).collect::<Vec<f64>>(), vec![-4.0, -3.0, 2.0]);
+		let qualified = calculator.reaction("4 Fe[FE(S)] + 3 O2 = 2 FE2O3(S)").unwrap();
+		assert_eq!(qualified.species()[0].phases, vec!["FE(S)".to_string()]);
+		assert!(calculator.reaction("4 Fe[GAS] + 3 O2 = 2 FE2O3(S)").is_err());
+		assert!(calculator.reaction("Xx + O2 = FE2O3(S)").is_err());
+		assert!(calculator.reaction("Fe + O2").is_err());
+	}
+
+	#[test]
+	fn unbalanced_reactions_fail(){
+		let calculator = iron(false);
+		let error = calculator.reaction("Fe + O2 = FE2O3(S)").unwrap_err();
+		assert!(format!("{:?}", error).contains("is not balanced"));
+		let calculator = iron(true);
+		let error = calculator.reaction("Fe[FE(S)] = Fe+2[AQUEOUS]").unwrap_err();
+		assert!(format!("{:?}", error).contains("is not balanced in charge"));
+	}
+
+	#[test]
+	fn gibbs_energy_and_equilibrium_constant(){
+		let calculator = iron(false);
+		let reaction = calculator.reaction("4 Fe + 3 O2 = 2 FE2O3(S)").unwrap();
+		let table = reaction.table(&[800.0, 1200.0]).unwrap();
+		// solid iron below the melting point, liquid above
+		assert_eq!(table[0].phases, vec!["FE(S)".to_string(), "GAS".to_string(), "FE2O3(S)".to_string()]);
+		assert_eq!(table[1].phases, vec!["FE(L)".to_string(), "GAS".to_string(), "FE2O3(S)".to_string()]);
+		for properties in table.iter() {
+			let t = properties.temperature;
+			let expected = 2.0*(-8.2e5 + 250.0*t) - 4.0*(10000.0 - 10.0*t).min(0.0);
+			assert!((properties.delta_g - expected).abs() < 1e-6*expected.abs());
+			assert!((properties.log_k + expected/(R*t*std::f64::consts::LN_10)).abs() < 1e-9*properties.log_k.abs());
+			assert!((properties.delta_s + 500.0).abs() < 1e-9);
+		}
+		assert!(reaction.properties(0.0).is_err());
+	}
+
+	#[test]
+	fn properties_remove_the_temperature_condition_only(){
+		let calculator = iron(false);
+		calculator.engine.set_condition(Condition::T, 0, 0, 1500.0).unwrap();
+		calculator.engine.set_condition(Condition::IA, 0, 1, 1.0).unwrap();
+		calculator.reaction("4 Fe + 3 O2 = 2 FE2O3(S)").unwrap().properties(800.0).unwrap();
+		assert_eq!(calculator.engine.conditions(), vec![("IA".to_string(), 0, 1, 1.0)]);
+	}
+
+	#[test]
+	fn terms_with_and_without_spaces(){
+		assert_eq!(split_terms("2 Fe + 1.5 O2"), vec!["2 Fe", "1.5 O2"]);
+		assert_eq!(split_terms("2Fe+1.5O2"), vec!["2Fe", "1.5O2"]);
+		assert_eq!(split_terms("Fe[BCC_A2]+O2"), vec!["Fe[BCC_A2]", "O2"]);
+		assert_eq!(split_terms(" Fe2O3 "), vec!["Fe2O3"]);
+	}
+
+	#[test]
+	fn charges_are_kept(){
+		assert_eq!(split_terms("Na+ + Cl-"), vec!["Na+", "Cl-"]);
+		assert_eq!(split_terms("Na++Cl-"), vec!["Na+", "Cl-"]);
+		assert_eq!(split_terms("Fe+2 + 2e-"), vec!["Fe+2", "2e-"]);
+		assert_eq!(split_terms("Fe+2[AQUEOUS]+O2"), vec!["Fe+2[AQUEOUS]", "O2"]);
+		assert_eq!(split_terms("2Fe+2O2"), vec!["2Fe", "2O2"]);
+	}
+
+	#[test]
+	fn term_coefficients_and_phases(){
+		assert_eq!(parse_term("1.5 O2").unwrap(), (1.5, "O2", None));
+		assert_eq!(parse_term("2Fe").unwrap(), (2.0, "Fe", None));
+		assert_eq!(parse_term("Fe2O3").unwrap(), (1.0, "Fe2O3", None));
+		assert_eq!(parse_term("Fe[BCC_A2]").unwrap(), (1.0, "Fe", Some("BCC_A2")));
+		assert_eq!(parse_term("0.5 Fe [ LIQUID ]").unwrap(), (0.5, "Fe", Some("LIQUID")));
+		assert!(parse_term("0 Fe").is_err());
+		assert!(parse_term("1.2.3 Fe").is_err());
+		assert!(parse_term("2").is_err());
+		assert!(parse_term("Fe[BCC_A2").is_err());
+	}
+
+	#[test]
+	fn stoichiometry_comparison(){
+		assert!(same_stoichiometry(&[2.0, 3.0], &[2.0, 3.0 + 1e-12]));
+		assert!(same_stoichiometry(&[0.0, 1e-12], &[0.0, 0.0]));
+		assert!(!same_stoichiometry(&[2.0, 3.0], &[2.0, 3.001]));
+		assert!(!same_stoichiometry(&[1.0, 0.0], &[1.0]));
+		assert!(same_stoichiometry(&[1e6, 1.0], &[1e6 + 1e-4, 1.0]));
+	}
+
+}
@@ -0,0 +1,96 @@
+// chemapp_rs::result.rs
+
+//! `EquilibriumResult` is returned by the `Calculator::calculate_*` and mapping functions: a snapshot of the calculated state together with the inputs, the target value found, the iteration count and the elapsed time,
+//! so that results can be stored and compared without querying the engine again.
+
+use std::time::{Duration};
+use nalgebra::{DVector};
+use serde::{Serialize, Deserialize};
+
+use crate::snapshot::{CalculatorSnapshot};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The kind of calculation a result comes from.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Serialize,Deserialize)]
+pub enum CalculationKind {
+	/// a no-target calculation at a given temperature
+	Isothermal,
+	/// a temperature-target calculation (formation or precipitation of a phase)
+	TargetT,
+	/// a composition-target calculation
+	TargetX,
+	/// a calculation with the conditions set so far (`tqcel`)
+	Listed,
+	/// a calculation starting from the previous results (`tqcenl`)
+	ListedNext,
+	/// a point of a one-dimensional temperature phase map
+	MappingT,
+	/// a point of a one-dimensional pressure phase map
+	MappingP,
+}
+
+/// The inputs of a calculation; fields not used by the calculation kind are empty.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct CalculationInputs {
+	/// composition as entered (in the transform basis)
+	pub composition : DVector<f64>,
+	/// incoming amounts of the system components passed to ChemApp
+	pub amounts     : DVector<f64>,
+	/// temperature of an isothermal calculation
+	pub temperature : Option<f64>,
+	/// limits of the target variable or of the mapping interval
+	pub interval    : Option<(f64,f64)>,
+	/// index of the target phase
+	pub target      : Option<usize>,
+}
+
+impl Default for CalculationInputs {
+	fn default()->CalculationInputs {
+		return CalculationInputs {
+			composition : DVector::zeros(0),
+			amounts     : DVector::zeros(0),
+			temperature : None,
+			interval    : None,
+			target      : None,
+		};
+	}
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The outcome of a calculation.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct EquilibriumResult {
+	/// the kind of calculation
+	pub kind       : CalculationKind,
+	/// the inputs used
+	pub inputs     : CalculationInputs,
+	/// the value of the target variable found (temperature for `TargetT` and `MappingT`, pressure for `MappingP`, the fraction of the way between the end compositions for `TargetX`)
+	pub target     : Option<f64>,
+	/// number of iterations of the fixed/adjusting component loop of `TargetT`, number of equilibrium calculations of `TargetX` (1 for single calculations)
+	pub iterations : usize,
+	/// wall time spent in the calculation
+	pub elapsed    : Duration,
+	/// the calculated state
+	pub snapshot   : CalculatorSnapshot,
+}
+
+impl EquilibriumResult {
+
+	/// equilibrium temperature
+	pub fn t(&self)->f64 {
+		return self.snapshot.system().t;
+	}
+
+	/// equilibrium pressure
+	pub fn p(&self)->f64 {
+		return self.snapshot.system().p;
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
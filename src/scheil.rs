@@ -0,0 +1,257 @@
+// chemapp_rs::scheil.rs
+
+//! Solidification paths. In the Scheil-Gulliver mode the temperature is stepped down from a liquid composition and after every equilibrium only the residual liquid (the amounts of the system components in the liquid phase) is fed into the next step,
+//! i.e. the solids formed are removed from the system (no back-diffusion); in the equilibrium mode the incoming amounts stay the same and the path follows the full equilibrium, for comparison.
+//!
+//! Fractions are calculated from the sums of the system component amounts, i.e. they are molar fractions for amounts in mol and mass fractions for amounts in mass units.
+
+use nalgebra::{DVector, Vector, Dim, Storage};
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{ResultQuantity};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The way solids are treated after every step.
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum CoolingMode {
+	/// the solids are removed, the residual liquid is cooled further
+	Scheil,
+	/// the solids stay in the system (full equilibrium)
+	Equilibrium,
+}
+
+/// Temperature stepping of `simulate`.
+#[derive(Debug,Clone)]
+pub struct ScheilOptions {
+	/// the first temperature (above the liquidus for a complete path)
+	pub start : f64,
+	/// temperature decrement per step
+	pub step : f64,
+	/// the path stops below this temperature
+	pub min_temperature : f64,
+	/// the path stops when the fraction of liquid drops below this value
+	pub min_liquid_fraction : f64,
+	/// equilibrium amount above which a phase counts as present
+	pub amount_tolerance : f64,
+}
+
+impl Default for ScheilOptions {
+	fn default()->ScheilOptions {
+		return ScheilOptions {
+			start : 2000.0,
+			step : 5.0,
+			min_temperature : 300.0,
+			min_liquid_fraction : 1e-4,
+			amount_tolerance : 1e-12,
+		};
+	}
+}
+
+/// The reason a solidification path ended.
+#[derive(Debug,Clone,PartialEq)]
+pub enum StopReason {
+	/// the liquid fraction fell below `min_liquid_fraction`
+	Solidified,
+	/// `min_temperature` was reached with liquid remaining
+	MinimumTemperature,
+	/// an equilibrium calculation failed at the temperature
+	Failed(f64, String),
+}
+
+/// The state after one temperature step.
+#[derive(Debug,Clone)]
+pub struct SolidificationStep {
+	/// temperature
+	pub temperature : f64,
+	/// fraction of solid in the initial amount
+	pub fraction_solid : f64,
+	/// fractions of the system components in the liquid
+	pub liquid_composition : Vec<f64>,
+	/// solid phases formed in this step (Scheil) or present (equilibrium), with their amounts
+	pub phases : Vec<(String,f64)>,
+	/// cumulative amounts of the solid phases formed so far (Scheil) or present (equilibrium), in the order of appearance
+	pub cumulative : Vec<(String,f64)>,
+}
+
+/// A calculated solidification path.
+#[derive(Debug,Clone)]
+pub struct SolidificationPath {
+	/// the cooling mode
+	pub mode : CoolingMode,
+	/// the liquid phase
+	pub liquid : String,
+	/// the system component names, in the order of `liquid_composition`
+	pub components : Vec<String>,
+	/// the steps, in decreasing temperature
+	pub steps : Vec<SolidificationStep>,
+	/// the solid phases in the order of formation, with the temperature of their first appearance
+	pub sequence : Vec<(String,f64)>,
+	/// why the path ended
+	pub stop : StopReason,
+}
+
+impl SolidificationPath {
+
+	/// `(temperature, fraction solid)` pairs of the path.
+	pub fn fraction_solid(&self)->Vec<(f64,f64)> {
+		return self.steps.iter().map(|s| (s.temperature, s.fraction_solid)).collect();
+	}
+
+	/// The temperature at which the first solid appears.
+	pub fn liquidus(&self)->Option<f64> {
+		return self.sequence.first().map(|s| s.1);
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// Cool `composition` (in the transform basis) from `options.start` with the liquid phase `liquid`.
+pub fn simulate<B: ChemAppBackend, D: Dim, S: Storage<f64,D>>(calculator: &Calculator<B>, composition: &Vector<f64,D,S>, liquid: &str, mode: CoolingMode, options: &ScheilOptions)->Result<SolidificationPath,ChemAppError>{
+	if !(options.step.is_finite() && options.step > 0.0) {
+		return Err(ChemAppError::OtherError(format!("Temperature step must be positive, got {}", options.step)));
+	}
+	if !(options.start.is_finite() && options.start > options.min_temperature && options.min_temperature > 0.0) {
+		return Err(ChemAppError::OtherError(format!("Start temperature must be above the minimal temperature > 0, got {} and {}", options.start, options.min_temperature)));
+	}
+	let engine = &calculator.engine;
+	let indexl = calculator.phase_index(liquid)?;
+	let components : Vec<String> = calculator.components().map(|c| c.name()).collect();
+	let initial : DVector<f64> = calculator.transform.transform_final2init(composition, false, false, false).column(0).into_owned();
+	let total = initial.sum();
+	if !(total > 0.0) {
+		return Err(ChemAppError::OtherError("No incoming amounts entered".to_string()));
+	}
+	let mut amounts = initial.clone();
+	let mut steps : Vec<SolidificationStep> = Vec::new();
+	let mut sequence : Vec<(String,f64)> = Vec::new();
+	let mut cumulative : Vec<(String,f64)> = Vec::new();
+	let mut temperature = options.start;
+	let stop = loop {
+		if temperature < options.min_temperature {
+			break StopReason::MinimumTemperature;
+		}
+		if let Err(e) = calculator.calculate_isothermal_(&amounts, temperature) {
+			break StopReason::Failed(temperature, e.to_string());
+		}
+		// solid phases; a gas phase is always phase 1 with the ideal mixing model in ChemApp
+		let mut phases : Vec<(String,f64)> = Vec::new();
+		for indexp in (1..=engine.tqnop()?).filter(|p| *p != indexl) {
+			if indexp == 1 && engine.tqmodl(indexp)?.trim() == "IDMX" {continue;}
+			let amount = engine.get_result(ResultQuantity::A, indexp, 0)?;
+			if amount > options.amount_tolerance {
+				phases.push((engine.tqgnp(indexp)?.trim().to_string(), amount));
+			}
+		}
+		for (name, amount) in phases.iter() {
+			if !sequence.iter().any(|s| &s.0 == name) {sequence.push((name.clone(), temperature));}
+			match cumulative.iter_mut().find(|c| &c.0 == name) {
+				Some(c) => {if mode == CoolingMode::Scheil {c.1 += amount;} else {c.1 = *amount;}}
+				None => {cumulative.push((name.clone(), *amount));}
+			}
+		}
+		if mode == CoolingMode::Equilibrium {
+			for c in cumulative.iter_mut() {
+				if !phases.iter().any(|p| p.0 == c.0) {c.1 = 0.0;}
+			}
+		}
+		// the liquid
+		let liquid_amounts : DVector<f64> = DVector::from_vec((1..=amounts.len()).map(|k| engine.get_result(ResultQuantity::AP, indexl, k)).collect::<Result<Vec<f64>,ChemAppError>>()?);
+		let liquid_total = liquid_amounts.sum();
+		let liquid_composition : Vec<f64> = if liquid_total > 0.0 {liquid_amounts.iter().map(|a| a/liquid_total).collect()} else {vec![0.0; amounts.len()]};
+		let fraction_liquid = liquid_total/total;
+		steps.push(SolidificationStep {
+			temperature,
+			fraction_solid : 1.0 - fraction_liquid,
+			liquid_composition,
+			phases,
+			cumulative : cumulative.clone(),
+		});
+		if fraction_liquid < options.min_liquid_fraction {
+			break StopReason::Solidified;
+		}
+		if mode == CoolingMode::Scheil {
+			amounts = liquid_amounts;
+		}
+		temperature -= options.step;
+	};
+	return Ok(SolidificationPath {
+		mode,
+		liquid : liquid.to_string(),
+		components,
+		steps,
+		sequence,
+		stop,
+	});
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::{MockBackend, MockResults};
+	use crate::backend::mock::{cu_ni_with};
+
+	/// Cu-Ni solidifying between 1500 and 1400 K: FCC_A1 takes the fraction (1500 - T)/100 of every incoming amount, the liquid the rest.
+	fn solidifying()->Calculator<MockBackend> {
+		let backend = cu_ni_with(|input| {
+			let amounts = input.incoming_amounts(2);
+			let solid = ((1500.0 - input.condition("T", 0, 0).unwrap_or(0.0))/100.0).clamp(0.0, 1.0);
+			let mut results = MockResults::new();
+			results.insert(("A".to_owned(), 1, 0), (1.0 - solid)*amounts.iter().sum::<f64>());
+			results.insert(("A".to_owned(), 2, 0), solid*amounts.iter().sum::<f64>());
+			for k in 0..2 {
+				results.insert(("AP".to_owned(), 1, k+1), (1.0 - solid)*amounts[k]);
+				results.insert(("AP".to_owned(), 2, k+1), solid*amounts[k]);
+			}
+			return Ok(results);
+		});
+		return Calculator::from_backend(backend).unwrap();
+	}
+
+	fn path(mode: CoolingMode)->SolidificationPath {
+		let options = ScheilOptions {start: 1550.0, step: 25.0, min_temperature: 1000.0, ..ScheilOptions::default()};
+		return simulate(&solidifying(), &DVector::from_vec(vec![0.4, 0.6]), "LIQUID", mode, &options).unwrap();
+	}
+
+	fn close(a: f64, b: f64)->bool {
+		return (a - b).abs() < 1e-9;
+	}
+
+	#[test]
+	fn scheil_path_removes_the_solids(){
+		let path = path(CoolingMode::Scheil);
+		assert_eq!(path.stop, StopReason::Solidified);
+		assert_eq!(path.sequence, vec![("FCC_A1".to_string(), 1475.0)]);
+		assert_eq!(path.liquidus(), Some(1475.0));
+		let expected = [(1550.0, 0.0), (1525.0, 0.0), (1500.0, 0.0), (1475.0, 0.25), (1450.0, 0.625), (1425.0, 0.90625), (1400.0, 1.0)];
+		let fraction_solid = path.fraction_solid();
+		assert_eq!(fraction_solid.len(), expected.len());
+		assert!(fraction_solid.iter().zip(expected.iter()).all(|(a, b)| a.0 == b.0 && close(a.1, b.1)), "{:?}", fraction_solid);
+		// the solids formed at every step add up to the initial amount
+		let last = path.steps.last().unwrap();
+		assert!(close(last.cumulative[0].1, 1.0));
+		assert!(close(last.liquid_composition.iter().sum::<f64>(), 0.0));
+		assert!(close(path.steps[4].liquid_composition[1], 0.6));
+	}
+
+	#[test]
+	fn equilibrium_path_keeps_the_solids(){
+		let path = path(CoolingMode::Equilibrium);
+		assert_eq!(path.stop, StopReason::Solidified);
+		assert_eq!(path.sequence, vec![("FCC_A1".to_string(), 1475.0)]);
+		let expected = [0.0, 0.0, 0.0, 0.25, 0.5, 0.75, 1.0];
+		assert!(path.steps.iter().zip(expected.iter()).all(|(s, f)| close(s.fraction_solid, *f)));
+		assert!(close(path.steps[4].cumulative[0].1, 0.5));
+		assert_eq!(path.steps[4].phases.len(), 1);
+	}
+
+}
@@ -1,10 +1,12 @@
 // chemapp_rs::snapshot::bond.rs
 //! Chemical bond (quadruplet) snapshot.
 
+use serde::{Serialize, Deserialize};
 use crate::entities::bond::Bond;
+use crate::backend::ChemAppBackend;
 
 /// A state snapshot representing a quasichemical quadruplet.
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
 pub struct BondSnapshot {
 	pub indexp   : usize,
 	pub indexs1  : usize,
@@ -21,7 +23,7 @@ pub struct BondSnapshot {
 impl BondSnapshot {
 	
 	/// create a new instance
-	pub fn new(bond: &Bond)->Self {
+	pub fn new<B: ChemAppBackend>(bond: &Bond<'_, B>)->Self {
 		return Self {
 			indexp   : bond.indexp,
 			indexs1  : bond.indexs1,
@@ -1,13 +1,15 @@
 // chemapp_rs::snapshot::component.rs
 //! System component snapshot : index, name + calculated properties
 
+use serde::{Serialize, Deserialize};
 use crate::entities::component::SystemComponent;
+use crate::backend::ChemAppBackend;
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
 /// A state snapshot of a system component
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
 pub struct SystemComponentSnapshot {
 	pub name : String,
 	pub ia   : f64,
@@ -19,7 +21,7 @@ pub struct SystemComponentSnapshot {
 impl SystemComponentSnapshot {
 	
 	/// create a new instance
-	pub fn new(component: &SystemComponent)->Self {
+	pub fn new<B: ChemAppBackend>(component: &SystemComponent<'_, B>)->Self {
 		return SystemComponentSnapshot {
 			name : component.name(),
 			ia   : component.ia(),
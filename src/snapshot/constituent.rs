@@ -1,13 +1,15 @@
 // chemapp_rs::snapshot::ConstituentSnapshot
 //! A state snapshot of a phase constituent.
 
+use serde::{Serialize, Deserialize};
 use crate::entities::constituent::Constituent;
+use crate::backend::ChemAppBackend;
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
 /// A state snapshot of a phase constituent
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
 pub struct ConstituentSnapshot {
 	pub indexp : usize,
 	pub index  : usize,
@@ -32,7 +34,7 @@ pub struct ConstituentSnapshot {
 impl ConstituentSnapshot {
 	
 	/// create a new instance
-	pub fn new(constituent: &Constituent)->Self {
+	pub fn new<B: ChemAppBackend>(constituent: &Constituent<'_, B>)->Self {
 		return Self {
 			indexp : constituent.indexp,
 			index  : constituent.index,
@@ -1,5 +1,5 @@
 // Calculator state snapshot
-//! Implementations of snapshot entities, which are not connected to an `Engine` instance and serve to keep a copy of a calculated state.
+//! Implementations of snapshot entities, which are not connected to an `Engine` (or any other `ChemAppBackend`) instance and serve to keep a copy of a calculated state.
 
 pub mod system;
 pub mod component;
@@ -8,7 +8,9 @@ pub mod constituent;
 pub mod species;
 pub mod bond;
 
+use serde::{Serialize, Deserialize};
 use crate::calculator::Calculator;
+use crate::backend::ChemAppBackend;
 pub use system::SystemSnapshot;
 pub use component::SystemComponentSnapshot;
 pub use phase::PhaseSnapshot;
@@ -20,7 +22,7 @@ pub use bond::BondSnapshot;
 /**********************************************************************************************************************/
 
 /// A snapshot of a calculator state
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
 pub struct CalculatorSnapshot {
 	system     : SystemSnapshot,
 	components : Vec<SystemComponentSnapshot>,
@@ -30,7 +32,7 @@ pub struct CalculatorSnapshot {
 impl CalculatorSnapshot {
 	
 	/// create a new instance
-	pub fn new(calculator: &Calculator)->Self {
+	pub fn new<B: ChemAppBackend>(calculator: &Calculator<B>)->Self {
 		let system = calculator.system().snapshot();
 		let components : Vec<SystemComponentSnapshot> = calculator.components().map(|c| c.snapshot()).collect();
 		let phases : Vec<PhaseSnapshot> = calculator.phases().map(|p| p.snapshot()).collect();
@@ -41,6 +43,31 @@ impl CalculatorSnapshot {
 		};
 	}
 	
+	/// global system properties
+	pub fn system(&self)->&SystemSnapshot {
+		return &self.system;
+	}
+	
+	/// system components in the index order
+	pub fn components(&self)->&[SystemComponentSnapshot] {
+		return &self.components;
+	}
+	
+	/// phases in the index order
+	pub fn phases(&self)->&[PhaseSnapshot] {
+		return &self.phases;
+	}
+	
+	/// a system component by name
+	pub fn component(&self, name: &str)->Option<&SystemComponentSnapshot> {
+		return self.components.iter().find(|c| c.name == name);
+	}
+	
+	/// a phase by name
+	pub fn phase(&self, name: &str)->Option<&PhaseSnapshot> {
+		return self.phases.iter().find(|p| p.name == name);
+	}
+	
 }
 
 /**********************************************************************************************************************/
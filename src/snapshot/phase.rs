@@ -1,7 +1,9 @@
 // chemapp_rs::snapshot::phase.rs
 //! Phase snapshot : index, name + calculated properties + constituents + species + bonds
 
+use serde::{Serialize, Deserialize};
 use crate::entities::phase::Phase;
+use crate::backend::ChemAppBackend;
 use crate::snapshot::ConstituentSnapshot;
 use crate::snapshot::SpeciesSnapshot;
 use crate::snapshot::BondSnapshot;
@@ -10,7 +12,7 @@ use crate::snapshot::BondSnapshot;
 /**********************************************************************************************************************/
 
 /// A state snapshot of phase.
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
 pub struct PhaseSnapshot {
 	pub index: usize,
 	pub status : String,
@@ -37,7 +39,7 @@ pub struct PhaseSnapshot {
 impl PhaseSnapshot {
 	
 	/// create a new instance (TODO)
-	pub fn new(phase: &Phase)->Self {
+	pub fn new<B: ChemAppBackend>(phase: &Phase<'_, B>)->Self {
 		return Self {
 			index     : phase.index,
 			status    : phase.status(),
@@ -1,10 +1,12 @@
 // chemapp_rs::snapshot::species.rs
 //! Chemical species snapshot
 
+use serde::{Serialize, Deserialize};
 use crate::entities::species::Species;
+use crate::backend::ChemAppBackend;
 
 /// A state snapshot of a sublattice species.
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
 pub struct SpeciesSnapshot {
 	pub indexp : usize,
 	pub indexl : usize,
@@ -16,7 +18,7 @@ pub struct SpeciesSnapshot {
 impl SpeciesSnapshot {
 	
 	/// create a new instance
-	pub fn new(species: &Species)->Self {
+	pub fn new<B: ChemAppBackend>(species: &Species<'_, B>)->Self {
 		return Self {
 			indexp : species.indexp,
 			indexl : species.indexl,
@@ -1,13 +1,15 @@
 // chemapp_rs::snapshot::system.rs
 //! Snapshot of global system properties.
 
+use serde::{Serialize, Deserialize};
 use crate::entities::system::System;
+use crate::backend::ChemAppBackend;
 
 /**********************************************************************************************************************/
 /**********************************************************************************************************************/
 
 /// A snapshot of the global system properties
-#[derive(Debug, Clone)]
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
 pub struct SystemSnapshot {
 	pub t : f64,
 	pub p : f64,
@@ -18,7 +20,7 @@ pub struct SystemSnapshot {
 impl SystemSnapshot {
 	
 	/// create a new instance.
-	pub fn new(system: &System)->Self {
+	pub fn new<B: ChemAppBackend>(system: &System<'_, B>)->Self {
 		return Self {
 			t : system.t(),
 			p : system.p(),
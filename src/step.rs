@@ -0,0 +1,316 @@
+// chemapp_rs::step.rs
+
+//! Step calculations: one input variable - temperature, pressure, the incoming amount of a system component or a phase constituent, or the mixing fraction between two compositions - is varied over a range with linear or logarithmic spacing
+//! and the equilibrium is collected at every point. Each point is started from the previous equilibrium (`tqcen`) with only the varied condition changed; after a failed point the next one starts from scratch (`tqce`).
+//! Failures are recorded per point, the step never aborts on a failed calculation.
+
+use nalgebra::{DVector, Vector, Dim, Storage};
+
+use crate::{Engine};
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{Condition, TargetVariable};
+use crate::snapshot::{CalculatorSnapshot};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The input varied by a step calculation.
+#[derive(Debug,Clone,PartialEq)]
+pub enum StepVariable {
+	/// temperature [current temperature unit]
+	Temperature,
+	/// pressure [current pressure unit]
+	Pressure,
+	/// incoming amount of a system component
+	Component(String),
+	/// incoming amount of a phase constituent `(phase, constituent)`, in addition to the base composition
+	Constituent(String, String),
+	/// fraction `x` of the mix `(1 - x)*first + x*second` of two compositions in the transform basis (the range within `[0, 1]`); replaces the base composition
+	Mix(DVector<f64>, DVector<f64>),
+}
+
+/// The distribution of the step points over the range.
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Spacing {
+	/// equal differences
+	Linear,
+	/// equal ratios (the range limits must be positive)
+	Log,
+}
+
+/// One point of a step calculation.
+#[derive(Debug,Clone)]
+pub struct StepPoint {
+	/// the value of the varied variable
+	pub value  : f64,
+	/// the equilibrium, or the error of the failed calculation
+	pub result : Result<CalculatorSnapshot,ChemAppError>,
+}
+
+/// The ordered results of a step calculation.
+#[derive(Debug,Clone)]
+pub struct StepSeries {
+	/// the varied variable
+	pub variable : StepVariable,
+	/// the points in the order of the range (from the first limit to the second)
+	pub points   : Vec<StepPoint>,
+}
+
+impl StepSeries {
+
+	/// The successful points.
+	pub fn snapshots(&self)->impl Iterator<Item=(f64,&CalculatorSnapshot)> + '_ {
+		return self.points.iter().filter_map(|p| p.result.as_ref().ok().map(|s| (p.value, s)));
+	}
+
+	/// The failed points.
+	pub fn failures(&self)->impl Iterator<Item=(f64,&ChemAppError)> + '_ {
+		return self.points.iter().filter_map(|p| p.result.as_ref().err().map(|e| (p.value, e)));
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A builder of a step calculation, created by [`Calculator::step`]:
+/// `calculator.step(StepVariable::Temperature, (1000.0, 2000.0)).points(51).composition(&x).run()?`.
+/// The fixed inputs (base composition in the transform basis, temperature, pressure) apply to every point, the varied variable overrides them.
+#[derive(Debug)]
+pub struct Step<'a, B: ChemAppBackend = Engine> {
+	calculator  : &'a Calculator<B>,
+	variable    : StepVariable,
+	range       : (f64,f64),
+	points      : usize,
+	spacing     : Spacing,
+	composition : Option<DVector<f64>>,
+	temperature : Option<f64>,
+	pressure    : Option<f64>,
+	warm_start  : bool,
+}
+
+impl<'a, B: ChemAppBackend> Step<'a, B> {
+
+	/// A linear step over `range` with 11 points, warm-started.
+	pub fn new(calculator: &'a Calculator<B>, variable: StepVariable, range: (f64,f64))->Self {
+		return Self {
+			calculator,
+			variable,
+			range,
+			points      : 11,
+			spacing     : Spacing::Linear,
+			composition : None,
+			temperature : None,
+			pressure    : None,
+			warm_start  : true,
+		};
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// number of points including both range limits
+	pub fn points(mut self, points: usize)->Self {
+		self.points = points;
+		return self;
+	}
+
+	/// spacing of the points
+	pub fn spacing(mut self, spacing: Spacing)->Self {
+		self.spacing = spacing;
+		return self;
+	}
+
+	/// base composition in the transform basis
+	pub fn composition<D: Dim, S: Storage<f64,D>>(mut self, composition: &Vector<f64,D,S>)->Self {
+		self.composition = Some(DVector::from_iterator(composition.len(), composition.iter().cloned()));
+		return self;
+	}
+
+	/// fixed temperature [current temperature unit]
+	pub fn temperature(mut self, value: f64)->Self {
+		self.temperature = Some(value);
+		return self;
+	}
+
+	/// fixed pressure [current pressure unit]
+	pub fn pressure(mut self, value: f64)->Self {
+		self.pressure = Some(value);
+		return self;
+	}
+
+	/// start every point from the previous equilibrium (`tqcen`, the default) or from scratch (`tqce`)
+	pub fn warm_start(mut self, warm_start: bool)->Self {
+		self.warm_start = warm_start;
+		return self;
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// The values of the varied variable.
+	pub fn values(&self)->Result<Vec<f64>,ChemAppError>{
+		let (first, last) = self.range;
+		if !(first.is_finite() && last.is_finite()) {
+			return Err(ChemAppError::OtherError(format!("Step range must be finite, got ({}, {})", first, last)));
+		}
+		if self.points < 2 {
+			return Err(ChemAppError::OtherError("A step needs at least two points".to_string()));
+		}
+		let n = (self.points - 1) as f64;
+		return match self.spacing {
+			Spacing::Linear => Ok((0..self.points).map(|i| first + (last - first)*i as f64/n).collect()),
+			Spacing::Log => {
+				if !(first > 0.0 && last > 0.0) {
+					return Err(ChemAppError::OtherError(format!("Logarithmic step range must be positive, got ({}, {})", first, last)));
+				}
+				let (a, b) = (first.ln(), last.ln());
+				Ok((0..self.points).map(|i| (a + (b - a)*i as f64/n).exp()).collect())
+			}
+		};
+	}
+
+	/// Run the calculations; the errors returned are those of the inputs, failed points are recorded in the series.
+	pub fn run(&self)->Result<StepSeries,ChemAppError>{
+		let values = self.values()?;
+		let base = self.base()?;
+		let varied = self.varied()?;
+		let mut points : Vec<StepPoint> = Vec::with_capacity(values.len());
+		let mut warm = false;
+		for &value in values.iter() {
+			let result = if warm {
+				self.point_next(&varied, value)
+			} else {
+				self.point_first(&base, &varied, value)
+			};
+			warm = self.warm_start && result.is_ok();
+			points.push(StepPoint {value, result: result.map(|_| self.calculator.snapshot())});
+		}
+		return Ok(StepSeries {variable: self.variable.clone(), points});
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// incoming amounts of the system components of the base composition
+	fn base(&self)->Result<DVector<f64>,ChemAppError>{
+		let ncomp = self.calculator.engine.tqnosc()?;
+		return match &self.composition {
+			Some(composition) => {
+				if composition.len() != self.calculator.basis.len() {
+					return Err(ChemAppError::OtherError(format!("Composition has {} entries, the transform basis {:?} has {}", composition.len(), self.calculator.basis, self.calculator.basis.len())));
+				}
+				Ok(self.calculator.transform.transform_final2init(composition, false, false, false).column(0).into_owned())
+			}
+			None => Ok(DVector::zeros(ncomp)),
+		};
+	}
+
+	/// the varied variable with the names resolved
+	fn varied(&self)->Result<Varied,ChemAppError>{
+		return match &self.variable {
+			StepVariable::Temperature => Ok(Varied::Temperature),
+			StepVariable::Pressure => Ok(Varied::Pressure),
+			StepVariable::Component(name) => Ok(Varied::Component(self.calculator.component_index(name)?)),
+			StepVariable::Constituent(phase, constituent) => {
+				let indexp = self.calculator.phase_index(phase)?;
+				Ok(Varied::Constituent(indexp, self.calculator.constituent_index(indexp, constituent)?))
+			}
+			StepVariable::Mix(first, second) => {
+				let n = self.calculator.basis.len();
+				if first.len() != n || second.len() != n {
+					return Err(ChemAppError::OtherError(format!("Mix compositions must have {} entries (transform basis {:?})", n, self.calculator.basis)));
+				}
+				let first = self.calculator.transform.transform_final2init(first, false, false, false).column(0).into_owned();
+				let second = self.calculator.transform.transform_final2init(second, false, false, false).column(0).into_owned();
+				Ok(Varied::Mix(first, second))
+			}
+		};
+	}
+
+	/// enter the varied condition
+	fn set_varied(&self, varied: &Varied, value: f64)->Result<(),ChemAppError>{
+		let engine = &self.calculator.engine;
+		match varied {
+			Varied::Temperature => {engine.set_condition(Condition::T, 0, 0, value)?;}
+			Varied::Pressure => {engine.set_condition(Condition::P, 0, 0, value)?;}
+			Varied::Component(indexc) => {engine.set_condition(Condition::IA, 0, *indexc, value)?;}
+			Varied::Constituent(indexp, indexc) => {engine.set_condition(Condition::IA, *indexp, *indexc, value)?;}
+			Varied::Mix(first, second) => {
+				for k in 0..first.len() {engine.set_condition(Condition::IA, 0, k+1, (1.0 - value)*first[k] + value*second[k])?;}
+			}
+		}
+		return Ok(());
+	}
+
+	/// a calculation from scratch with all conditions entered
+	fn point_first(&self, base: &DVector<f64>, varied: &Varied, value: f64)->Result<(),ChemAppError>{
+		let engine = &self.calculator.engine;
+		self.calculator.reset()?;
+		if let Some(t) = self.temperature {engine.set_condition(Condition::T, 0, 0, t)?;}
+		if let Some(p) = self.pressure {engine.set_condition(Condition::P, 0, 0, p)?;}
+		if !matches!(varied, Varied::Mix(..)) {
+			for k in 0..base.len() {
+				if let Varied::Component(indexc) = varied {
+					if *indexc == k + 1 {continue;}
+				}
+				if base[k] != 0.0 {engine.set_condition(Condition::IA, 0, k+1, base[k])?;}
+			}
+		}
+		self.set_varied(varied, value)?;
+		engine.calculate(TargetVariable::None, 0, 0, (0.0, 0.0))?;
+		return Ok(());
+	}
+
+	/// a calculation from the previous equilibrium with only the varied condition changed
+	fn point_next(&self, varied: &Varied, value: f64)->Result<(),ChemAppError>{
+		self.set_varied(varied, value)?;
+		self.calculator.engine.calculate_next(TargetVariable::None, 0, 0, (0.0, 0.0))?;
+		return Ok(());
+	}
+
+}
+
+/// `StepVariable` with the names replaced by indices and the mix compositions by system component amounts.
+#[derive(Debug,Clone)]
+enum Varied {
+	Temperature,
+	Pressure,
+	Component(usize),
+	Constituent(usize, usize),
+	Mix(DVector<f64>, DVector<f64>),
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::mock::{calculator};
+
+	#[test]
+	fn step_keeps_the_point_order(){
+		let calculator = calculator();
+		let series = calculator.step(StepVariable::Temperature, (1000.0, 2000.0)).points(3).composition(&DVector::from_vec(vec![0.5, 0.5])).run().unwrap();
+		let values : Vec<f64> = series.points.iter().map(|p| p.value).collect();
+		assert_eq!(values, vec![1000.0, 1500.0, 2000.0]);
+		assert_eq!(series.failures().count(), 0);
+		let liquid : Vec<f64> = series.snapshots().map(|(_, s)| s.phase("LIQUID").unwrap().a).collect();
+		assert_eq!(liquid, vec![0.0, 0.0, 1.0]);
+		assert_eq!(calculator.engine.calculations(), 3);
+	}
+
+	#[test]
+	fn step_records_failed_points(){
+		let calculator = calculator();
+		// no incoming amounts: every point fails with error 508
+		let series = calculator.step(StepVariable::Temperature, (1000.0, 2000.0)).points(2).run().unwrap();
+		assert_eq!(series.failures().count(), 2);
+		assert!(series.failures().all(|(_, e)| e.code() == Some(508)));
+	}
+
+}
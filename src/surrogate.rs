@@ -0,0 +1,361 @@
+// chemapp_rs::surrogate.rs
+
+//! Precomputed property tables. A `PropertyTable` holds the results of a `GridSweep` on a rectilinear grid (strictly increasing axis values) and answers queries by multilinear or monotone cubic (Fritsch-Carlson, tensor product) interpolation
+//! without calling ChemApp. Every answer tells whether the query was clamped to the grid and whether the nodes it is interpolated from differ in the stable phase assemblage (or include a failed node),
+//! i.e. whether the interpolation spans a phase boundary and is not to be trusted.
+
+use std::fs::{File};
+use std::io::{Write, BufWriter, BufReader};
+use std::path::{Path};
+use serde::{Serialize, Deserialize};
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::diagram::{Assemblage};
+use crate::sweep::{GridSweep, SweepTable};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The interpolation scheme of `PropertyTable::interpolate`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Interpolation {
+	/// multilinear between the nodes of the grid cell
+	Linear,
+	/// monotone piecewise cubic along every axis (no overshoots), from the 4 x 4 x ... neighbouring nodes
+	MonotoneCubic,
+}
+
+/// An interpolated value with its validity flags.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Interpolated {
+	/// the interpolated value
+	pub value : f64,
+	/// the query was outside the grid and was clamped to its border
+	pub outside : bool,
+	/// the nodes the value is interpolated from (the cell corners, or the 4 x 4 x ... neighbourhood of a monotone cubic) differ in the stable phase assemblage or include a failed calculation
+	pub assemblage_change : bool,
+}
+
+/// Property values on a rectilinear grid; the values of every property are stored with the last axis varying fastest.
+#[derive(Debug,Clone,Serialize,Deserialize)]
+pub struct PropertyTable {
+	/// the axis names (`T`, `P` or basis formulas)
+	pub axes        : Vec<String>,
+	/// the node values of every axis
+	pub grids       : Vec<Vec<f64>>,
+	/// the property names
+	pub properties  : Vec<String>,
+	/// the node values of every property (`NaN` for failed nodes)
+	pub values      : Vec<Vec<f64>>,
+	/// the stable phases of every node (`None` for failed nodes)
+	pub assemblages : Vec<Option<Assemblage>>,
+}
+
+impl PropertyTable {
+
+	/// Build the table from a sweep and its results; the axis values must be strictly increasing and the table must hold the axes and the grid points of the sweep.
+	pub fn from_sweep(sweep: &GridSweep, table: &SweepTable)->Result<PropertyTable,ChemAppError>{
+		let grids : Vec<Vec<f64>> = sweep.axes().iter().map(|a| a.values().to_vec()).collect();
+		for (axis, grid) in sweep.axes().iter().zip(grids.iter()) {
+			if grid.is_empty() || grid.windows(2).any(|w| !(w[0] < w[1])) {
+				return Err(ChemAppError::OtherError(format!("The values of the axis {} must be strictly increasing", axis.name())));
+			}
+		}
+		let size : usize = grids.iter().map(|g| g.len()).product();
+		if table.len() != size || table.assemblages.len() != size || table.values.iter().any(|v| v.len() != size) {
+			return Err(ChemAppError::OtherError(format!("The sweep table has {} rows, the grid {} nodes", table.len(), size)));
+		}
+		let names : Vec<String> = sweep.axes().iter().map(|a| a.name()).collect();
+		if table.axes != names || table.inputs.len() != grids.len() {
+			return Err(ChemAppError::OtherError(format!("The sweep table has the axes {:?}, the sweep {:?}", table.axes, names)));
+		}
+		// the rows run over the grid with the last axis varying fastest
+		let mut stride = size;
+		for ((name, grid), column) in names.iter().zip(grids.iter()).zip(table.inputs.iter()) {
+			stride /= grid.len();
+			if column.len() != size || column.iter().enumerate().any(|(row, x)| *x != grid[(row/stride) % grid.len()]) {
+				return Err(ChemAppError::OtherError(format!("The values of the axis {} in the sweep table differ from the sweep", name)));
+			}
+		}
+		return Ok(PropertyTable {
+			axes        : table.axes.clone(),
+			grids,
+			properties  : table.outputs.clone(),
+			values      : table.values.clone(),
+			assemblages : table.assemblages.clone(),
+		});
+	}
+
+	/// Run the sweep on a calculator and build the table.
+	pub fn calculate<B: ChemAppBackend>(calculator: &Calculator<B>, sweep: &GridSweep)->Result<PropertyTable,ChemAppError>{
+		return PropertyTable::from_sweep(sweep, &sweep.run(calculator)?);
+	}
+
+	/// Write the table in the binary form (bincode) into a file.
+	pub fn save<P: AsRef<Path>>(&self, path: P)->Result<(),ChemAppError>{
+		let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+		bincode::serde::encode_into_std_write(self, &mut writer, bincode::config::standard()).map_err(|e| ChemAppError::OtherError(format!("Cannot encode the property table: {}", e)))?;
+		return writer.flush().map_err(io_error);
+	}
+
+	/// Read a table written by `save`.
+	pub fn load<P: AsRef<Path>>(path: P)->Result<PropertyTable,ChemAppError>{
+		let mut reader = BufReader::new(File::open(path).map_err(io_error)?);
+		return bincode::serde::decode_from_std_read(&mut reader, bincode::config::standard()).map_err(|e| ChemAppError::OtherError(format!("Cannot decode the property table: {}", e)));
+	}
+
+	/// the index of a property by name
+	pub fn property_index(&self, name: &str)->Result<usize,ChemAppError>{
+		return self.properties.iter().position(|p| p == name).ok_or(ChemAppError::OtherError(format!("Property {} not in the table {:?}", name, self.properties)));
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// Interpolate the property `name` at `point` (one coordinate per axis).
+	pub fn interpolate(&self, name: &str, point: &[f64], method: Interpolation)->Result<Interpolated,ChemAppError>{
+		let property = self.property_index(name)?;
+		return Ok(self.interpolate_all(point, method)?.swap_remove(property));
+	}
+
+	/// Interpolate all properties at `point`, in the order of `properties`.
+	pub fn interpolate_all(&self, point: &[f64], method: Interpolation)->Result<Vec<Interpolated>,ChemAppError>{
+		if point.len() != self.grids.len() {
+			return Err(ChemAppError::OtherError(format!("The query has {} coordinates, the table {} axes {:?}", point.len(), self.grids.len(), self.axes)));
+		}
+		if point.iter().any(|x| !x.is_finite()) {
+			return Err(ChemAppError::OtherError(format!("The query {:?} is not finite", point)));
+		}
+		let mut outside = false;
+		let cells : Vec<(usize,f64)> = self.grids.iter().zip(point.iter()).map(|(grid, &x)| {
+			let (first, last) = (grid[0], grid[grid.len()-1]);
+			if x < first || x > last {outside = true;}
+			let x = x.clamp(first, last);
+			let i = grid.partition_point(|g| *g <= x).saturating_sub(1).min(grid.len().saturating_sub(2));
+			return (i, x);
+		}).collect();
+		let assemblage_change = self.stencil_changes(&cells, method);
+		let mut index = vec![0usize; self.grids.len()];
+		return Ok((0..self.properties.len()).map(|property| Interpolated {
+			value : self.along(property, 0, &mut index, &cells, method),
+			outside,
+			assemblage_change,
+		}).collect());
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// the flat index of a node
+	fn flat(&self, index: &[usize])->usize {
+		return self.grids.iter().zip(index.iter()).fold(0, |flat, (grid, i)| flat*grid.len() + i);
+	}
+
+	/// the node indices along the axis `dim` which `method` reads for the cell `i`
+	fn stencil(&self, dim: usize, i: usize, method: Interpolation)->Vec<usize> {
+		let grid = &self.grids[dim];
+		if grid.len() == 1 {
+			return vec![0];
+		}
+		return match method {
+			Interpolation::Linear => vec![i, i+1],
+			Interpolation::MonotoneCubic => (i.saturating_sub(1)..=(i+2).min(grid.len()-1)).collect(),
+		};
+	}
+
+	/// `true` if the nodes read by `method` differ in the assemblage or include a failed node
+	fn stencil_changes(&self, cells: &[(usize,f64)], method: Interpolation)->bool {
+		let corners : Vec<Vec<usize>> = cells.iter().enumerate().map(|(dim, (i, _))| self.stencil(dim, *i, method)).collect();
+		let mut first : Option<&Option<Assemblage>> = None;
+		let mut index = vec![0usize; corners.len()];
+		let count : usize = corners.iter().map(|c| c.len()).product();
+		for n in 0..count {
+			let mut rest = n;
+			for k in (0..corners.len()).rev() {
+				index[k] = corners[k][rest % corners[k].len()];
+				rest /= corners[k].len();
+			}
+			let assemblage = &self.assemblages[self.flat(&index)];
+			if assemblage.is_none() {return true;}
+			match first {
+				None => {first = Some(assemblage);}
+				Some(a) => {if a != assemblage {return true;}}
+			}
+		}
+		return false;
+	}
+
+	/// interpolate along the axis `dim` and all following ones, the preceding node indices fixed in `index`
+	fn along(&self, property: usize, dim: usize, index: &mut Vec<usize>, cells: &[(usize,f64)], method: Interpolation)->f64 {
+		if dim == self.grids.len() {
+			return self.values[property][self.flat(index)];
+		}
+		let grid = &self.grids[dim];
+		let (i, x) = cells[dim];
+		if grid.len() == 1 {
+			index[dim] = 0;
+			return self.along(property, dim + 1, index, cells, method);
+		}
+		let nodes = self.stencil(dim, i, method);
+		let ys : Vec<f64> = nodes.iter().map(|&j| {index[dim] = j; self.along(property, dim + 1, index, cells, method)}).collect();
+		let xs : Vec<f64> = nodes.iter().map(|&j| grid[j]).collect();
+		let k = nodes.iter().position(|&j| j == i).unwrap_or(0);
+		return match method {
+			Interpolation::Linear => {
+				let t = (x - xs[0])/(xs[1] - xs[0]);
+				ys[0] + t*(ys[1] - ys[0])
+			}
+			Interpolation::MonotoneCubic => monotone_cubic(&xs, &ys, k, x),
+		};
+	}
+
+}
+
+/// The Fritsch-Carlson slope at the node between the secants `d0` (interval `h0`) and `d1` (interval `h1`): zero at a local extremum, a weighted harmonic mean otherwise.
+fn slope(h0: f64, d0: f64, h1: f64, d1: f64)->f64 {
+	if d0*d1 <= 0.0 {
+		return 0.0;
+	}
+	return 3.0*(h0 + h1)/((2.0*h1 + h0)/d0 + (h1 + 2.0*h0)/d1);
+}
+
+/// Monotone cubic Hermite interpolation at `x` in the interval `(xs[k], xs[k+1])` of the nodes `xs`, `ys`.
+fn monotone_cubic(xs: &[f64], ys: &[f64], k: usize, x: f64)->f64 {
+	let n = xs.len();
+	let h : Vec<f64> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+	let d : Vec<f64> = (0..n-1).map(|j| (ys[j+1] - ys[j])/h[j]).collect();
+	let tangent = |j: usize|->f64 {
+		if j == 0 {return d[0];}
+		if j == n - 1 {return d[n-2];}
+		return slope(h[j-1], d[j-1], h[j], d[j]);
+	};
+	let (m0, m1) = (tangent(k), tangent(k+1));
+	let t = (x - xs[k])/h[k];
+	let (t2, t3) = (t*t, t*t*t);
+	return (2.0*t3 - 3.0*t2 + 1.0)*ys[k] + (t3 - 2.0*t2 + t)*h[k]*m0 + (-2.0*t3 + 3.0*t2)*ys[k+1] + (t3 - t2)*h[k]*m1;
+}
+
+fn io_error(e: std::io::Error)->ChemAppError {
+	return ChemAppError::OtherError(format!("Property table I/O error: {}", e));
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::sweep::{Axis};
+
+	/// `f(x, y) = 2x + 3y` on a 3 x 4 grid, the assemblage changing at `y > 15`
+	fn plane()->PropertyTable {
+		let grids = vec![vec![0.0, 1.0, 2.0], vec![0.0, 10.0, 20.0, 30.0]];
+		let mut values : Vec<f64> = Vec::new();
+		let mut assemblages : Vec<Option<Assemblage>> = Vec::new();
+		for x in grids[0].iter() {
+			for y in grids[1].iter() {
+				values.push(2.0*x + 3.0*y);
+				assemblages.push(Some(Assemblage(if *y > 15.0 {vec!["LIQUID".to_string()]} else {vec!["FCC".to_string()]})));
+			}
+		}
+		return PropertyTable {
+			axes        : vec!["T".to_string(), "P".to_string()],
+			grids,
+			properties  : vec!["G".to_string(), "minus G".to_string()],
+			values      : vec![values.clone(), values.iter().map(|v| -v).collect()],
+			assemblages,
+		};
+	}
+
+	#[test]
+	fn monotone_cubic_reproduces_nodes_and_lines(){
+		let xs = [0.0, 1.0, 3.0, 4.0];
+		let line : Vec<f64> = xs.iter().map(|x| 1.0 + 2.0*x).collect();
+		for k in 0..3 {
+			assert!((monotone_cubic(&xs, &line, k, xs[k]) - line[k]).abs() < 1e-12);
+			assert!((monotone_cubic(&xs, &line, k, xs[k+1]) - line[k+1]).abs() < 1e-12);
+			let x = 0.5*(xs[k] + xs[k+1]);
+			assert!((monotone_cubic(&xs, &line, k, x) - (1.0 + 2.0*x)).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn monotone_cubic_does_not_overshoot(){
+		let xs = [0.0, 1.0, 2.0, 3.0];
+		let step = [0.0, 0.0, 1.0, 1.0];
+		for i in 0..=100 {
+			let x = 1.0 + i as f64/100.0;
+			let y = monotone_cubic(&xs, &step, 1, x);
+			assert!((0.0..=1.0).contains(&y), "{} at {}", y, x);
+		}
+		// flat at a local extremum
+		let peak = [0.0, 1.0, 0.0];
+		assert!((monotone_cubic(&xs[..3], &peak, 0, 0.999) - 1.0).abs() < 1e-5);
+		assert_eq!(slope(1.0, 1.0, 1.0, -1.0), 0.0);
+	}
+
+	#[test]
+	fn interpolation_of_a_plane(){
+		let table = plane();
+		for method in [Interpolation::Linear, Interpolation::MonotoneCubic] {
+			let answers = table.interpolate_all(&[0.5, 5.0], method).unwrap();
+			assert_eq!(answers.len(), 2);
+			assert!((answers[0].value - 16.0).abs() < 1e-9);
+			assert!((answers[1].value + 16.0).abs() < 1e-9);
+			assert!(!answers[0].outside);
+			let node = table.interpolate("G", &[2.0, 30.0], method).unwrap();
+			assert!((node.value - 94.0).abs() < 1e-9);
+			assert!(!node.outside);
+		}
+	}
+
+	#[test]
+	fn interpolation_flags(){
+		let table = plane();
+		let clamped = table.interpolate("G", &[3.0, -5.0], Interpolation::Linear).unwrap();
+		assert!(clamped.outside);
+		assert!((clamped.value - 4.0).abs() < 1e-9);
+		assert!(table.interpolate("G", &[1.5, 15.0], Interpolation::Linear).unwrap().assemblage_change);
+		assert!(!table.interpolate("G", &[1.5, 25.0], Interpolation::Linear).unwrap().assemblage_change);
+		// the cubic also reads the node beyond the cell, at y = 20
+		assert!(!table.interpolate("G", &[0.5, 5.0], Interpolation::Linear).unwrap().assemblage_change);
+		assert!(table.interpolate("G", &[0.5, 5.0], Interpolation::MonotoneCubic).unwrap().assemblage_change);
+		assert!(table.interpolate("G", &[0.5, 25.0], Interpolation::MonotoneCubic).unwrap().assemblage_change);
+		let mut failed = plane();
+		failed.assemblages[0] = None;
+		assert!(failed.interpolate("G", &[0.5, 5.0], Interpolation::Linear).unwrap().assemblage_change);
+		assert!(table.interpolate_all(&[1.0], Interpolation::Linear).is_err());
+		assert!(table.interpolate_all(&[1.0, f64::NAN], Interpolation::Linear).is_err());
+		assert!(table.interpolate("H", &[1.0, 1.0], Interpolation::Linear).is_err());
+	}
+
+	#[test]
+	fn from_sweep_checks_the_table(){
+		let sweep = GridSweep::new().axis(Axis::Temperature(vec![1000.0, 2000.0])).axis(Axis::Pressure(vec![1.0, 2.0, 3.0]));
+		let table = || SweepTable {
+			axes        : vec!["T".to_string(), "P".to_string()],
+			outputs     : vec!["G".to_string()],
+			inputs      : vec![vec![1000.0, 1000.0, 1000.0, 2000.0, 2000.0, 2000.0], vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]],
+			values      : vec![vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]],
+			assemblages : vec![None; 6],
+			status      : vec![None; 6],
+		};
+		let built = PropertyTable::from_sweep(&sweep, &table()).unwrap();
+		assert_eq!(built.grids, vec![vec![1000.0, 2000.0], vec![1.0, 2.0, 3.0]]);
+		let mut renamed = table();
+		renamed.axes[1] = "SiO2".to_string();
+		assert!(PropertyTable::from_sweep(&sweep, &renamed).is_err());
+		let mut shifted = table();
+		shifted.inputs[1][4] = 2.5;
+		assert!(PropertyTable::from_sweep(&sweep, &shifted).is_err());
+		let mut missing = table();
+		missing.inputs.pop();
+		assert!(PropertyTable::from_sweep(&sweep, &missing).is_err());
+		let swapped = GridSweep::new().axis(Axis::Pressure(vec![1.0, 2.0, 3.0])).axis(Axis::Temperature(vec![1000.0, 2000.0]));
+		assert!(PropertyTable::from_sweep(&swapped, &table()).is_err());
+	}
+
+}
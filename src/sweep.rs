@@ -0,0 +1,463 @@
+// chemapp_rs::sweep.rs
+
+//! Property grids. `GridSweep` calculates the equilibrium at every point of a temperature x pressure x composition grid (the composition axes are formulas of the calculator transform basis)
+//! and collects the requested results into a columnar `SweepTable` with one row per grid point, the stable phase assemblage and a status column; failed points keep `NaN` values and the error message.
+//! The grid runs on a single calculator or in parallel on a `CalculatorPool`; the table is written as CSV or in a compact binary form (bincode).
+
+use std::fs::{File};
+use std::io::{Write, BufWriter, BufReader};
+use std::path::{Path};
+use nalgebra::{DVector, Vector, Dim, Storage};
+use serde::{Serialize, Deserialize};
+
+use crate::calculator::{Calculator};
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+use crate::options::{ChemAppOption, Condition, ResultQuantity, TargetVariable};
+use crate::pool::{CalculatorPool};
+use crate::diagram::{Assemblage};
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A grid axis and its values.
+#[derive(Debug,Clone,PartialEq)]
+pub enum Axis {
+	/// temperature [current temperature unit]
+	Temperature(Vec<f64>),
+	/// pressure [current pressure unit]
+	Pressure(Vec<f64>),
+	/// amount of a formula of the transform basis, replacing its amount in the base composition
+	Composition(String, Vec<f64>),
+}
+
+impl Axis {
+
+	/// the column name
+	pub fn name(&self)->String {
+		return match self {
+			Self::Temperature(_) => "T".to_string(),
+			Self::Pressure(_) => "P".to_string(),
+			Self::Composition(formula, _) => formula.clone(),
+		};
+	}
+
+	/// the axis values
+	pub fn values(&self)->&[f64] {
+		return match self {
+			Self::Temperature(values) | Self::Pressure(values) | Self::Composition(_, values) => values,
+		};
+	}
+
+}
+
+/// `n` equally spaced values from `first` to `last`.
+pub fn linspace(first: f64, last: f64, n: usize)->Vec<f64> {
+	if n < 2 {return vec![first; n];}
+	return (0..n).map(|i| first + (last - first)*i as f64/(n - 1) as f64).collect();
+}
+
+/// A result quantity collected at every grid point.
+#[derive(Debug,Clone,PartialEq)]
+pub enum Output {
+	/// a property of the whole system, `(0, 0)`, e.g. `ResultQuantity::H`
+	System(ResultQuantity),
+	/// a property of a system component, `(0, indexc)`, e.g. `ResultQuantity::AC`
+	Component(ResultQuantity, String),
+	/// a property of a phase, `(indexp, 0)`, e.g. `ResultQuantity::A`
+	Phase(ResultQuantity, String),
+	/// a property of a phase constituent, `(indexp, indexc)`, e.g. `ResultQuantity::X`
+	Constituent(ResultQuantity, String, String),
+}
+
+impl Output {
+
+	/// the column name, e.g. `A(LIQUID)` or `X(LIQUID,FeO)`
+	pub fn name(&self)->String {
+		return match self {
+			Self::System(q) => q.code().trim().to_string(),
+			Self::Component(q, c) => format!("{}({})", q.code().trim(), c),
+			Self::Phase(q, p) => format!("{}({})", q.code().trim(), p),
+			Self::Constituent(q, p, c) => format!("{}({},{})", q.code().trim(), p, c),
+		};
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// The results of a grid sweep, stored by columns; row `i` is the `i`-th grid point, the last axis varying fastest.
+#[derive(Debug,Clone,Default,Serialize,Deserialize)]
+pub struct SweepTable {
+	/// names of the axis columns
+	pub axes    : Vec<String>,
+	/// names of the output columns
+	pub outputs : Vec<String>,
+	/// the axis values, one column per axis
+	pub inputs  : Vec<Vec<f64>>,
+	/// the output values, one column per output (`NaN` for failed points)
+	pub values  : Vec<Vec<f64>>,
+	/// the stable phases (`None` for failed points)
+	pub assemblages : Vec<Option<Assemblage>>,
+	/// `None` for a successful point, the error message otherwise
+	pub status  : Vec<Option<String>>,
+}
+
+impl SweepTable {
+
+	/// number of rows (grid points)
+	pub fn len(&self)->usize {
+		return self.status.len();
+	}
+
+	/// `true` if the table has no rows
+	pub fn is_empty(&self)->bool {
+		return self.status.is_empty();
+	}
+
+	/// number of failed points
+	pub fn failures(&self)->usize {
+		return self.status.iter().filter(|s| s.is_some()).count();
+	}
+
+	/// the column of an axis or an output by name
+	pub fn column(&self, name: &str)->Option<&[f64]> {
+		if let Some(i) = self.axes.iter().position(|a| a == name) {
+			return Some(&self.inputs[i]);
+		}
+		return self.outputs.iter().position(|o| o == name).map(|i| self.values[i].as_slice());
+	}
+
+	/// Write the table as CSV: the axis columns, the output columns, a `phases` column and a `status` column (`ok` or the quoted error message).
+	pub fn write_csv<W: Write>(&self, writer: &mut W)->Result<(),ChemAppError>{
+		let header : Vec<String> = self.axes.iter().chain(self.outputs.iter()).map(|n| csv_field(n)).chain(["phases".to_string(), "status".to_string()]).collect();
+		writeln!(writer, "{}", header.join(",")).map_err(io_error)?;
+		for i in 0..self.len() {
+			let mut fields : Vec<String> = self.inputs.iter().chain(self.values.iter()).map(|c| format!("{:e}", c[i])).collect();
+			fields.push(self.assemblages[i].as_ref().map(|a| csv_field(&a.to_string())).unwrap_or_default());
+			fields.push(match &self.status[i] {
+				None => "ok".to_string(),
+				Some(message) => csv_field(message),
+			});
+			writeln!(writer, "{}", fields.join(",")).map_err(io_error)?;
+		}
+		return Ok(());
+	}
+
+	/// Write the table as CSV into a file.
+	pub fn save_csv<P: AsRef<Path>>(&self, path: P)->Result<(),ChemAppError>{
+		let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+		self.write_csv(&mut writer)?;
+		return writer.flush().map_err(io_error);
+	}
+
+	/// Write the table in the binary form into a file.
+	pub fn save_binary<P: AsRef<Path>>(&self, path: P)->Result<(),ChemAppError>{
+		let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+		bincode::serde::encode_into_std_write(self, &mut writer, bincode::config::standard()).map_err(|e| ChemAppError::OtherError(format!("Cannot encode the sweep table: {}", e)))?;
+		return writer.flush().map_err(io_error);
+	}
+
+	/// Read a table written by `save_binary`.
+	pub fn load_binary<P: AsRef<Path>>(path: P)->Result<SweepTable,ChemAppError>{
+		let mut reader = BufReader::new(File::open(path).map_err(io_error)?);
+		return bincode::serde::decode_from_std_read(&mut reader, bincode::config::standard()).map_err(|e| ChemAppError::OtherError(format!("Cannot decode the sweep table: {}", e)));
+	}
+
+}
+
+fn io_error(e: std::io::Error)->ChemAppError {
+	return ChemAppError::OtherError(format!("Sweep table I/O error: {}", e));
+}
+
+/// quote a CSV field if needed
+fn csv_field(text: &str)->String {
+	if text.contains(',') || text.contains('"') || text.contains('\n') || text.contains('\r') {
+		return format!("\"{}\"", text.replace('"', "\"\""));
+	}
+	return text.to_string();
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+/// A builder of a grid sweep: `GridSweep::new().axis(Axis::Temperature(linspace(1000.0, 2000.0, 11))).axis(Axis::Composition("SiO2".to_string(), linspace(0.0, 1.0, 21))).output(Output::System(ResultQuantity::H)).composition(&x).run(&calculator)?`.
+/// Without a pressure axis or a fixed pressure the current ChemApp default applies.
+#[derive(Debug,Clone)]
+pub struct GridSweep {
+	axes        : Vec<Axis>,
+	outputs     : Vec<Output>,
+	composition : Option<DVector<f64>>,
+	temperature : Option<f64>,
+	pressure    : Option<f64>,
+	tolerance   : f64,
+}
+
+impl Default for GridSweep {
+	fn default()->GridSweep {
+		return GridSweep {
+			axes        : Vec::new(),
+			outputs     : Vec::new(),
+			composition : None,
+			temperature : None,
+			pressure    : None,
+			tolerance   : 1e-12,
+		};
+	}
+}
+
+/// The sweep with the names replaced by indices.
+#[derive(Debug,Clone)]
+struct Resolved {
+	/// per axis: `None` for T and P, the basis index for compositions
+	axes    : Vec<Option<usize>>,
+	outputs : Vec<(ResultQuantity,usize,usize)>,
+	base    : DVector<f64>,
+}
+
+impl GridSweep {
+
+	/// An empty sweep.
+	pub fn new()->GridSweep {
+		return GridSweep::default();
+	}
+
+	/// add an axis (the last axis added varies fastest)
+	pub fn axis(mut self, axis: Axis)->Self {
+		self.axes.push(axis);
+		return self;
+	}
+
+	/// add an output column
+	pub fn output(mut self, output: Output)->Self {
+		self.outputs.push(output);
+		return self;
+	}
+
+	/// base composition in the transform basis (zero without it)
+	pub fn composition<D: Dim, S: Storage<f64,D>>(mut self, composition: &Vector<f64,D,S>)->Self {
+		self.composition = Some(DVector::from_iterator(composition.len(), composition.iter().cloned()));
+		return self;
+	}
+
+	/// temperature without a temperature axis
+	pub fn temperature(mut self, value: f64)->Self {
+		self.temperature = Some(value);
+		return self;
+	}
+
+	/// pressure without a pressure axis
+	pub fn pressure(mut self, value: f64)->Self {
+		self.pressure = Some(value);
+		return self;
+	}
+
+	/// equilibrium amount above which a phase counts as stable in the assemblage column
+	pub fn amount_tolerance(mut self, tolerance: f64)->Self {
+		self.tolerance = tolerance;
+		return self;
+	}
+
+	/// the axes
+	pub fn axes(&self)->&[Axis] {
+		return &self.axes;
+	}
+
+	/// the outputs
+	pub fn outputs(&self)->&[Output] {
+		return &self.outputs;
+	}
+
+	/// number of grid points
+	pub fn len(&self)->usize {
+		return self.axes.iter().map(|a| a.values().len()).product();
+	}
+
+	/// `true` if the grid has no points
+	pub fn is_empty(&self)->bool {
+		return self.len() == 0;
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	/// Run the grid on one calculator.
+	pub fn run<B: ChemAppBackend>(&self, calculator: &Calculator<B>)->Result<SweepTable,ChemAppError>{
+		let resolved = self.resolve(calculator)?;
+		let points = self.points();
+		let results : Vec<Result<(Vec<f64>,Assemblage),ChemAppError>> = points.iter().map(|point| self.point(calculator, &resolved, point)).collect();
+		return Ok(self.table(points, results));
+	}
+
+	/// Run the grid in parallel on the calculators of a pool (all with the same transform).
+	pub fn run_pool(&self, pool: &mut CalculatorPool)->Result<SweepTable,ChemAppError>{
+		let resolved = self.resolve(&pool.calculators()[0])?;
+		let points = self.points();
+		let results = pool.map(&points, |calculator, point| self.point(calculator, &resolved, point));
+		return Ok(self.table(points, results));
+	}
+
+	/***************************************************************************************************************************************************************************************************************************/
+	/***************************************************************************************************************************************************************************************************************************/
+
+	fn resolve<B: ChemAppBackend>(&self, calculator: &Calculator<B>)->Result<Resolved,ChemAppError>{
+		if self.axes.is_empty() {
+			return Err(ChemAppError::OtherError("A grid sweep needs at least one axis".to_string()));
+		}
+		let basis = &calculator.basis;
+		let base = match &self.composition {
+			Some(composition) if composition.len() != basis.len() => {
+				return Err(ChemAppError::OtherError(format!("Composition has {} entries, the transform basis {:?} has {}", composition.len(), basis, basis.len())));
+			}
+			Some(composition) => composition.clone(),
+			None => DVector::zeros(basis.len()),
+		};
+		let mut axes : Vec<Option<usize>> = Vec::with_capacity(self.axes.len());
+		for axis in self.axes.iter() {
+			axes.push(match axis {
+				Axis::Temperature(_) | Axis::Pressure(_) => None,
+				Axis::Composition(formula, _) => Some(basis.iter().position(|b| b.trim() == formula.trim()).ok_or(ChemAppError::OtherError(format!("{} is not a formula of the transform basis {:?}", formula, basis)))?),
+			});
+		}
+		let mut outputs : Vec<(ResultQuantity,usize,usize)> = Vec::with_capacity(self.outputs.len());
+		for output in self.outputs.iter() {
+			let (quantity, indexp, indexc) = match output {
+				Output::System(q) => (*q, 0, 0),
+				Output::Component(q, c) => (*q, 0, calculator.component_index(c)?),
+				Output::Phase(q, p) => (*q, calculator.phase_index(p)?, 0),
+				Output::Constituent(q, p, c) => {
+					let indexp = calculator.phase_index(p)?;
+					(*q, indexp, calculator.constituent_index(indexp, c)?)
+				}
+			};
+			quantity.check_indices(indexp, indexc)?;
+			outputs.push((quantity, indexp, indexc));
+		}
+		return Ok(Resolved {axes, outputs, base});
+	}
+
+	/// the axis values of every grid point, the last axis varying fastest
+	fn points(&self)->Vec<Vec<f64>> {
+		let mut points : Vec<Vec<f64>> = vec![Vec::new()];
+		for axis in self.axes.iter() {
+			points = points.into_iter().flat_map(|p| axis.values().iter().map(move |v| {let mut q = p.clone(); q.push(*v); q})).collect();
+		}
+		return points;
+	}
+
+	/// the equilibrium and the outputs at one grid point
+	fn point<B: ChemAppBackend>(&self, calculator: &Calculator<B>, resolved: &Resolved, point: &[f64])->Result<(Vec<f64>,Assemblage),ChemAppError>{
+		let engine = &calculator.engine;
+		let mut temperature = self.temperature;
+		let mut pressure = self.pressure;
+		let mut composition = resolved.base.clone();
+		for (k, axis) in self.axes.iter().enumerate() {
+			match axis {
+				Axis::Temperature(_) => {temperature = Some(point[k]);}
+				Axis::Pressure(_) => {pressure = Some(point[k]);}
+				Axis::Composition(..) => {if let Some(i) = resolved.axes[k] {composition[i] = point[k];}}
+			}
+		}
+		let amounts : DVector<f64> = calculator.transform.transform_final2init(&composition, false, false, false).column(0).into_owned();
+		calculator.reset()?;
+		if let Some(t) = temperature {engine.set_condition(Condition::T, 0, 0, t)?;}
+		if let Some(p) = pressure {engine.set_condition(Condition::P, 0, 0, p)?;}
+		for k in 0..amounts.len() {engine.set_condition(Condition::IA, 0, k+1, amounts[k])?;}
+		engine.calculate(TargetVariable::None, 0, 0, (0.0, 0.0))?;
+		let values = resolved.outputs.iter().map(|(quantity, indexp, indexc)| engine.get_result(*quantity, *indexp, *indexc)).collect::<Result<Vec<f64>,ChemAppError>>()?;
+		return Ok((values, Assemblage::current(calculator, self.tolerance)?));
+	}
+
+	fn table(&self, points: Vec<Vec<f64>>, results: Vec<Result<(Vec<f64>,Assemblage),ChemAppError>>)->SweepTable {
+		let mut table = SweepTable {
+			axes    : self.axes.iter().map(|a| a.name()).collect(),
+			outputs : self.outputs.iter().map(|o| o.name()).collect(),
+			inputs  : vec![Vec::with_capacity(points.len()); self.axes.len()],
+			values  : vec![Vec::with_capacity(points.len()); self.outputs.len()],
+			assemblages : Vec::with_capacity(points.len()),
+			status  : Vec::with_capacity(points.len()),
+		};
+		for (point, result) in points.into_iter().zip(results.into_iter()) {
+			for (k, v) in point.into_iter().enumerate() {table.inputs[k].push(v);}
+			match result {
+				Ok((values, assemblage)) => {
+					for (k, v) in values.into_iter().enumerate() {table.values[k].push(v);}
+					table.assemblages.push(Some(assemblage));
+					table.status.push(None);
+				}
+				Err(e) => {
+					for column in table.values.iter_mut() {column.push(f64::NAN);}
+					table.assemblages.push(None);
+					table.status.push(Some(e.to_string()));
+				}
+			}
+		}
+		return table;
+	}
+
+}
+
+/*******************************************************************************************************************************************************************************************************************************/
+/*******************************************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::backend::mock::{calculator};
+
+	fn close(a: &[f64], b: &[f64])->bool {
+		return a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-12);
+	}
+
+	#[test]
+	fn csv_fields_are_quoted_when_needed(){
+		assert_eq!(csv_field("G"), "G");
+		assert_eq!(csv_field("AC(LIQUID,SiO2)"), "\"AC(LIQUID,SiO2)\"");
+		assert_eq!(csv_field("say \"no\""), "\"say \"\"no\"\"\"");
+		assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+		assert_eq!(csv_field("line\r\nbreak"), "\"line\r\nbreak\"");
+		assert_eq!(csv_field(""), "");
+	}
+
+	#[test]
+	fn csv_rows(){
+		let table = SweepTable {
+			axes        : vec!["T".to_string()],
+			outputs     : vec!["AC(LIQUID,Cu)".to_string()],
+			inputs      : vec![vec![1000.0, 2000.0]],
+			values      : vec![vec![0.5, f64::NAN]],
+			assemblages : vec![Some(Assemblage(vec!["FCC".to_string(), "LIQUID".to_string()])), None],
+			status      : vec![None, Some("ChemApp error 704, \"no convergence\"".to_string())],
+		};
+		let mut text : Vec<u8> = Vec::new();
+		table.write_csv(&mut text).unwrap();
+		let lines : Vec<String> = String::from_utf8(text).unwrap().lines().map(|l| l.to_string()).collect();
+		assert_eq!(lines[0], "T,\"AC(LIQUID,Cu)\",phases,status");
+		assert_eq!(lines[1], "1e3,5e-1,FCC + LIQUID,ok");
+		assert_eq!(lines[2], "2e3,NaN,,\"ChemApp error 704, \"\"no convergence\"\"\"");
+	}
+
+	#[test]
+	fn grid_sweep_collects_outputs_and_assemblages(){
+		let calculator = calculator();
+		let sweep = GridSweep::new()
+			.axis(Axis::Temperature(vec![1000.0, 2000.0]))
+			.axis(Axis::Composition("Ni".to_string(), vec![0.25, 0.75]))
+			.output(Output::Phase(ResultQuantity::A, "LIQUID".to_string()))
+			.output(Output::Constituent(ResultQuantity::X, "FCC_A1".to_string(), "Ni".to_string()))
+			.composition(&DVector::from_vec(vec![1.0, 0.0]));
+		let table = sweep.run(&calculator).unwrap();
+		assert_eq!(table.len(), 4);
+		assert_eq!(table.failures(), 0);
+		assert_eq!(table.column("T").unwrap(), &[1000.0, 1000.0, 2000.0, 2000.0]);
+		assert_eq!(table.column("Ni").unwrap(), &[0.25, 0.75, 0.25, 0.75]);
+		assert!(close(table.column("A(LIQUID)").unwrap(), &[0.0, 0.0, 1.25, 1.75]));
+		assert!(close(table.column("X(FCC_A1,Ni)").unwrap(), &[0.2, 0.75/1.75, 0.0, 0.0]));
+		assert_eq!(table.assemblages[0], Some(Assemblage(vec!["FCC_A1".to_string()])));
+		assert_eq!(table.assemblages[3], Some(Assemblage(vec!["LIQUID".to_string()])));
+		assert!(GridSweep::new().axis(Axis::Composition("Co".to_string(), vec![1.0])).run(&calculator).is_err());
+	}
+
+}
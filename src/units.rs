@@ -0,0 +1,260 @@
+// chemapp_rs::units.rs
+
+//! Fortran I/O units. ChemApp addresses files by unit numbers; it only accepts units up to 10 or from 20 on (error 105), while 5 and 6 are the standard input and output.
+//! Every backend owns a `UnitManager` handing out free unit numbers, and `FortranFile` is an open file which is closed with `tqclos` (and its unit released) when dropped.
+
+use std::fmt;
+use std::sync::{Mutex};
+use std::collections::{BTreeSet};
+use serde::{Serialize, Deserialize};
+
+use crate::backend::{ChemAppBackend};
+use crate::error::{ChemAppError};
+
+/// the largest unit number handed out
+const UNIT_MAX : usize = 99;
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// Book-keeping of the Fortran units in use by a backend.
+#[derive(Debug,Default)]
+pub struct UnitManager {
+	in_use: Mutex<BTreeSet<usize>>,
+}
+
+impl UnitManager {
+	
+	pub fn new()->UnitManager {
+		return UnitManager::default();
+	}
+	
+	/// Whether ChemApp accepts `unit` for files (not the standard input/output, not within 11..=19).
+	pub fn is_permitted(unit: usize)->bool {
+		return unit >= 1 && unit <= UNIT_MAX && (unit <= 10 || unit >= 20) && unit != 5 && unit != 6;
+	}
+	
+	/// The first free permitted unit, starting from 20, without taking it (another thread may take it first, see `allocate`).
+	pub fn first_free(&self)->Result<usize,ChemAppError>{
+		let in_use = self.in_use.lock().unwrap();
+		let unit = (20..=UNIT_MAX).chain(1..=10).filter(|unit| UnitManager::is_permitted(*unit)).find(|unit| !in_use.contains(unit));
+		return unit.ok_or(ChemAppError::OtherError("All Fortran units are in use".to_string()));
+	}
+	
+	/// Take the first free permitted unit, starting from 20; looking up and taking the unit is atomic.
+	pub fn allocate(&self)->Result<usize,ChemAppError>{
+		let mut in_use = self.in_use.lock().unwrap();
+		let unit = (20..=UNIT_MAX).chain(1..=10).filter(|unit| UnitManager::is_permitted(*unit)).find(|unit| !in_use.contains(unit));
+		let unit = unit.ok_or(ChemAppError::OtherError("All Fortran units are in use".to_string()))?;
+		in_use.insert(unit);
+		return Ok(unit);
+	}
+	
+	/// Mark a unit chosen by the caller (e.g. for `tqopen`) as used, so that it is not handed out.
+	pub fn reserve(&self, unit: usize)->Result<(),ChemAppError>{
+		if !UnitManager::is_permitted(unit) {
+			return Err(ChemAppError::native(105));
+		}
+		if !self.in_use.lock().unwrap().insert(unit) {
+			return Err(ChemAppError::OtherError(format!("Fortran unit {} is already in use", unit)));
+		}
+		return Ok(());
+	}
+	
+	/// Return a unit to the pool.
+	pub fn release(&self, unit: usize){
+		self.in_use.lock().unwrap().remove(&unit);
+	}
+	
+	/// Reserve `unit` and open a file on it with `open`; the unit is released again if opening fails.
+	pub fn open<F: FnOnce()->Result<(),ChemAppError>>(&self, unit: usize, open: F)->Result<(),ChemAppError>{
+		self.reserve(unit)?;
+		let result = open();
+		if result.is_err() {
+			self.release(unit);
+		}
+		return result;
+	}
+	
+	/// Close the file on `unit` with `close` and release the unit.
+	pub fn close<F: FnOnce()->Result<(),ChemAppError>>(&self, unit: usize, close: F)->Result<(),ChemAppError>{
+		let result = close();
+		self.release(unit);
+		return result;
+	}
+	
+	pub fn is_free(&self, unit: usize)->bool {
+		return UnitManager::is_permitted(unit) && !self.in_use.lock().unwrap().contains(&unit);
+	}
+	
+	/// units currently handed out or reserved
+	pub fn in_use(&self)->Vec<usize> {
+		return self.in_use.lock().unwrap().iter().copied().collect();
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+/// The routine a file is opened with.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Serialize,Deserialize)]
+pub enum FileKind {
+	/// ASCII datafile (`tqopna`)
+	Ascii,
+	/// binary datafile (`tqopnb`)
+	Binary,
+	/// transparent-header datafile (`tqopnt`)
+	Transparent,
+	/// any other file, e.g. for output (`tqopen`)
+	Other,
+}
+
+impl FileKind {
+	
+	/// the ChemApp routine opening this kind of file
+	pub fn routine(&self)->&'static str {
+		match self {
+			Self::Ascii       => {return "tqopna";}
+			Self::Binary      => {return "tqopnb";}
+			Self::Transparent => {return "tqopnt";}
+			Self::Other       => {return "tqopen";}
+		}
+	}
+	
+}
+
+/// A file opened on a unit taken from the backend's `UnitManager`; closed with `tqclos` on drop.
+pub struct FortranFile<'a, B: ChemAppBackend> {
+	backend: &'a B,
+	unit: usize,
+	path: String,
+	open: bool,
+}
+
+impl<'a, B: ChemAppBackend> FortranFile<'a, B> {
+	
+	/// Open `path` on a free unit, taken from the backend's `UnitManager` before the file is opened and released again if opening fails.
+	pub fn open(backend: &'a B, path: &str, kind: FileKind)->Result<FortranFile<'a, B>,ChemAppError>{
+		let unit = backend.units().allocate()?;
+		if let Err(error) = backend.open_unit(path, unit, kind) {
+			backend.units().release(unit);
+			return Err(error);
+		}
+		return Ok(FortranFile {
+			backend,
+			unit,
+			path: path.to_string(),
+			open: true,
+		});
+	}
+	
+	pub fn unit(&self)->usize {
+		return self.unit;
+	}
+	
+	pub fn path(&self)->&str {
+		return &self.path;
+	}
+	
+	/// Close the file, reporting a `tqclos` error (dropping the guard ignores it).
+	pub fn close(mut self)->Result<(),ChemAppError>{
+		self.open = false;
+		return self.backend.tqclos(self.unit);
+	}
+	
+	/// Keep the file open beyond the guard; the caller becomes responsible for `tqclos`.
+	pub fn detach(mut self)->usize {
+		self.open = false;
+		return self.unit;
+	}
+	
+}
+
+impl<'a, B: ChemAppBackend> fmt::Debug for FortranFile<'a, B> {
+	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>)->fmt::Result {
+		return write!(f, "FortranFile {{ unit: {}, path: {:?}, open: {} }}", self.unit, self.path, self.open);
+	}
+	
+}
+
+impl<'a, B: ChemAppBackend> Drop for FortranFile<'a, B> {
+	
+	fn drop(&mut self){
+		if self.open {
+			let _ = self.backend.tqclos(self.unit);
+		}
+	}
+	
+}
+
+/*****************************************************************************************************************************************************************************************************/
+/*****************************************************************************************************************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+	
+	use super::*;
+	use crate::backend::{MockBackend};
+	
+	#[test]
+	fn permitted_units(){
+		let permitted : Vec<usize> = (0..=UNIT_MAX+1).filter(|u| UnitManager::is_permitted(*u)).collect();
+		assert_eq!(permitted.len(), 8 + (UNIT_MAX - 19));
+		for unit in [0, 5, 6, 11, 15, 19, UNIT_MAX+1] {
+			assert!(!UnitManager::is_permitted(unit), "{}", unit);
+		}
+	}
+	
+	#[test]
+	fn allocate_starts_from_20_and_falls_back_to_low_units(){
+		let units = UnitManager::new();
+		assert_eq!(units.allocate().unwrap(), 20);
+		assert_eq!(units.allocate().unwrap(), 21);
+		units.release(20);
+		assert_eq!(units.allocate().unwrap(), 20);
+		for _ in 22..=UNIT_MAX {units.allocate().unwrap();}
+		let low : Vec<usize> = (0..8).map(|_| units.allocate().unwrap()).collect();
+		assert_eq!(low, vec![1, 2, 3, 4, 7, 8, 9, 10]);
+		assert!(units.allocate().is_err());
+		assert!(units.first_free().is_err());
+	}
+	
+	#[test]
+	fn reserved_units_are_skipped(){
+		let units = UnitManager::new();
+		units.reserve(20).unwrap();
+		assert_eq!(units.first_free().unwrap(), 21);
+		assert!(units.reserve(20).is_err());
+		assert_eq!(units.reserve(12).unwrap_err().code(), Some(105));
+		assert!(!units.is_free(20));
+		assert!(!units.is_free(6));
+		assert_eq!(units.in_use(), vec![20]);
+	}
+	
+	#[test]
+	fn open_and_close_keep_the_book(){
+		let units = UnitManager::new();
+		units.open(30, || Ok(())).unwrap();
+		assert!(units.open(30, || Ok(())).is_err());
+		assert!(units.open(31, || Err(ChemAppError::native(106))).is_err());
+		assert_eq!(units.in_use(), vec![30]);
+		assert!(units.close(30, || Err(ChemAppError::native(107))).is_err());
+		assert!(units.in_use().is_empty());
+	}
+	
+	#[test]
+	fn fortran_files_take_and_release_their_units(){
+		let backend = MockBackend::new();
+		assert_eq!(backend.open_file("", FileKind::Ascii).unwrap_err().code(), Some(107));
+		assert!(backend.units().in_use().is_empty());
+		backend.tqopna("user.dat", 20).unwrap();
+		let file = backend.open_file("data.dat", FileKind::Ascii).unwrap();
+		assert_eq!(file.unit(), 21);
+		assert_eq!(backend.units().in_use(), vec![20, 21]);
+		drop(file);
+		assert_eq!(backend.units().in_use(), vec![20]);
+	}
+	
+}